@@ -0,0 +1,584 @@
+//! `deadline`: command-line access to the same repository the desktop app uses.
+//!
+//! Examples:
+//!   deadline add "OS lab 3" --due "2025-11-28 23:59" --zone AoE --difficulty 7 --tag os
+//!   deadline list --sort urgency --tag os
+//!   deadline progress 01KAQW 60
+//!   deadline status 01KAQW waiting
+//!   deadline list --status open --status blocked
+//!   deadline remind 01KAQW 1d 2h
+//!   deadline archive --month 2026-03 --tag os
+//!   deadline workload --weeks 2
+//!   deadline export --output backup.json
+//!   deadline export --format ics --output deadlines.ics
+//!   deadline import course.ics
+//!   deadline --workspace "Job hunt" list
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context};
+use clap::{Parser, Subcommand, ValueEnum};
+use deadline_tracker::config::{self, Config};
+use deadline_tracker::model::datetime::Datetime;
+use deadline_tracker::model::recurrence::Recurrence;
+use deadline_tracker::model::reminder::Lead;
+use deadline_tracker::model::timezone::DueZone;
+use deadline_tracker::model::urgency::UrgencyStrategy;
+use deadline_tracker::model::workload::{daily_load, overloads};
+use deadline_tracker::model::{Deadline, Status};
+use deadline_tracker::persistence::archive::{archive_entries, describe_lateness, month_label, ArchiveSummary};
+use deadline_tracker::persistence::ics::{export_ics, import_ics, IcsComponent};
+use deadline_tracker::persistence::workspace::{create_workspace, list_workspaces, open_workspace};
+use deadline_tracker::persistence::{Backend, HomeworkRecord, HomeworkRepo, NewHomework, Patch, Query, DEFAULT_WORKSPACE};
+
+#[derive(Parser)]
+#[command(name = "deadline", version, about = "Manage DeadlineTracker deadlines from the terminal")]
+struct Cli {
+    /// Directory containing deadlines.json (defaults to $DEADLINE_TRACKER_DATA_DIR, then the
+    /// per-user data directory, like the app)
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+    /// Workspace to use (defaults to the one last open in the app)
+    #[arg(long, global = true)]
+    workspace: Option<String>,
+    /// Storage backend (defaults to $DEADLINE_TRACKER_BACKEND, then the config file, then json)
+    #[arg(long, global = true)]
+    backend: Option<String>,
+    /// Print JSON instead of a table
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List workspaces, or create one
+    Workspaces {
+        /// Name of a workspace to create
+        #[arg(long)]
+        create: Option<String>,
+    },
+    /// Create a deadline
+    Add {
+        name: String,
+        /// Due date "YYYY-MM-DD HH:MM"
+        #[arg(long)]
+        due: String,
+        /// Time zone of the due date (IANA name, +HH:MM, UTC or AoE); defaults to local
+        #[arg(long)]
+        zone: Option<String>,
+        #[arg(long, default_value_t = 5)]
+        difficulty: u8,
+        /// Tag (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Recurrence rule, e.g. "FREQ=WEEKLY;BYDAY=MO"
+        #[arg(long)]
+        repeat: Option<String>,
+    },
+    /// List deadlines (archived ones are left out)
+    List {
+        #[arg(long, value_enum, default_value_t = SortKey::Urgency)]
+        sort: SortKey,
+        /// Only deadlines carrying this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Only deadlines with at least this normalized urgency (0..1)
+        #[arg(long)]
+        min_urgency: Option<f32>,
+        /// Urgency model used for scoring and sorting (defaults to the config file's)
+        #[arg(long)]
+        model: Option<String>,
+        /// Only deadlines with this status (repeatable)
+        #[arg(long = "status")]
+        statuses: Vec<String>,
+    },
+    /// Show one deadline
+    Show { id: String },
+    /// Change fields of a deadline
+    Edit {
+        id: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        due: Option<String>,
+        #[arg(long)]
+        zone: Option<String>,
+        #[arg(long)]
+        difficulty: Option<u8>,
+        /// Replace all tags (repeatable)
+        #[arg(long = "tag")]
+        tags: Option<Vec<String>>,
+        /// Replace the recurrence rule ("" to stop repeating)
+        #[arg(long)]
+        repeat: Option<String>,
+    },
+    /// Set progress (0-100)
+    Progress { id: String, percent: u8 },
+    /// Mark done; recurring deadlines move on to their next occurrence
+    Done { id: String },
+    /// Set the status: open, blocked, waiting, submitted, completed or cancelled
+    Status { id: String, status: String },
+    /// Set how long before the due time to be reminded ("30m", "2h", "1d", "1w"); none turns reminders off
+    Remind {
+        id: String,
+        leads: Vec<String>,
+        /// Go back to the reminders from config.toml
+        #[arg(long, conflicts_with = "leads")]
+        default: bool,
+    },
+    /// List archived deadlines, most recently completed first, with how early or late they were done
+    Archive {
+        /// Only deadlines completed in this month ("YYYY-MM")
+        #[arg(long)]
+        month: Option<String>,
+        /// Only deadlines carrying this tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Show the work due each day for the coming weeks, and the days and weeks over capacity
+    Workload {
+        /// Weeks to look ahead, starting with the current one
+        #[arg(long, default_value_t = 4)]
+        weeks: u32,
+    },
+    /// Move a deadline to the trash
+    Rm { id: String },
+    /// Bring a deleted deadline back (needs the full id)
+    Restore { id: String },
+    /// List deleted deadlines, most recently deleted first
+    Trash,
+    /// Permanently remove a deleted deadline (needs the full id)
+    Purge { id: String },
+    /// Write all deadlines as JSON or iCalendar
+    Export {
+        /// Output file (stdout if omitted)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// Calendar component used by the ics format
+        #[arg(long, value_enum, default_value_t = ComponentKind::Todo)]
+        component: ComponentKind,
+    },
+    /// Import VTODO/VEVENT items from an .ics file, skipping known UIDs
+    Import { file: PathBuf },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Ics,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ComponentKind {
+    Todo,
+    Event,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortKey {
+    Due,
+    Urgency,
+    Progress,
+}
+
+fn parse_due(s: &str) -> anyhow::Result<Datetime> {
+    Datetime::from_string(s.trim()).ok_or_else(|| anyhow!("invalid due date {s:?}, expected \"YYYY-MM-DD HH:MM\""))
+}
+
+fn parse_zone(s: &str) -> anyhow::Result<DueZone> {
+    DueZone::parse(s).ok_or_else(|| anyhow!("unknown time zone {s:?}"))
+}
+
+fn parse_status(s: &str) -> anyhow::Result<Status> {
+    Status::parse(s).ok_or_else(|| {
+        let names: Vec<&str> = Status::ALL.iter().map(Status::as_str).collect();
+        anyhow!("unknown status {s:?}, expected one of {}", names.join(", "))
+    })
+}
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+fn parse_repeat(s: &str) -> anyhow::Result<String> {
+    if s.trim().is_empty() {
+        return Ok(String::new());
+    }
+    let rule = Recurrence::parse(s).ok_or_else(|| anyhow!("invalid recurrence rule {s:?}"))?;
+    Ok(rule.to_rrule())
+}
+
+/// Accept a full uid or an unambiguous prefix of a non-deleted one.
+fn resolve(repo: &Arc<dyn HomeworkRepo>, id: &str) -> anyhow::Result<HomeworkRecord> {
+    if let Some(rec) = repo.get(id)? {
+        return Ok(rec);
+    }
+    let needle = id.to_ascii_uppercase();
+    let mut matches: Vec<HomeworkRecord> =
+        repo.list()?.into_iter().filter(|r| r.uid.starts_with(&needle)).collect();
+    match matches.len() {
+        0 => bail!("no deadline with id {id:?}"),
+        1 => Ok(matches.remove(0)),
+        n => bail!("id {id:?} is ambiguous ({n} matches)"),
+    }
+}
+
+fn scored(rec: &HomeworkRecord, strategy: &UrgencyStrategy) -> Deadline {
+    let mut d = rec.to_deadline();
+    d.update_urgency(strategy);
+    d
+}
+
+fn print_table(rows: &[(HomeworkRecord, Deadline)]) {
+    println!(
+        "{:<26} {:<28} {:<17} {:>4} {:>5} {:<9} {:>7}  TAGS",
+        "ID", "NAME", "DUE (LOCAL)", "DIFF", "PROG", "STATUS", "URGENCY"
+    );
+    for (rec, d) in rows {
+        let mut name = d.name.clone();
+        if name.chars().count() > 28 {
+            name = name.chars().take(27).collect::<String>() + "…";
+        }
+        println!(
+            "{:<26} {:<28} {:<17} {:>4} {:>4}% {:<9} {:>7.2}  {}",
+            rec.uid,
+            name,
+            d.local_due().to_string(),
+            d.difficulty,
+            d.progress,
+            d.status.as_str(),
+            d.urgency,
+            d.tags.join(", ")
+        );
+    }
+}
+
+fn print_record(rec: &HomeworkRecord, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(rec)?);
+        return Ok(());
+    }
+    let d = rec.to_deadline();
+    println!("id:          {}", rec.uid);
+    println!("name:        {}", d.name);
+    println!("due:         {} (local {})", d.original_due_text(), d.local_due().to_string());
+    println!("difficulty:  {}", d.difficulty);
+    println!("progress:    {}%", d.progress);
+    println!("status:      {}", d.status.as_str());
+    if let Some(ts) = rec.completed_at {
+        println!("completed:   {}", format_timestamp(ts));
+    }
+    if let Some(ts) = rec.cancelled_at {
+        println!("cancelled:   {}", format_timestamp(ts));
+    }
+    println!("tags:        {}", d.tags.join(", "));
+    if let Some(rule) = &d.recurrence {
+        println!("repeats:     {}", rule.describe());
+    }
+    match &d.reminders {
+        Some(leads) => println!("reminders:   {}", Lead::format_list(leads)),
+        None => println!("reminders:   default"),
+    }
+    for m in &d.milestones {
+        println!("milestone:   [{}] {} (weight {})", if m.done { "x" } else { " " }, m.title, m.weight);
+    }
+    if rec.deleted {
+        println!("deleted:     yes");
+    }
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config_path = config::config_path();
+    let settings = Config::load(&config_path).with_context(|| format!("cannot read {}", config_path.display()))?;
+    let backend = match &cli.backend {
+        Some(s) => Backend::parse(s).ok_or_else(|| anyhow!("unknown backend {s:?}"))?,
+        None => Backend::env_override().unwrap_or(settings.backend),
+    };
+    let dir = config::data_dir(cli.data_dir);
+    let cwd = std::env::current_dir().context("cannot determine current directory")?;
+    for file in config::migrate_legacy_data(&dir, &cwd)? {
+        eprintln!("Copied {} to {}", file.display(), dir.display());
+    }
+    let workspace = cli.workspace.or(settings.workspace).unwrap_or_else(|| DEFAULT_WORKSPACE.to_string());
+
+    if let Command::Workspaces { create } = &cli.command {
+        if let Some(name) = create {
+            let name = create_workspace(&dir, name)?;
+            if !cli.json {
+                println!("Created workspace {name}");
+            }
+        }
+        let names = list_workspaces(&dir);
+        if cli.json {
+            println!("{}", serde_json::to_string_pretty(&names)?);
+        } else {
+            for name in names {
+                let marker = if name == workspace { "*" } else { " " };
+                println!("{marker} {name}");
+            }
+        }
+        return Ok(());
+    }
+    if !list_workspaces(&dir).contains(&workspace) {
+        bail!("no workspace {workspace:?}; create it with `deadline workspaces --create {workspace:?}`");
+    }
+    let repo = open_workspace(&dir, &workspace, backend)?;
+
+    match cli.command {
+        Command::Workspaces { .. } => unreachable!("handled above"),
+        Command::Add { name, due, zone, difficulty, tags, repeat } => {
+            let due_zone = match zone {
+                Some(z) => parse_zone(&z)?,
+                None => DueZone::local(),
+            };
+            let rec = repo.create(NewHomework {
+                name,
+                due_text: parse_due(&due)?.to_string(),
+                due_zone: due_zone.to_string(),
+                difficulty,
+                progress: 0,
+                tags,
+                milestones: Vec::new(),
+                recurrence: repeat.as_deref().map(parse_repeat).transpose()?.unwrap_or_default(),
+                external_uid: String::new(),
+                status: Status::Open,
+                reminders: None,
+            })?;
+            print_record(&rec, cli.json)?;
+        }
+        Command::List { sort, tag, min_urgency, model, statuses } => {
+            let strategy = match model {
+                Some(model) => UrgencyStrategy::parse(&model).ok_or_else(|| anyhow!("unknown urgency model {model:?}"))?,
+                None => settings.urgency,
+            };
+            let query = Query {
+                tags: tag.into_iter().collect(),
+                status: statuses.iter().map(|s| parse_status(s)).collect::<anyhow::Result<_>>()?,
+                archive: settings.archive_after_days.exclude(chrono::Utc::now().timestamp()),
+                ..Default::default()
+            };
+            let mut rows: Vec<(HomeworkRecord, Deadline)> = repo
+                .query(&query)?
+                .into_iter()
+                .map(|r| {
+                    let d = scored(&r, &strategy);
+                    (r, d)
+                })
+                .filter(|(_, d)| min_urgency.is_none_or(|m| d.urgency >= m))
+                .collect();
+            match sort {
+                SortKey::Due => rows.sort_by(|a, b| a.1.local_due().cmp(&b.1.local_due())),
+                SortKey::Urgency => rows.sort_by(|a, b| b.1.urgency.total_cmp(&a.1.urgency)),
+                SortKey::Progress => rows.sort_by_key(|(_, d)| d.progress),
+            }
+            if cli.json {
+                let records: Vec<&HomeworkRecord> = rows.iter().map(|(r, _)| r).collect();
+                println!("{}", serde_json::to_string_pretty(&records)?);
+            } else {
+                print_table(&rows);
+            }
+        }
+        Command::Show { id } => {
+            let rec = resolve(&repo, &id)?;
+            print_record(&rec, cli.json)?;
+        }
+        Command::Edit { id, name, due, zone, difficulty, tags, repeat } => {
+            let rec = resolve(&repo, &id)?;
+            let patch = Patch {
+                name,
+                due_text: due.as_deref().map(parse_due).transpose()?.map(|d| d.to_string()),
+                due_zone: zone.as_deref().map(parse_zone).transpose()?.map(|z| z.to_string()),
+                difficulty,
+                tags,
+                recurrence: repeat.as_deref().map(parse_repeat).transpose()?,
+                ..Default::default()
+            };
+            let rec = repo.patch(&rec.uid, patch)?;
+            print_record(&rec, cli.json)?;
+        }
+        Command::Progress { id, percent } => {
+            let rec = resolve(&repo, &id)?;
+            if !rec.milestones.is_empty() {
+                bail!("{} tracks progress by its milestones; tick them off in the app instead", rec.uid);
+            }
+            let patch = Patch { progress: Some(percent.min(100)), ..Default::default() };
+            let rec = repo.patch(&rec.uid, patch)?;
+            print_record(&rec, cli.json)?;
+        }
+        Command::Done { id } => {
+            let mut rec = resolve(&repo, &id)?;
+            let mut d = rec.to_deadline();
+            if d.mark_done() && !cli.json {
+                println!("Next occurrence: {}", d.original_due_text());
+            }
+            rec.apply_deadline(&d, chrono::Utc::now().timestamp())?;
+            let rec = repo.update(rec)?;
+            print_record(&rec, cli.json)?;
+        }
+        Command::Status { id, status } => {
+            let rec = resolve(&repo, &id)?;
+            let patch = Patch { status: Some(parse_status(&status)?), ..Default::default() };
+            let rec = repo.patch(&rec.uid, patch)?;
+            print_record(&rec, cli.json)?;
+        }
+        Command::Remind { id, leads, default } => {
+            let rec = resolve(&repo, &id)?;
+            let leads = if default { None } else { Some(Lead::parse_list(&leads.join(",")).map_err(|e| anyhow!(e))?) };
+            let rec = repo.patch(&rec.uid, Patch { reminders: Some(leads), ..Default::default() })?;
+            print_record(&rec, cli.json)?;
+        }
+        Command::Archive { month, tag } => {
+            let query = Query {
+                tags: tag.into_iter().collect(),
+                archive: settings.archive_after_days.only(chrono::Utc::now().timestamp()),
+                ..Default::default()
+            };
+            let entries: Vec<_> = archive_entries(repo.query(&query)?, &DueZone::Local)
+                .into_iter()
+                .filter(|e| month.as_ref().is_none_or(|m| e.month_key() == *m))
+                .collect();
+            if cli.json {
+                let records: Vec<&HomeworkRecord> = entries.iter().map(|e| &e.record).collect();
+                println!("{}", serde_json::to_string_pretty(&records)?);
+                return Ok(());
+            }
+            let mut current_month = String::new();
+            for e in &entries {
+                if e.month_key() != current_month {
+                    current_month = e.month_key();
+                    println!("{}", month_label(&current_month));
+                }
+                println!(
+                    "  {:<26} {:<28} due {}  done {}  {}",
+                    e.record.uid,
+                    e.record.name,
+                    e.planned.to_string(),
+                    e.completed.to_string(),
+                    describe_lateness(e.lateness_minutes)
+                );
+            }
+            let summary = ArchiveSummary::of(&entries);
+            match summary.median_lateness_minutes {
+                Some(median) => println!(
+                    "{} completed, {} on time, typically {}",
+                    summary.count,
+                    summary.on_time,
+                    describe_lateness(median)
+                ),
+                None => println!("Nothing archived"),
+            }
+        }
+        Command::Workload { weeks } => {
+            let query = Query { archive: settings.archive_after_days.exclude(chrono::Utc::now().timestamp()), ..Default::default() };
+            let deadlines: Vec<Deadline> = repo.query(&query)?.iter().map(|r| r.to_deadline()).collect();
+            let today = chrono::Local::now().date_naive();
+            let from = settings.week_start.week_of(today);
+            let to = from + chrono::Duration::weeks(i64::from(weeks.max(1))) - chrono::Duration::days(1);
+            let loads = daily_load(&deadlines, from, to);
+            if cli.json {
+                let days: Vec<_> = loads
+                    .iter()
+                    .map(|day| {
+                        let ids: Vec<&str> = day.deadlines.iter().map(|d| d.id.as_str()).collect();
+                        serde_json::json!({ "date": day.date.to_string(), "hours": day.hours, "deadlines": ids })
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&days)?);
+                return Ok(());
+            }
+            let capacity = settings.workload.daily_capacity();
+            for week in loads.chunks(7) {
+                let hours: f32 = week.iter().map(|d| d.hours).sum();
+                println!("Week of {}  {:.1}h of {:.0}h", week[0].date.format("%a, %b %-d"), hours, capacity * week.len() as f32);
+                for day in week.iter().filter(|d| !d.deadlines.is_empty()) {
+                    let names: Vec<&str> = day.deadlines.iter().map(|d| d.name.as_str()).collect();
+                    let flag = if day.hours > capacity { "!" } else { " " };
+                    println!("  {flag} {}  {:>5.1}h  {}", day.date.format("%a %d"), day.hours, names.join(", "));
+                }
+            }
+            let found = overloads(&loads, capacity, today);
+            if found.is_empty() {
+                println!("Nothing over capacity ({capacity:.0}h a day)");
+            }
+            for o in &found {
+                let span = if o.is_week() { format!("Week of {}", o.from.format("%a, %b %-d")) } else { o.from.format("%a, %b %-d").to_string() };
+                println!("{span}: {:.1}h due, {:.0}h fits", o.hours, o.capacity);
+                for (d, start) in &o.start_earlier {
+                    println!("  start '{}' by {}", d.name, start.format("%a, %b %-d"));
+                }
+            }
+        }
+        Command::Rm { id } => {
+            let rec = resolve(&repo, &id)?;
+            repo.delete(&rec.uid)?;
+            if !cli.json {
+                println!("Deleted {} ({})", rec.name, rec.uid);
+            }
+        }
+        Command::Restore { id } => {
+            let rec = repo.restore(&id)?;
+            print_record(&rec, cli.json)?;
+        }
+        Command::Trash => {
+            let records = repo.list_deleted()?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&records)?);
+            } else {
+                println!("{:<26} {:<28} {:<17}  DELETED", "ID", "NAME", "DUE");
+                for rec in &records {
+                    let deleted = rec.deleted_time().map(format_timestamp).unwrap_or_default();
+                    println!("{:<26} {:<28} {:<17}  {}", rec.uid, rec.name, rec.due_text, deleted);
+                }
+            }
+        }
+        Command::Purge { id } => {
+            let rec = repo.get(&id)?.ok_or_else(|| anyhow!("no deadline with id {id:?}"))?;
+            if !rec.deleted {
+                bail!("{} is not in the trash; delete it with `rm` first", rec.uid);
+            }
+            repo.purge(&rec.uid)?;
+            if !cli.json {
+                println!("Purged {} ({})", rec.name, rec.uid);
+            }
+        }
+        Command::Export { output, format, component } => {
+            let records: Vec<HomeworkRecord> = repo.list()?.into_iter().filter(|r| !r.deleted).collect();
+            let text = match format {
+                ExportFormat::Json => serde_json::to_string_pretty(&records)?,
+                ExportFormat::Ics => {
+                    let component = match component {
+                        ComponentKind::Todo => IcsComponent::Todo,
+                        ComponentKind::Event => IcsComponent::Event,
+                    };
+                    export_ics(&records, component)
+                }
+            };
+            match output {
+                Some(path) => std::fs::write(&path, text).with_context(|| format!("writing {}", path.display()))?,
+                None => println!("{text}"),
+            }
+        }
+        Command::Import { file } => {
+            let text = std::fs::read_to_string(&file).with_context(|| format!("reading {}", file.display()))?;
+            let summary = import_ics(repo.as_ref(), &text)?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            } else {
+                println!(
+                    "Imported {}, skipped {} duplicate(s) and {} item(s) without a due date",
+                    summary.imported, summary.duplicates, summary.skipped
+                );
+            }
+        }
+    }
+    Ok(())
+}
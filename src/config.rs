@@ -0,0 +1,321 @@
+//! User configuration (config.toml) and where deadlines are stored on disk.
+//!
+//! Data lives in the per-user data directory (`~/.local/share/deadline-tracker` on Linux,
+//! following XDG), unless `--data-dir` or `$DEADLINE_TRACKER_DATA_DIR` point elsewhere.
+//! The config file lives in the per-user config directory, or at `$DEADLINE_TRACKER_CONFIG`.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::model::reminder::ReminderSettings;
+use crate::model::urgency::UrgencyStrategy;
+use crate::model::workload::WorkloadSettings;
+use crate::persistence::archive::ArchiveDelay;
+use crate::persistence::json::DATA_FILE_NAME;
+use crate::persistence::sqlite::DB_FILE_NAME;
+use crate::persistence::{Backend, TrashRetention};
+
+/// Directory name under the platform's data and config directories.
+pub const APP_DIR_NAME: &str = "deadline-tracker";
+pub const DATA_DIR_ENV: &str = "DEADLINE_TRACKER_DATA_DIR";
+pub const CONFIG_ENV: &str = "DEADLINE_TRACKER_CONFIG";
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// First column of the calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Sunday,
+    Monday,
+}
+
+impl WeekStart {
+    pub const ALL: [WeekStart; 2] = [WeekStart::Sunday, WeekStart::Monday];
+
+    /// Stable identifier for settings storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeekStart::Sunday => "sunday",
+            WeekStart::Monday => "monday",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|w| w.as_str() == s.trim())
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WeekStart::Sunday => "Sunday",
+            WeekStart::Monday => "Monday",
+        }
+    }
+
+    /// Column of `day` in a week starting on this day (0-based).
+    pub fn column(&self, day: Weekday) -> u32 {
+        match self {
+            WeekStart::Sunday => day.num_days_from_sunday(),
+            WeekStart::Monday => day.num_days_from_monday(),
+        }
+    }
+
+    /// The seven weekdays in column order.
+    pub fn days(&self) -> [Weekday; 7] {
+        let first = match self {
+            WeekStart::Sunday => Weekday::Sun,
+            WeekStart::Monday => Weekday::Mon,
+        };
+        let mut days = [first; 7];
+        for i in 1..7 {
+            days[i] = days[i - 1].succ();
+        }
+        days
+    }
+
+    /// First day of the week containing `date`.
+    pub fn week_of(&self, date: NaiveDate) -> NaiveDate {
+        date - Duration::days(i64::from(self.column(date.weekday())))
+    }
+}
+
+/// How `Home` shows deadlines; the last choice is remembered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HomeView {
+    #[default]
+    List,
+    Month,
+    Week,
+    Agenda,
+}
+
+impl HomeView {
+    pub const ALL: [HomeView; 4] = [HomeView::List, HomeView::Month, HomeView::Week, HomeView::Agenda];
+
+    /// Stable identifier for settings storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HomeView::List => "list",
+            HomeView::Month => "month",
+            HomeView::Week => "week",
+            HomeView::Agenda => "agenda",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|v| v.as_str() == s.trim())
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HomeView::List => "📝 List",
+            HomeView::Month => "📅 Month",
+            HomeView::Week => "🗓 Week",
+            HomeView::Agenda => "☰ Agenda",
+        }
+    }
+}
+
+/// Colour scheme; `System` follows the OS preference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    /// Stable identifier for settings storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.as_str() == s.trim())
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::System => "Same as system",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+        }
+    }
+}
+
+/// Contents of config.toml. Missing keys take their defaults, so a partial file is fine.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Storage backend; `$DEADLINE_TRACKER_BACKEND` still takes precedence
+    pub backend: Backend,
+    pub urgency: UrgencyStrategy,
+    pub week_start: WeekStart,
+    pub theme: Theme,
+    /// Last view chosen on the home page
+    pub home_view: HomeView,
+    /// Days a completed deadline stays in the main list before it is archived, or "never"
+    pub archive_after_days: ArchiveDelay,
+    /// Days deleted deadlines stay in the trash before they are purged, or "never"
+    pub trash_retention: TrashRetention,
+    pub reminders: ReminderSettings,
+    pub workload: WorkloadSettings,
+    /// Last used workspace; the default one if unset
+    pub workspace: Option<String>,
+    /// Whether the "All workspaces" view was open
+    pub all_workspaces: bool,
+}
+
+impl Config {
+    /// Read the config at `path`; a missing file gives the defaults.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(toml::from_str(&text)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut tmp_name = path.as_os_str().to_owned();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+
+        // Write beside the target and rename over it, so a crash never leaves half a config.
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(toml::to_string_pretty(self)?.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&tmp_path, path)?;
+        // Persist the rename itself; not supported on every platform, so best effort.
+        if let Some(dir) = path.parent() {
+            if let Ok(d) = File::open(dir) {
+                let _ = d.sync_all();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where config.toml lives: `$DEADLINE_TRACKER_CONFIG`, then the per-user config directory.
+pub fn config_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(CONFIG_ENV) {
+        return PathBuf::from(path);
+    }
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR_NAME)
+        .join(CONFIG_FILE_NAME)
+}
+
+/// Where deadlines are stored: `flag` (from `--data-dir`), then `$DEADLINE_TRACKER_DATA_DIR`,
+/// then the per-user data directory.
+pub fn data_dir(flag: Option<PathBuf>) -> PathBuf {
+    flag.or_else(|| std::env::var_os(DATA_DIR_ENV).map(PathBuf::from))
+        .or_else(|| dirs::data_dir().map(|d| d.join(APP_DIR_NAME)))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Earlier versions kept their data in the working directory. If `data_dir` holds no data
+/// yet, copy any data files found in `legacy_dir` over. The originals are left in place.
+/// Returns the files that were copied.
+pub fn migrate_legacy_data(data_dir: &Path, legacy_dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let files = [DATA_FILE_NAME, DB_FILE_NAME];
+    if files.iter().any(|f| data_dir.join(f).exists()) {
+        return Ok(Vec::new());
+    }
+    if let (Ok(a), Ok(b)) = (data_dir.canonicalize(), legacy_dir.canonicalize()) {
+        if a == b {
+            return Ok(Vec::new());
+        }
+    }
+    let mut copied = Vec::new();
+    for name in files {
+        let from = legacy_dir.join(name);
+        if from.is_file() {
+            fs::create_dir_all(data_dir)?;
+            fs::copy(&from, data_dir.join(name))?;
+            copied.push(from);
+        }
+    }
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::reminder::DEFAULT_LEADS;
+    use crate::persistence::conformance::TempDir;
+
+    #[test]
+    fn partial_config_falls_back_to_defaults() {
+        let config: Config = toml::from_str("week_start = \"monday\"\nbackend = \"sqlite\"\n").unwrap();
+        assert_eq!(
+            config,
+            Config { backend: Backend::Sqlite, week_start: WeekStart::Monday, ..Config::default() }
+        );
+
+        let dir = TempDir::new();
+        let path = dir.0.join("nested").join(CONFIG_FILE_NAME);
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+        let config = Config {
+            urgency: UrgencyStrategy::Effort,
+            theme: Theme::Dark,
+            trash_retention: TrashRetention { days: None },
+            ..config
+        };
+        assert_eq!(Config::default().trash_retention, TrashRetention::default());
+        assert_eq!(config.reminders.leads, DEFAULT_LEADS);
+        assert_eq!(config.workload.daily_capacity_hours, 6);
+        config.save(&path).unwrap();
+        assert_eq!(Config::load(&path).unwrap(), config);
+        assert!(fs::read_to_string(&path).unwrap().contains("trash_retention = \"never\""));
+        assert!(!path.with_extension("toml.tmp").exists());
+    }
+
+    #[test]
+    fn legacy_data_is_copied_only_into_an_empty_data_dir() {
+        let legacy = TempDir::new();
+        let data = TempDir::new();
+        let data_dir = data.0.join(APP_DIR_NAME);
+        fs::write(legacy.0.join(DATA_FILE_NAME), "[]").unwrap();
+
+        let copied = migrate_legacy_data(&data_dir, &legacy.0).unwrap();
+        assert_eq!(copied, vec![legacy.0.join(DATA_FILE_NAME)]);
+        assert!(data_dir.join(DATA_FILE_NAME).exists());
+        assert!(legacy.0.join(DATA_FILE_NAME).exists());
+
+        assert!(migrate_legacy_data(&data_dir, &legacy.0).unwrap().is_empty());
+        assert!(migrate_legacy_data(&legacy.0, &legacy.0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn monday_weeks_shift_columns() {
+        assert_eq!(WeekStart::Monday.days()[0], Weekday::Mon);
+        assert_eq!(WeekStart::Monday.days()[6], Weekday::Sun);
+        assert_eq!(WeekStart::Monday.column(Weekday::Sun), 6);
+        assert_eq!(WeekStart::Sunday.column(Weekday::Sun), 0);
+
+        let wednesday = NaiveDate::from_ymd_opt(2025, 11, 26).unwrap();
+        assert_eq!(WeekStart::Monday.week_of(wednesday), NaiveDate::from_ymd_opt(2025, 11, 24).unwrap());
+        assert_eq!(WeekStart::Sunday.week_of(wednesday), NaiveDate::from_ymd_opt(2025, 11, 23).unwrap());
+        assert_eq!(WeekStart::Sunday.week_of(NaiveDate::from_ymd_opt(2025, 11, 23).unwrap()).day(), 23);
+    }
+}
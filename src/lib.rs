@@ -0,0 +1,9 @@
+//! Core of DeadlineTracker shared by the desktop app and the `deadline` command-line tool.
+//! Contains no UI code, so it builds without any Dioxus platform.
+
+/// Define a model module that contains the data structures for our app.
+pub mod model;
+/// Persistence layer (backend-only): repository trait + Memory/JSON/SQLite implementations.
+pub mod persistence;
+/// Data directory resolution and the user's config.toml.
+pub mod config;
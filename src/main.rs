@@ -1,19 +1,25 @@
 // The dioxus prelude contains a ton of common items used in dioxus apps. It's a good idea to import wherever you
 // need dioxus
 use dioxus::prelude::*;
+use clap::Parser;
 use std::path::PathBuf;
-use persistence::init_repo;
+use config::Config;
+use persistence::workspace::{list_workspaces, open_all_workspaces, open_workspace};
+use persistence::{ArchiveDelay, Backend, HistoryRepo, HomeworkRepo, TrashRetention, DEFAULT_WORKSPACE};
+use std::sync::Arc;
+use std::time::Duration;
 
-use views::{Blog, Home, Navbar};
+use views::{Archive, Blog, Home, Navbar, Trash};
 
+// The model and persistence layers live in the library crate so the CLI can share them.
+use deadline_tracker::{config, model, persistence};
 
-/// Define a model module that contains the data structures for our app.
-mod model;
-/// Persistence layer (backend-only): repository trait + Memory/SQLite implementations.
-mod persistence;
 /// Define a views module that contains the UI for all Layouts and Routes for our app.
 mod views;
 
+#[cfg(feature = "desktop")]
+mod notifier;
+
 /// The Route enum is used to define the structure of internal routes in our app. All route enums need to derive
 /// the [`Routable`] trait, which provides the necessary methods for the router to work.
 /// 
@@ -29,6 +35,12 @@ enum Route {
         // the component for that route will be rendered. The component name that is rendered defaults to the variant name.
         #[route("/")]
         Home {},
+        // Soft-deleted deadlines, with restore and permanent purge.
+        #[route("/trash")]
+        Trash {},
+        // Completed deadlines past the archive delay, by month and tag.
+        #[route("/archive")]
+        Archive {},
         // The route attribute can include dynamic parameters that implement [`std::str::FromStr`] and [`std::fmt::Display`] with the `:` syntax.
         // In this case, id will match any integer like `/blog/123` or `/blog/-456`.
         #[route("/blog/:id")]
@@ -42,12 +54,59 @@ enum Route {
 const FAVICON: Asset = asset!("/assets/favicon.ico");
 // The asset macro also minifies some assets like CSS and JS to make bundled smaller
 const MAIN_CSS: Asset = asset!("/assets/styling/main.css");
+/// How often to check the data file for edits made by scripts or sync tools.
+const EXTERNAL_POLL_INTERVAL: Duration = Duration::from_secs(2);
 // const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 
+/// Command-line options of the desktop app.
+#[derive(Parser)]
+#[command(name = "DeadlineTracker", version)]
+struct Args {
+    /// Directory holding the deadlines (defaults to $DEADLINE_TRACKER_DATA_DIR, then the per-user data directory)
+    #[arg(long)]
+    data_dir: Option<PathBuf>,
+}
+
+/// Settings resolved before the UI starts, provided to `App` as context.
+#[derive(Clone)]
+struct Startup {
+    data_dir: PathBuf,
+    config_path: PathBuf,
+    config: Config,
+}
+
+/// The workspace the views show, or all of them merged with `name` receiving new deadlines.
+#[derive(Clone, Debug, PartialEq)]
+struct OpenWorkspace {
+    name: String,
+    merged: bool,
+}
+
 fn main() {
-    // The `launch` function is the main entry point for a dioxus app. It takes a component and renders it with the platform feature
-    // you have enabled
-    dioxus::launch(App);
+    let args = Args::parse();
+    let config_path = config::config_path();
+    let config = Config::load(&config_path).unwrap_or_else(|e| {
+        eprintln!("Ignoring {}: {e}", config_path.display());
+        Config::default()
+    });
+    let data_dir = config::data_dir(args.data_dir);
+    // Earlier versions kept deadlines.json in the working directory.
+    if let Ok(cwd) = std::env::current_dir() {
+        match config::migrate_legacy_data(&data_dir, &cwd) {
+            Ok(files) => {
+                for file in files {
+                    eprintln!("Copied {} to {}", file.display(), data_dir.display());
+                }
+            }
+            Err(e) => eprintln!("Could not copy old data from {}: {e}", cwd.display()),
+        }
+    }
+
+    // The launch builder is the main entry point for a dioxus app. It takes a component and renders it with the platform
+    // feature you have enabled
+    dioxus::LaunchBuilder::new()
+        .with_context(Startup { data_dir, config_path, config })
+        .launch(App);
 }
 
 /// App is the main component of our app. Components are the building blocks of dioxus apps. Each component is a function
@@ -56,11 +115,65 @@ fn main() {
 /// Components should be annotated with `#[component]` to support props, better error messages, and autocomplete
 #[component]
 fn App() -> Element {
-    use_context_provider(|| {
-        let dir = std::env::current_dir().unwrap_or(PathBuf::from("."));
-        init_repo(Some(dir)).expect("failed to init repo")
+    let startup = use_context::<Startup>();
+    let urgency = use_context_provider(|| Signal::new(startup.config.urgency));
+    let week_start = use_context_provider(|| Signal::new(startup.config.week_start));
+    let theme = use_context_provider(|| Signal::new(startup.config.theme));
+    let home_view = use_context_provider(|| Signal::new(startup.config.home_view));
+    let backend = use_context_provider(|| Signal::new(startup.config.backend));
+    let archive_delay = use_context_provider(|| Signal::<ArchiveDelay>::new(startup.config.archive_after_days));
+    let reminders = use_context_provider(|| Signal::new(startup.config.reminders.clone()));
+    let workload = use_context_provider(|| Signal::new(startup.config.workload));
+    let trash_retention = use_context_provider(|| Signal::<TrashRetention>::new(startup.config.trash_retention));
+    // A workspace deleted since the last run falls back to the default one.
+    let open = use_context_provider(|| {
+        let name = startup
+            .config
+            .workspace
+            .clone()
+            .filter(|name| list_workspaces(&startup.data_dir).contains(name))
+            .unwrap_or_else(|| DEFAULT_WORKSPACE.to_string());
+        Signal::new(OpenWorkspace { name, merged: startup.config.all_workspaces })
+    });
+
+    // Write settings back to config.toml when they change.
+    let mut saved = use_signal(|| startup.config.clone());
+    use_effect({
+        let config_path = startup.config_path.clone();
+        move || {
+            let config = Config {
+                backend: backend(),
+                urgency: urgency(),
+                week_start: week_start(),
+                theme: theme(),
+                home_view: home_view(),
+                archive_after_days: archive_delay(),
+                trash_retention: trash_retention(),
+                reminders: reminders(),
+                workload: workload(),
+                workspace: Some(open().name),
+                all_workspaces: open().merged,
+            };
+            if *saved.peek() != config {
+                if let Err(e) = config.save(&config_path) {
+                    eprintln!("Could not save {}: {e}", config_path.display());
+                }
+                saved.set(config);
+            }
+        }
+    });
+
+    // `System` follows the OS preference, so the scheme is resolved in the webview.
+    use_effect(move || {
+        document::eval(&format!(
+            r#"const theme = "{}";
+            const dark = theme === "dark" || (theme === "system" && window.matchMedia("(prefers-color-scheme: dark)").matches);
+            document.documentElement.dataset.theme = dark ? "dark" : "light";"#,
+            theme().as_str()
+        ));
     });
 
+    let workspace = open();
     // The `rsx!` macro lets us define HTML inside of rust. It expands to an Element with all of our HTML inside.
     rsx! {
         // In addition to element and text (which we will see later), rsx can contain other components. In this case,
@@ -69,6 +182,64 @@ fn App() -> Element {
         document::Link { rel: "stylesheet", href: MAIN_CSS }
         // document::Link { rel: "stylesheet", href: TAILWIND_CSS }
 
+        WorkspaceRoot { key: "{workspace.name}/{workspace.merged}", workspace: workspace.clone() }
+    }
+}
+
+/// Repository contexts for the open workspace. `App` keys it by the workspace, so switching
+/// remounts the router and every view below it against the new repository.
+#[component]
+fn WorkspaceRoot(workspace: OpenWorkspace) -> Element {
+    let startup = use_context::<Startup>();
+    let backend = Backend::env_override().unwrap_or(startup.config.backend);
+    // Present only in the "All workspaces" view, where items are labelled with their workspace.
+    let merged = use_context_provider(|| {
+        workspace.merged.then(|| {
+            let merged = open_all_workspaces(&startup.data_dir, &workspace.name, backend);
+            Arc::new(merged.expect("failed to open workspaces"))
+        })
+    });
+    // Views mutate through the history wrapper so every change can be undone.
+    let history = use_context_provider(|| {
+        let repo: Arc<dyn HomeworkRepo> = match merged.clone() {
+            Some(merged) => merged,
+            None => open_workspace(&startup.data_dir, &workspace.name, backend).expect("failed to init repo"),
+        };
+        Arc::new(HistoryRepo::new(repo))
+    });
+    let repo = use_context_provider(move || history as Arc<dyn HomeworkRepo>);
+    let retention = use_context::<Signal<TrashRetention>>();
+
+    // Purge expired trash at startup and whenever the retention setting changes.
+    use_effect({
+        let repo = repo.clone();
+        move || {
+            if let Some(cutoff) = retention().cutoff(chrono::Utc::now().timestamp()) {
+                let _ = repo.purge_deleted_before(cutoff);
+            }
+        }
+    });
+
+    // Desktop notifications for upcoming and overdue deadlines in this workspace.
+    #[cfg(feature = "desktop")]
+    use_future({
+        let repo = repo.clone();
+        let settings = use_context::<Signal<model::reminder::ReminderSettings>>();
+        move || notifier::run_reminders(repo.clone(), settings)
+    });
+
+    // Watch for outside edits; the repo announces them to views as `RepoEvent::Refreshed`.
+    use_future(move || {
+        let repo = repo.clone();
+        async move {
+            loop {
+                tokio::time::sleep(EXTERNAL_POLL_INTERVAL).await;
+                let _ = repo.poll_external_changes();
+            }
+        }
+    });
+
+    rsx! {
         // The router component renders the route enum we defined above. It will handle synchronization of the URL and render
         // the layouts and components for the active route.
         Router::<Route> {}
@@ -0,0 +1,134 @@
+//! Day-by-day grouping for the agenda and week views: which deadlines (including projected
+//! occurrences of recurring ones) fall on which local day, and how to name that day.
+
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+use super::datetime::Datetime;
+use super::Deadline;
+
+/// Stored deadlines plus projected occurrences whose local due date falls on `from..=to`,
+/// ordered by due time.
+pub fn occurrences_between(deadlines: &[Deadline], from: NaiveDate, to: NaiveDate) -> Vec<Deadline> {
+    // Project a day either side: occurrences are computed in each deadline's own zone.
+    let lo = Datetime::from_naive(&(from - Duration::days(1)).and_time(NaiveTime::MIN));
+    let hi = Datetime::from_naive(&(to + Duration::days(2)).and_time(NaiveTime::MIN));
+    let mut out: Vec<Deadline> = deadlines
+        .iter()
+        .flat_map(|d| std::iter::once(d.clone()).chain(d.projected_occurrences(&lo, &hi)))
+        .filter(|d| local_date(d).is_some_and(|day| from <= day && day <= to))
+        .collect();
+    out.sort_by_key(|d| d.local_due().to_total_minutes());
+    out
+}
+
+/// The local calendar day `d` is due on.
+pub fn local_date(d: &Deadline) -> Option<NaiveDate> {
+    d.local_due().to_naive().map(|n| n.date())
+}
+
+/// "Today", "Tomorrow", "Yesterday", the weekday name within the coming week, otherwise
+/// "In 9 days" / "5 days ago".
+pub fn relative_day_label(day: NaiveDate, today: NaiveDate) -> String {
+    match (day - today).num_days() {
+        0 => "Today".to_string(),
+        1 => "Tomorrow".to_string(),
+        -1 => "Yesterday".to_string(),
+        2..=6 => day.format("%A").to_string(),
+        n if n > 0 => format!("In {n} days"),
+        n => format!("{} days ago", -n),
+    }
+}
+
+/// One day of the agenda.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AgendaDay {
+    pub date: NaiveDate,
+    pub label: String,
+    pub deadlines: Vec<Deadline>,
+}
+
+/// Open deadlines already past their due day, then the next `days` days starting today. Days
+/// with nothing due are left out; `overdue` holds the open deadlines due before today.
+pub fn agenda(deadlines: &[Deadline], today: NaiveDate, days: u32) -> (Vec<Deadline>, Vec<AgendaDay>) {
+    let mut overdue: Vec<Deadline> = deadlines
+        .iter()
+        .filter(|d| !d.status.is_closed() && local_date(d).is_some_and(|day| day < today))
+        .cloned()
+        .collect();
+    overdue.sort_by_key(|d| d.local_due().to_total_minutes());
+
+    let last = today + Duration::days(i64::from(days.max(1)) - 1);
+    let mut out: Vec<AgendaDay> = Vec::new();
+    for d in occurrences_between(deadlines, today, last) {
+        let Some(date) = local_date(&d) else { continue };
+        match out.last_mut() {
+            Some(day) if day.date == date => day.deadlines.push(d),
+            _ => out.push(AgendaDay { date, label: relative_day_label(date, today), deadlines: vec![d] }),
+        }
+    }
+    (overdue, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::recurrence::Recurrence;
+    use crate::model::timezone::DueZone;
+    use crate::model::Status;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn deadline(name: &str, due: Datetime) -> Deadline {
+        let mut d = Deadline::new(name.to_string(), name.to_string(), due, 5);
+        d.due_zone = DueZone::Local;
+        d
+    }
+
+    fn names(list: &[Deadline]) -> Vec<&str> {
+        list.iter().map(|d| d.name.as_str()).collect()
+    }
+
+    #[test]
+    fn labels_are_relative_to_today() {
+        let today = date(2025, 11, 26); // Wednesday
+        assert_eq!(relative_day_label(today, today), "Today");
+        assert_eq!(relative_day_label(date(2025, 11, 27), today), "Tomorrow");
+        assert_eq!(relative_day_label(date(2025, 11, 25), today), "Yesterday");
+        assert_eq!(relative_day_label(date(2025, 11, 28), today), "Friday");
+        assert_eq!(relative_day_label(date(2025, 12, 2), today), "Tuesday");
+        assert_eq!(relative_day_label(date(2025, 12, 3), today), "In 7 days");
+        assert_eq!(relative_day_label(date(2025, 11, 21), today), "5 days ago");
+    }
+
+    #[test]
+    fn agenda_groups_by_day_with_overdue_first() {
+        let today = date(2025, 11, 26);
+        let mut weekly = deadline("Quiz", Datetime::new(2025, 11, 27, 9, 0));
+        weekly.recurrence = Recurrence::parse("FREQ=WEEKLY;BYDAY=TH");
+        let mut done = deadline("Done", Datetime::new(2025, 11, 20, 12, 0));
+        done.status = Status::Completed;
+        let list = vec![
+            deadline("Essay", Datetime::new(2025, 11, 26, 23, 59)),
+            deadline("Late", Datetime::new(2025, 11, 24, 12, 0)),
+            deadline("Lab", Datetime::new(2025, 11, 26, 8, 0)),
+            weekly,
+            done,
+            deadline("Far", Datetime::new(2026, 1, 10, 12, 0)),
+        ];
+
+        let (overdue, days) = agenda(&list, today, 14);
+        assert_eq!(names(&overdue), vec!["Late"]);
+        let summary: Vec<(&str, Vec<&str>)> = days.iter().map(|d| (d.label.as_str(), names(&d.deadlines))).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Today", vec!["Lab", "Essay"]),
+                ("Tomorrow", vec!["Quiz"]),
+                ("In 8 days", vec!["Quiz"]),
+            ]
+        );
+        assert!(days[2].deadlines[0].is_projected());
+    }
+}
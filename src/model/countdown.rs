@@ -0,0 +1,125 @@
+//! How much time a deadline has left, as shown on its card, and how soon that display (and
+//! the urgency behind it) needs redrawing: every minute in the final day, less often further out.
+
+use super::datetime::Datetime;
+use super::Deadline;
+
+const DAY_MINUTES: i64 = 24 * 60;
+const WEEK_MINUTES: i64 = 7 * DAY_MINUTES;
+/// Longest gap between refreshes, so urgency of far-off deadlines still creeps up.
+pub const MAX_REFRESH_MINUTES: i64 = 60;
+/// Refresh interval for deadlines due within a week.
+const WEEK_REFRESH_MINUTES: i64 = 15;
+
+/// Time left until a deadline, in the steps the due badge shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DueState {
+    /// More than a day left, in whole days
+    Days(i64),
+    /// The final 24 hours, counted down to the minute
+    Countdown { hours: i64, minutes: i64 },
+    /// The due time has been reached; minutes since then
+    Overdue(i64),
+}
+
+impl DueState {
+    pub fn from_minutes_left(left: i64) -> Self {
+        match left {
+            l if l <= 0 => DueState::Overdue(-l),
+            l if l <= DAY_MINUTES => DueState::Countdown { hours: l / 60, minutes: l % 60 },
+            l => DueState::Days(l / DAY_MINUTES),
+        }
+    }
+
+    /// State of `d` at `now`, both as wall-clock times in the viewer's zone.
+    pub fn of(d: &Deadline, now: &Datetime) -> Self {
+        Self::from_minutes_left(minutes_left(d, now))
+    }
+
+    pub fn is_overdue(&self) -> bool {
+        matches!(self, DueState::Overdue(_))
+    }
+
+    /// "Due in 3d", "Due in 05:42", "Overdue 2h 5m" or "Overdue 3d".
+    pub fn label(&self) -> String {
+        match *self {
+            DueState::Days(d) => format!("Due in {d}d"),
+            DueState::Countdown { hours, minutes } => format!("Due in {hours:02}:{minutes:02}"),
+            DueState::Overdue(m) if m < DAY_MINUTES => format!("Overdue {}h {}m", m / 60, m % 60),
+            DueState::Overdue(m) => format!("Overdue {}d", m / DAY_MINUTES),
+        }
+    }
+}
+
+fn minutes_left(d: &Deadline, now: &Datetime) -> i64 {
+    d.local_due().to_total_minutes() - now.to_total_minutes()
+}
+
+/// Minutes from `now` until the cards of `deadlines` next change: when a badge ticks over
+/// (including the moment one turns overdue), or at the latest when urgency is due a refresh.
+pub fn minutes_until_refresh(deadlines: &[Deadline], now: &Datetime) -> i64 {
+    deadlines
+        .iter()
+        .filter(|d| !d.status.is_closed())
+        .map(|d| {
+            let left = minutes_left(d, now);
+            let cap = match left.abs() {
+                a if a <= DAY_MINUTES => 1,
+                a if a <= WEEK_MINUTES => WEEK_REFRESH_MINUTES,
+                _ => MAX_REFRESH_MINUTES,
+            };
+            let current = DueState::from_minutes_left(left);
+            (1..cap).find(|m| DueState::from_minutes_left(left - m) != current).unwrap_or(cap)
+        })
+        .min()
+        .unwrap_or(MAX_REFRESH_MINUTES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::timezone::DueZone;
+    use crate::model::Status;
+
+    fn due_at(due: Datetime) -> Deadline {
+        let mut d = Deadline::new("id".to_string(), "Essay".to_string(), due, 5);
+        d.due_zone = DueZone::Local;
+        d
+    }
+
+    #[test]
+    fn labels_count_down_in_the_final_day() {
+        let now = Datetime::new(2025, 11, 26, 14, 0);
+        let label = |due| DueState::of(&due_at(due), &now).label();
+        assert_eq!(label(Datetime::new(2025, 11, 29, 15, 0)), "Due in 3d");
+        assert_eq!(label(Datetime::new(2025, 11, 27, 14, 0)), "Due in 24:00");
+        assert_eq!(label(Datetime::new(2025, 11, 26, 19, 42)), "Due in 05:42");
+        assert_eq!(label(Datetime::new(2025, 11, 26, 14, 1)), "Due in 00:01");
+        assert_eq!(label(Datetime::new(2025, 11, 26, 14, 0)), "Overdue 0h 0m");
+        assert_eq!(label(Datetime::new(2025, 11, 26, 11, 55)), "Overdue 2h 5m");
+        assert_eq!(label(Datetime::new(2025, 11, 23, 9, 0)), "Overdue 3d");
+        assert!(DueState::from_minutes_left(0).is_overdue());
+        assert!(!DueState::from_minutes_left(1).is_overdue());
+    }
+
+    #[test]
+    fn refresh_gets_finer_as_deadlines_approach() {
+        let now = Datetime::new(2025, 11, 26, 14, 0);
+        let refresh = |dues: &[Datetime]| {
+            let list: Vec<Deadline> = dues.iter().map(|d| due_at(*d)).collect();
+            minutes_until_refresh(&list, &now)
+        };
+        assert_eq!(refresh(&[]), MAX_REFRESH_MINUTES);
+        assert_eq!(refresh(&[Datetime::new(2026, 1, 1, 9, 0)]), MAX_REFRESH_MINUTES);
+        assert_eq!(refresh(&[Datetime::new(2025, 11, 30, 9, 0)]), WEEK_REFRESH_MINUTES);
+        // "Due in 2d" becomes "Due in 1d" at 14:01 ...
+        assert_eq!(refresh(&[Datetime::new(2025, 11, 28, 14, 0)]), 1);
+        // ... and "Due in 1d" turns into the countdown after 10 minutes.
+        assert_eq!(refresh(&[Datetime::new(2025, 11, 27, 14, 10)]), 10);
+        assert_eq!(refresh(&[Datetime::new(2026, 1, 1, 9, 0), Datetime::new(2025, 11, 26, 18, 0)]), 1);
+
+        let mut done = due_at(Datetime::new(2025, 11, 26, 14, 30));
+        done.status = Status::Completed;
+        assert_eq!(minutes_until_refresh(&[done], &now), MAX_REFRESH_MINUTES);
+    }
+}
@@ -1,6 +1,8 @@
 use chrono::prelude::*;
 use std::cmp::Ordering;
 
+use super::timezone::DueZone;
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[allow(dead_code)]
 pub struct TimeDiff {
@@ -83,6 +85,56 @@ impl Datetime {
         )
     }
 
+    pub fn is_leap_year(year: u16) -> bool {
+        (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+    }
+
+    pub fn days_in_month(year: u16, month: u8) -> u8 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if Self::is_leap_year(year) => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+
+    pub fn to_naive(&self) -> Option<NaiveDateTime> {
+        NaiveDate::from_ymd_opt(self.year as i32, self.month as u32, self.day as u32)?
+            .and_hms_opt(self.hour as u32, self.minute as u32, 0)
+    }
+
+    pub fn from_naive(naive: &NaiveDateTime) -> Self {
+        Self {
+            year: naive.year() as u16,
+            month: naive.month() as u8,
+            day: naive.day() as u8,
+            hour: naive.hour() as u8,
+            minute: naive.minute() as u8,
+        }
+    }
+
+    /// The wall-clock time `minutes` later (or earlier, if negative).
+    pub fn shifted_minutes(&self, minutes: i64) -> Option<Self> {
+        let naive = self.to_naive()?.checked_add_signed(chrono::Duration::minutes(minutes))?;
+        Some(Self::from_naive(&naive))
+    }
+
+    /// Reinterpret this wall-clock time from zone `from` as a wall-clock time in zone `to`.
+    /// Falls back to the unchanged value if the time cannot be resolved in `from`.
+    pub fn convert(&self, from: &DueZone, to: &DueZone) -> Self {
+        from.to_utc(self).map(|utc| to.from_utc(&utc)).unwrap_or(*self)
+    }
+
+    /// Whether this is a real calendar date and time of day (no Feb 30, no hour 25).
+    pub fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= Self::days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+    }
+
     pub fn from_string(s: &str) -> Option<Self> {
         // Expected format: "YYYY-MM-DD HH:MM"
         let parts: Vec<&str> = s.split(' ').collect();
@@ -100,7 +152,8 @@ impl Datetime {
         let hour = time_parts[0].parse().ok()?;
         let minute = time_parts[1].parse().ok()?;
         
-        Some(Self { year, month, day, hour, minute })
+        let dt = Self { year, month, day, hour, minute };
+        if dt.is_valid() { Some(dt) } else { None }
     }
 
     /// calculate time difference
@@ -125,17 +178,106 @@ impl Datetime {
         }
     } // use .time_diff(other).to_hours() would be useful
 
-    /// to total minutes
+    /// Days since 1970-01-01 in the proleptic Gregorian calendar.
+    /// Out-of-range months/days are not normalized, so only use this on valid dates.
+    pub fn days_since_epoch(&self) -> i64 {
+        // Shift the year so it starts in March; the leap day then falls at the end.
+        let month = self.month as i64;
+        let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month_from_march = (month + 9) % 12;
+        let day_of_year = (153 * month_from_march + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// to total minutes (since 1970-01-01 00:00)
     pub fn to_total_minutes(&self) -> i64 {
-        let days_in_month = 30;
-        let total_days = (self.year as i64 * 365)
-            + ((self.month as i64 - 1) * days_in_month)
-            + (self.day as i64);
-        let total_minutes = (total_days * 24 * 60) + (self.hour as i64 * 60) + (self.minute as i64);
-        total_minutes
+        let total_days = self.days_since_epoch();
+        (total_days * 24 * 60) + (self.hour as i64 * 60) + (self.minute as i64)
     }
 
     pub fn cmp(&self, other: &Datetime) -> Ordering {
         self.to_total_minutes().cmp(&other.to_total_minutes())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_across_month_end() {
+        let jan31 = Datetime::new(2025, 1, 31, 12, 0);
+        let feb1 = Datetime::new(2025, 2, 1, 12, 0);
+        assert_eq!(feb1.time_diff(&jan31).to_minutes(), 24 * 60);
+        assert_eq!(jan31.cmp(&feb1), Ordering::Less);
+    }
+
+    #[test]
+    fn diff_across_leap_day() {
+        let feb28 = Datetime::new(2024, 2, 28, 0, 0);
+        let mar1 = Datetime::new(2024, 3, 1, 0, 0);
+        assert_eq!(mar1.time_diff(&feb28).days, 2);
+
+        let feb28 = Datetime::new(2023, 2, 28, 0, 0);
+        let mar1 = Datetime::new(2023, 3, 1, 0, 0);
+        assert_eq!(mar1.time_diff(&feb28).days, 1);
+    }
+
+    #[test]
+    fn diff_across_year_end() {
+        let dec31 = Datetime::new(2024, 12, 31, 23, 30);
+        let jan1 = Datetime::new(2025, 1, 1, 0, 15);
+        let diff = dec31.time_diff(&jan1);
+        assert!(diff.is_negative);
+        assert_eq!(diff.to_minutes(), -45);
+    }
+
+    #[test]
+    fn full_years() {
+        let a = Datetime::new(2024, 1, 1, 0, 0);
+        let b = Datetime::new(2025, 1, 1, 0, 0);
+        let c = Datetime::new(2026, 1, 1, 0, 0);
+        assert_eq!(b.time_diff(&a).days, 366);
+        assert_eq!(c.time_diff(&b).days, 365);
+    }
+
+    #[test]
+    fn epoch_matches_chrono() {
+        let dt = Datetime::new(2000, 2, 29, 0, 0);
+        let naive = NaiveDate::from_ymd_opt(2000, 2, 29).unwrap();
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        assert_eq!(dt.days_since_epoch(), naive.signed_duration_since(epoch).num_days());
+        assert_eq!(Datetime::new(1970, 1, 1, 0, 0).days_since_epoch(), 0);
+    }
+
+    #[test]
+    fn convert_between_zones() {
+        let aoe = DueZone::parse("AoE").unwrap();
+        let berlin = DueZone::parse("Europe/Berlin").unwrap();
+        // 23:59 AoE (UTC-12) is 11:59 UTC the next day, 12:59 in Berlin during winter.
+        let due = Datetime::new(2025, 1, 10, 23, 59);
+        assert_eq!(due.convert(&aoe, &DueZone::Offset(0)), Datetime::new(2025, 1, 11, 11, 59));
+        assert_eq!(due.convert(&aoe, &berlin), Datetime::new(2025, 1, 11, 12, 59));
+        // Summer time shifts Berlin by one more hour.
+        let due = Datetime::new(2025, 7, 10, 23, 59);
+        assert_eq!(due.convert(&aoe, &berlin), Datetime::new(2025, 7, 11, 13, 59));
+    }
+
+    #[test]
+    fn from_string_rejects_invalid() {
+        assert_eq!(
+            Datetime::from_string("2024-02-29 23:59"),
+            Some(Datetime::new(2024, 2, 29, 23, 59))
+        );
+        assert_eq!(Datetime::from_string("2023-02-29 12:00"), None);
+        assert_eq!(Datetime::from_string("2025-02-30 12:00"), None);
+        assert_eq!(Datetime::from_string("2025-04-31 12:00"), None);
+        assert_eq!(Datetime::from_string("2025-13-01 12:00"), None);
+        assert_eq!(Datetime::from_string("2025-01-00 12:00"), None);
+        assert_eq!(Datetime::from_string("2025-01-01 25:00"), None);
+        assert_eq!(Datetime::from_string("2025-01-01 12:60"), None);
+    }
+}
@@ -1,15 +1,28 @@
 use super::datetime::Datetime;
+use super::milestone::{progress_from_milestones, Milestone};
+use super::recurrence::Recurrence;
+use super::reminder::Lead;
+use super::status::Status;
+use super::timezone::DueZone;
+use super::urgency::UrgencyModel;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Deadline {
     pub id: String,
     pub name: String,
-    pub due_date: Datetime,
+    pub due_date: Datetime, // wall-clock time in `due_zone`
+    pub due_zone: DueZone,
     pub difficulty: u8,
     pub progress: u8, // percentage from 0 to 100
-    pub milestones: Vec<(u8, String)>,
-    pub urgency: f32, // calculated
+    pub milestones: Vec<Milestone>,
+    pub urgency: f32, // calculated, normalized to 0..=1
     pub tags: Vec<String>,
+    pub recurrence: Option<Recurrence>,
+    pub status: Status,
+    /// Reminder leads; `None` uses the global defaults, an empty list turns reminders off.
+    pub reminders: Option<Vec<Lead>>,
+    /// 0 for the stored deadline; n > 0 for the nth projected future occurrence of its series.
+    pub occurrence: u32,
 }
 
 #[allow(dead_code)]
@@ -19,24 +32,133 @@ impl Deadline {
             id,
             name,
             due_date,
+            due_zone: DueZone::local(),
             difficulty,
             progress: 0,
             milestones: Vec::new(),
             urgency: 0.0,
             tags: Vec::new(),
+            recurrence: None,
+            status: Status::Open,
+            reminders: None,
+            occurrence: 0,
         }
     }
 
+    /// The due date as a wall-clock time in the viewer's zone.
+    pub fn local_due(&self) -> Datetime {
+        self.due_date.convert(&self.due_zone, &DueZone::Local)
+    }
+
+    /// The due date in its original zone, e.g. "2025-11-25 23:59 AoE".
+    pub fn original_due_text(&self) -> String {
+        format!("{} {}", self.due_date.to_string(), self.due_zone.label())
+    }
+
     pub fn hours_until_due(&self) -> f32 {
         let now = Datetime::now();
-        self.due_date.time_diff(&now).to_hours()
+        self.local_due().time_diff(&now).to_hours()
     }
     
-    pub fn update_urgency(&mut self) -> f32 {
-        let hours_left = self.hours_until_due();
-        let delta = 0.0001; // safe.
-        let hours_left_safe = if hours_left < delta { delta } else { hours_left };
-        self.urgency = self.difficulty as f32 * (100.0 - self.progress as f32) / hours_left_safe;
+    /// Closed (completed or cancelled) deadlines score 0.
+    pub fn update_urgency(&mut self, model: &dyn UrgencyModel) -> f32 {
+        self.urgency = if self.status.is_closed() {
+            0.0
+        } else {
+            model.score(self, self.hours_until_due())
+        };
         self.urgency
     }
+
+    /// Keep the status in step with progress moved by hand: reaching 100% completes the
+    /// deadline (unless its status rules that out) and dropping below reopens a completed one.
+    /// Returns whether the status changed.
+    pub fn sync_status_with_progress(&mut self) -> bool {
+        self.status = match (self.progress >= 100, self.status) {
+            (true, s) if s != Status::Completed && s.can_become(Status::Completed) => Status::Completed,
+            (false, Status::Completed) => Status::Open,
+            _ => return false,
+        };
+        true
+    }
+
+    /// Re-derive `progress` from completed milestone weights, if there are milestones.
+    pub fn sync_progress_from_milestones(&mut self) {
+        if let Some(p) = progress_from_milestones(&self.milestones) {
+            self.progress = p;
+        }
+    }
+
+    pub fn is_projected(&self) -> bool {
+        self.occurrence > 0
+    }
+
+    /// Projected future occurrences of a recurring deadline falling within `[from, to]`
+    /// (in the deadline's own zone). The stored occurrence itself is not included.
+    pub fn projected_occurrences(&self, from: &Datetime, to: &Datetime) -> Vec<Deadline> {
+        let Some(rule) = &self.recurrence else { return Vec::new() };
+        let mut out = Vec::new();
+        let mut index = 0;
+        rule.for_each_occurrence(&self.due_date, |dt| {
+            if dt.cmp(to).is_gt() {
+                return false;
+            }
+            if dt.cmp(&self.due_date).is_gt() {
+                index += 1;
+                if !dt.cmp(from).is_lt() {
+                    let mut projected = self.clone();
+                    projected.due_date = dt;
+                    projected.progress = 0;
+                    projected.status = Status::Open;
+                    projected.occurrence = index;
+                    for m in projected.milestones.iter_mut() {
+                        m.done = false;
+                    }
+                    out.push(projected);
+                }
+            }
+            true
+        });
+        out
+    }
+
+    /// Move a recurring deadline on to its next occurrence once the current one is finished.
+    /// Returns false (and leaves the deadline untouched) if it does not repeat or the series is over.
+    pub fn advance_recurrence(&mut self) -> bool {
+        let Some(rule) = self.recurrence.as_mut() else { return false };
+        if rule.count.is_some_and(|c| c <= 1) {
+            return false;
+        }
+        let Some(next) = rule.next_after(&self.due_date, &self.due_date) else { return false };
+        if let Some(c) = rule.count.as_mut() {
+            *c -= 1;
+        }
+        // Exceptions before the new due date no longer matter.
+        rule.exceptions.retain(|e| (e.year, e.month, e.day) >= (next.year, next.month, next.day));
+        // Milestones keep their relative offset to the due date in the next occurrence.
+        let shift = next.to_total_minutes() - self.due_date.to_total_minutes();
+        for m in self.milestones.iter_mut() {
+            m.done = false;
+            m.due = m.due.and_then(|due| due.shifted_minutes(shift));
+        }
+        self.due_date = next;
+        self.progress = 0;
+        self.status = Status::Open;
+        self.occurrence = 0;
+        true
+    }
+
+    /// Finish the deadline: a recurring one moves on to its next occurrence (returns true),
+    /// anything else is completed with full progress.
+    pub fn mark_done(&mut self) -> bool {
+        self.progress = 100;
+        for m in self.milestones.iter_mut() {
+            m.done = true;
+        }
+        if self.advance_recurrence() {
+            return true;
+        }
+        self.status = Status::Completed;
+        false
+    }
 }
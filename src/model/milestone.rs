@@ -0,0 +1,47 @@
+use super::datetime::Datetime;
+
+/// One checklist item of a deadline. Order is the position in `Deadline::milestones`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Milestone {
+    pub title: String,
+    /// Relative share of the deadline's work (at least 1).
+    pub weight: u8,
+    pub done: bool,
+    /// Optional own due date, in the parent deadline's zone.
+    pub due: Option<Datetime>,
+}
+
+/// Progress (0..=100) implied by the completed share of milestone weights,
+/// or `None` when there are no milestones to derive it from.
+pub fn progress_from_milestones(milestones: &[Milestone]) -> Option<u8> {
+    let total: u32 = milestones.iter().map(|m| m.weight.max(1) as u32).sum();
+    if total == 0 {
+        return None;
+    }
+    let done: u32 = milestones
+        .iter()
+        .filter(|m| m.done)
+        .map(|m| m.weight.max(1) as u32)
+        .sum();
+    Some(((done * 100 + total / 2) / total) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn milestone(weight: u8, done: bool) -> Milestone {
+        Milestone { title: "Step".to_string(), weight, done, due: None }
+    }
+
+    #[test]
+    fn progress_is_the_done_share_of_the_weights() {
+        assert_eq!(progress_from_milestones(&[]), None);
+        assert_eq!(progress_from_milestones(&[milestone(2, false), milestone(1, false)]), Some(0));
+        assert_eq!(progress_from_milestones(&[milestone(2, true), milestone(1, false)]), Some(67));
+        assert_eq!(progress_from_milestones(&[milestone(1, true), milestone(3, false)]), Some(25));
+        assert_eq!(progress_from_milestones(&[milestone(2, true), milestone(5, true)]), Some(100));
+        // A zero weight still counts as one share.
+        assert_eq!(progress_from_milestones(&[milestone(0, true), milestone(1, false)]), Some(50));
+    }
+}
@@ -1,4 +1,16 @@
+pub mod agenda;
+pub mod countdown;
 pub mod datetime;
 pub mod deadline;
+pub mod milestone;
+pub mod quick_add;
+pub mod recurrence;
+pub mod reschedule;
+pub mod reminder;
+pub mod status;
+pub mod timezone;
+pub mod urgency;
+pub mod workload;
 
-pub use deadline::Deadline;
\ No newline at end of file
+pub use deadline::Deadline;
+pub use status::Status;
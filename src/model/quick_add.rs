@@ -0,0 +1,378 @@
+//! Parser for the quick-add bar: one line of text such as "OS lab 3 next fri 23:59 #os #lab !8"
+//! becomes a name, due date, tags and difficulty.
+//!
+//! Recognised anywhere in the text (case-insensitive):
+//! - `#tag` and `!difficulty` (1-10)
+//! - dates: `today`, `tonight`, `tomorrow`, `in 3 days` / `in 2 weeks` / `in a month`,
+//!   weekdays (`fri`, `this friday`, `next monday`), `end of month`, `2025-12-05`, `dec 5`, `5 dec`
+//! - times: `23:59`, `9am`, `5:30 pm`, `noon`, `midnight`
+//! - zones: `AoE`, `UTC` or an IANA name such as `Europe/Berlin`
+//!
+//! "due", "by", "on" and "at" directly before a date or time are dropped too. The first date
+//! and the first time win; everything not recognised is the name. A date without a time is
+//! due at 23:59, and a time without a date is due on its next occurrence.
+
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use super::datetime::Datetime;
+use super::timezone::DueZone;
+use super::Deadline;
+
+/// Due time used when only a date is given.
+const END_OF_DAY: (u32, u32) = (23, 59);
+const DEFAULT_DIFFICULTY: u8 = 5;
+
+/// Words dropped when they introduce a date or time ("due fri", "at 9am").
+const CONNECTORS: [&str; 4] = ["due", "by", "on", "at"];
+
+/// What the quick-add text describes so far.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuickAdd {
+    pub name: String,
+    /// Wall-clock due time in `zone`
+    pub due: Option<Datetime>,
+    /// `None` = the viewer's zone
+    pub zone: Option<DueZone>,
+    pub tags: Vec<String>,
+    pub difficulty: Option<u8>,
+}
+
+/// One recognised phrase and how many words it used.
+enum Piece {
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Zone(DueZone),
+    Tag(String),
+    Difficulty(u8),
+}
+
+impl QuickAdd {
+    /// Parse `text`, resolving relative dates against `now` (the viewer's local time).
+    pub fn parse(text: &str, now: NaiveDateTime) -> Self {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+        let today = now.date();
+
+        let mut name: Vec<&str> = Vec::new();
+        let mut date = None;
+        let mut time = None;
+        let mut zone = None;
+        let mut tags: Vec<String> = Vec::new();
+        let mut difficulty = None;
+
+        let mut i = 0;
+        while i < words.len() {
+            let piece = match piece_at(&words, &lower, i, today) {
+                Some(found) => Some(found),
+                // "due fri", "at 9am": only drop the connector if a date or time follows.
+                None if CONNECTORS.contains(&lower[i].as_str()) => piece_at(&words, &lower, i + 1, today)
+                    .filter(|(p, _)| matches!(p, Piece::Date(_) | Piece::Time(_)))
+                    .map(|(p, used)| (p, used + 1)),
+                None => None,
+            };
+            let used = match piece {
+                Some((Piece::Date(d), used)) if date.is_none() => {
+                    date = Some(d);
+                    used
+                }
+                Some((Piece::Time(t), used)) if time.is_none() => {
+                    time = Some(t);
+                    used
+                }
+                Some((Piece::Zone(z), used)) if zone.is_none() => {
+                    zone = Some(z);
+                    used
+                }
+                Some((Piece::Tag(t), used)) => {
+                    if !tags.iter().any(|x| x.eq_ignore_ascii_case(&t)) {
+                        tags.push(t);
+                    }
+                    used
+                }
+                Some((Piece::Difficulty(d), used)) if difficulty.is_none() => {
+                    difficulty = Some(d);
+                    used
+                }
+                // Unrecognised, or a second date / time / zone: part of the name.
+                _ => {
+                    name.push(words[i]);
+                    1
+                }
+            };
+            i += used;
+        }
+
+        let due = match (date, time) {
+            (Some(d), Some(t)) => Some(d.and_time(t)),
+            (Some(d), None) => d.and_hms_opt(END_OF_DAY.0, END_OF_DAY.1, 0),
+            (None, Some(t)) => {
+                let at = today.and_time(t);
+                Some(if at > now { at } else { at + Duration::days(1) })
+            }
+            (None, None) => None,
+        };
+
+        Self {
+            name: name.join(" "),
+            due: due.map(|d| Datetime::from_naive(&d)),
+            zone,
+            tags,
+            difficulty,
+        }
+    }
+
+    /// The deadline to create, or `None` while the name or due date is missing.
+    pub fn to_deadline(&self) -> Option<Deadline> {
+        if self.name.is_empty() {
+            return None;
+        }
+        let mut d = Deadline::new(
+            String::new(),
+            self.name.clone(),
+            self.due?,
+            self.difficulty.unwrap_or(DEFAULT_DIFFICULTY),
+        );
+        if let Some(zone) = &self.zone {
+            d.due_zone = zone.clone();
+        }
+        d.tags = self.tags.clone();
+        Some(d)
+    }
+}
+
+/// The phrase starting at word `i`, if any.
+fn piece_at(words: &[&str], lower: &[String], i: usize, today: NaiveDate) -> Option<(Piece, usize)> {
+    let word = lower.get(i)?.as_str();
+    let next = lower.get(i + 1).map(String::as_str);
+
+    if let Some(tag) = words[i].strip_prefix('#').filter(|t| !t.is_empty()) {
+        return Some((Piece::Tag(tag.to_string()), 1));
+    }
+    if let Some(d) = word.strip_prefix('!').and_then(|d| d.parse::<u8>().ok()) {
+        return (1..=10).contains(&d).then_some((Piece::Difficulty(d), 1));
+    }
+    if let Some((t, used)) = time_at(word, next) {
+        return Some((Piece::Time(t), used));
+    }
+    if let Some((d, used)) = date_at(lower, i, today) {
+        return Some((Piece::Date(d), used));
+    }
+    if word == "aoe" || word == "utc" || word.contains('/') {
+        return DueZone::parse(words[i]).map(|z| (Piece::Zone(z), 1));
+    }
+    None
+}
+
+/// "23:59", "9am", "9:30pm", "5 pm", "noon", "midnight" (the last minute of the day).
+fn time_at(word: &str, next: Option<&str>) -> Option<(NaiveTime, usize)> {
+    match word {
+        "noon" => return Some((NaiveTime::from_hms_opt(12, 0, 0)?, 1)),
+        "midnight" => return NaiveTime::from_hms_opt(END_OF_DAY.0, END_OF_DAY.1, 0).map(|t| (t, 1)),
+        _ => {}
+    }
+    let (clock, meridiem, used) = match next {
+        Some(m @ ("am" | "pm")) => (word, Some(m), 2),
+        _ => match word.strip_suffix("am").or_else(|| word.strip_suffix("pm")) {
+            Some(clock) => (clock, Some(&word[clock.len()..]), 1),
+            None => (word, None, 1),
+        },
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) if m.len() == 2 => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        // A bare number is only a time with am/pm ("OS lab 3" keeps its 3).
+        None if meridiem.is_some() => (clock.parse::<u32>().ok()?, 0),
+        _ => return None,
+    };
+    let hour = match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some("am") => hour % 12,
+        Some(_) => hour % 12 + 12,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0).map(|t| (t, used))
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    let day = match word {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(day)
+}
+
+fn month(word: &str) -> Option<u32> {
+    const NAMES: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+    let word = word.trim_end_matches('.');
+    let i = NAMES.iter().position(|m| word.len() >= 3 && m.starts_with(&word[..3.min(word.len())]))?;
+    // Accept "dec", "december" and "sept", but not "decimal".
+    let full = ["january", "february", "march", "april", "may", "june", "july", "august", "september", "october", "november", "december"][i];
+    (full.starts_with(word)).then_some(i as u32 + 1)
+}
+
+/// "5", "5th", "21st".
+fn day_of_month(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &word[digits.len()..];
+    if !matches!(suffix, "" | "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    digits.parse::<u32>().ok().filter(|d| (1..=31).contains(d))
+}
+
+/// The next `day` on or after `from` (strictly after, if `skip_today`).
+fn next_weekday(from: NaiveDate, day: Weekday, skip_today: bool) -> NaiveDate {
+    let ahead = (day.num_days_from_monday() + 7 - from.weekday().num_days_from_monday()) % 7;
+    let ahead = if ahead == 0 && skip_today { 7 } else { ahead };
+    from + Duration::days(i64::from(ahead))
+}
+
+/// `month`/`day` this year, or next year if that date has passed.
+fn upcoming_date(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(today.year(), month, day)
+        .filter(|d| *d >= today)
+        .or_else(|| NaiveDate::from_ymd_opt(today.year() + 1, month, day))
+}
+
+fn date_at(lower: &[String], i: usize, today: NaiveDate) -> Option<(NaiveDate, usize)> {
+    let word = |k: usize| lower.get(i + k).map(String::as_str);
+    let first = word(0)?;
+
+    match first {
+        "today" | "tonight" => return Some((today, 1)),
+        "tomorrow" | "tmr" | "tmrw" => return Some((today.succ_opt()?, 1)),
+        "eom" => return Some((last_day_of_month(today)?, 1)),
+        _ => {}
+    }
+    if first == "end" && word(1) == Some("of") {
+        let (noun, used) = match word(2) {
+            Some("the") => (word(3), 4),
+            other => (other, 3),
+        };
+        if noun == Some("month") {
+            return Some((last_day_of_month(today)?, used));
+        }
+    }
+    if first == "in" {
+        let count = match word(1)? {
+            "a" | "an" | "one" => 1,
+            n => n.parse::<u32>().ok()?,
+        };
+        let date = match word(2)?.trim_end_matches('s') {
+            "day" => today.checked_add_signed(Duration::days(i64::from(count)))?,
+            "week" => today.checked_add_signed(Duration::weeks(i64::from(count)))?,
+            "month" => today.checked_add_months(Months::new(count))?,
+            _ => return None,
+        };
+        return Some((date, 3));
+    }
+    if let Some(day) = weekday(first) {
+        return Some((next_weekday(today, day, false), 1));
+    }
+    if let Some(day) = word(1).and_then(weekday) {
+        match first {
+            "this" => return Some((next_weekday(today, day, false), 2)),
+            "next" => return Some((next_weekday(today, day, true), 2)),
+            _ => {}
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(first, "%Y-%m-%d") {
+        return Some((date, 1));
+    }
+    // "dec 5" / "5 dec"
+    if let (Some(m), Some(d)) = (month(first), word(1).and_then(day_of_month)) {
+        return upcoming_date(today, m, d).map(|date| (date, 2));
+    }
+    if let (Some(d), Some(m)) = (day_of_month(first), word(1).and_then(month)) {
+        return upcoming_date(today, m, d).map(|date| (date, 2));
+    }
+    None
+}
+
+fn last_day_of_month(today: NaiveDate) -> Option<NaiveDate> {
+    today.with_day(1)?.checked_add_months(Months::new(1))?.pred_opt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Wednesday afternoon
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 11, 26).unwrap().and_hms_opt(14, 0, 0).unwrap()
+    }
+
+    fn due(text: &str) -> String {
+        QuickAdd::parse(text, now()).due.map(|d| d.to_string()).unwrap_or_default()
+    }
+
+    #[test]
+    fn splits_name_tags_difficulty_and_due() {
+        let q = QuickAdd::parse("OS lab 3 next fri 23:59 #os #lab !8", now());
+        assert_eq!(q.name, "OS lab 3");
+        assert_eq!(q.due, Some(Datetime::new(2025, 11, 28, 23, 59)));
+        assert_eq!(q.tags, vec!["os", "lab"]);
+        assert_eq!(q.difficulty, Some(8));
+        assert_eq!(q.zone, None);
+
+        let d = q.to_deadline().unwrap();
+        assert_eq!((d.name.as_str(), d.difficulty, d.tags.len()), ("OS lab 3", 8, 2));
+    }
+
+    #[test]
+    fn relative_dates() {
+        assert_eq!(due("Essay today"), "2025-11-26 23:59");
+        assert_eq!(due("Essay tomorrow"), "2025-11-27 23:59");
+        assert_eq!(due("Essay in 3 days"), "2025-11-29 23:59");
+        assert_eq!(due("Essay in 2 weeks"), "2025-12-10 23:59");
+        assert_eq!(due("Essay in a month"), "2025-12-26 23:59");
+        assert_eq!(due("Essay end of month"), "2025-11-30 23:59");
+        assert_eq!(due("Essay end of the month 17:00"), "2025-11-30 17:00");
+        // Bare and "this" weekdays include today; "next" never does.
+        assert_eq!(due("Essay wed"), "2025-11-26 23:59");
+        assert_eq!(due("Essay next wed"), "2025-12-03 23:59");
+        assert_eq!(due("Essay this monday"), "2025-12-01 23:59");
+    }
+
+    #[test]
+    fn absolute_dates_and_times() {
+        assert_eq!(due("Essay 2026-01-15 9am"), "2026-01-15 09:00");
+        assert_eq!(due("Essay dec 5 at 5:30 pm"), "2025-12-05 17:30");
+        assert_eq!(due("Essay 3rd March noon"), "2026-03-03 12:00");
+        assert_eq!(due("Essay by midnight"), "2025-11-26 23:59");
+        // A time alone is its next occurrence.
+        assert_eq!(due("Call 15:30"), "2025-11-26 15:30");
+        assert_eq!(due("Call 9:00"), "2025-11-27 09:00");
+        assert_eq!(due("Call 12am"), "2025-11-27 00:00");
+    }
+
+    #[test]
+    fn unrecognised_words_stay_in_the_name() {
+        let q = QuickAdd::parse("Read chapter 3 on decimals due fri !11 #", now());
+        assert_eq!(q.name, "Read chapter 3 on decimals !11 #");
+        assert_eq!(q.due, Some(Datetime::new(2025, 11, 28, 23, 59)));
+        assert_eq!(q.difficulty, None);
+
+        // Only the first date counts.
+        let q = QuickAdd::parse("Monday standup notes fri", now());
+        assert_eq!(q.name, "standup notes fri");
+        assert_eq!(q.due, Some(Datetime::new(2025, 12, 1, 23, 59)));
+
+        let q = QuickAdd::parse("Submit AoE tomorrow #Uni #uni", now());
+        assert_eq!(q.zone, Some(DueZone::parse("AoE").unwrap()));
+        assert_eq!(q.tags, vec!["Uni"]);
+        assert_eq!(q.name, "Submit");
+    }
+
+    #[test]
+    fn incomplete_text_makes_no_deadline() {
+        assert_eq!(QuickAdd::parse("Essay", now()).to_deadline(), None);
+        assert_eq!(QuickAdd::parse("tomorrow #os", now()).to_deadline(), None);
+        assert_eq!(QuickAdd::parse("", now()).name, "");
+    }
+}
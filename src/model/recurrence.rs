@@ -0,0 +1,413 @@
+use chrono::prelude::*;
+use chrono::Duration;
+
+use super::datetime::Datetime;
+
+/// How often a series repeats. The time of day always comes from the series start.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    /// On the given weekdays; an empty list means the weekday of the series start.
+    Weekly { weekdays: Vec<Weekday> },
+    /// On this day of the month; months without that day are skipped.
+    MonthlyByDate { day: u8 },
+    /// On the nth weekday of the month (1..=5, or -1 for the last one).
+    MonthlyByWeekday { nth: i8, weekday: Weekday },
+}
+
+/// An RRULE-style recurrence rule, persisted in `HomeworkRecord::recurrence` as text such as
+/// "FREQ=WEEKLY;INTERVAL=1;BYDAY=MO,WE;COUNT=10;EXDATE=20251105".
+/// `COUNT` counts occurrences from the record's current due date onwards, so completing one
+/// occurrence decrements it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    /// Repeat every `interval` days / weeks / months (at least 1).
+    pub interval: u32,
+    /// Last allowed occurrence (inclusive).
+    pub until: Option<Datetime>,
+    /// Number of occurrences left, including the current one.
+    pub count: Option<u32>,
+    /// Dates (time of day ignored) on which an occurrence is skipped.
+    pub exceptions: Vec<Datetime>,
+}
+
+/// Stop scanning for occurrences after this many candidates, so a rule that can never
+/// match (e.g. day 31 every 12 months from April) cannot loop forever.
+const MAX_CANDIDATES: usize = 10_000;
+
+impl Recurrence {
+    pub fn new(frequency: Frequency) -> Self {
+        Self {
+            frequency,
+            interval: 1,
+            until: None,
+            count: None,
+            exceptions: Vec::new(),
+        }
+    }
+
+    /// Parse the persisted form. Returns `None` for empty or malformed text.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut freq = None;
+        let mut interval = 1;
+        let mut by_day: Vec<(i8, Weekday)> = Vec::new();
+        let mut by_month_day = None;
+        let mut until = None;
+        let mut count = None;
+        let mut exceptions = Vec::new();
+
+        for part in s.trim().split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once('=')?;
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => freq = Some(value.trim().to_ascii_uppercase()),
+                "INTERVAL" => interval = value.trim().parse::<u32>().ok()?.max(1),
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        by_day.push(parse_by_day(day.trim())?);
+                    }
+                }
+                "BYMONTHDAY" => by_month_day = Some(value.trim().parse::<u8>().ok()?),
+                "UNTIL" => until = Some(parse_compact(value.trim())?),
+                "COUNT" => count = Some(value.trim().parse::<u32>().ok()?),
+                "EXDATE" => {
+                    for date in value.split(',') {
+                        exceptions.push(parse_compact(date.trim())?);
+                    }
+                }
+                _ => return None,
+            }
+        }
+
+        let frequency = match freq?.as_str() {
+            "DAILY" => Frequency::Daily,
+            "WEEKLY" => Frequency::Weekly {
+                weekdays: by_day.iter().map(|(_, wd)| *wd).collect(),
+            },
+            "MONTHLY" => match (by_month_day, by_day.first()) {
+                (Some(day), _) if (1..=31).contains(&day) => Frequency::MonthlyByDate { day },
+                (None, Some(&(nth, weekday))) if nth != 0 => {
+                    Frequency::MonthlyByWeekday { nth, weekday }
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(Self { frequency, interval, until, count, exceptions })
+    }
+
+    /// Canonical persisted form, accepted back by `Recurrence::parse`.
+    pub fn to_rrule(&self) -> String {
+        let mut parts = Vec::new();
+        match &self.frequency {
+            Frequency::Daily => parts.push("FREQ=DAILY".to_string()),
+            Frequency::Weekly { weekdays } => {
+                parts.push("FREQ=WEEKLY".to_string());
+                if !weekdays.is_empty() {
+                    let days: Vec<&str> = weekdays.iter().map(|wd| weekday_code(*wd)).collect();
+                    parts.push(format!("BYDAY={}", days.join(",")));
+                }
+            }
+            Frequency::MonthlyByDate { day } => {
+                parts.push("FREQ=MONTHLY".to_string());
+                parts.push(format!("BYMONTHDAY={}", day));
+            }
+            Frequency::MonthlyByWeekday { nth, weekday } => {
+                parts.push("FREQ=MONTHLY".to_string());
+                parts.push(format!("BYDAY={}{}", nth, weekday_code(*weekday)));
+            }
+        }
+        parts.push(format!("INTERVAL={}", self.interval.max(1)));
+        if let Some(until) = &self.until {
+            parts.push(format!("UNTIL={}", format_compact(until)));
+        }
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+        if !self.exceptions.is_empty() {
+            let dates: Vec<String> = self
+                .exceptions
+                .iter()
+                .map(|d| format!("{:04}{:02}{:02}", d.year, d.month, d.day))
+                .collect();
+            parts.push(format!("EXDATE={}", dates.join(",")));
+        }
+        parts.join(";")
+    }
+
+    /// Short human description, e.g. "Every 2 weeks on Mon, Wed".
+    pub fn describe(&self) -> String {
+        let n = self.interval.max(1);
+        let every = |unit: &str| {
+            if n == 1 { format!("Every {}", unit) } else { format!("Every {} {}s", n, unit) }
+        };
+        let mut text = match &self.frequency {
+            Frequency::Daily => every("day"),
+            Frequency::Weekly { weekdays } if weekdays.is_empty() => every("week"),
+            Frequency::Weekly { weekdays } => {
+                let days: Vec<String> = weekdays.iter().map(|wd| wd.to_string()).collect();
+                format!("{} on {}", every("week"), days.join(", "))
+            }
+            Frequency::MonthlyByDate { day } => format!("{} on day {}", every("month"), day),
+            Frequency::MonthlyByWeekday { nth, weekday } => {
+                let ordinal = match nth {
+                    -1 => "last".to_string(),
+                    1 => "1st".to_string(),
+                    2 => "2nd".to_string(),
+                    3 => "3rd".to_string(),
+                    n => format!("{}th", n),
+                };
+                format!("{} on the {} {}", every("month"), ordinal, weekday)
+            }
+        };
+        if let Some(count) = self.count {
+            text.push_str(&format!(", {} left", count));
+        }
+        if let Some(until) = &self.until {
+            text.push_str(&format!(", until {:04}-{:02}-{:02}", until.year, until.month, until.day));
+        }
+        text
+    }
+
+    /// Walk occurrences in order starting at `start`, honouring interval, until, count and
+    /// exceptions. `visit` returns false to stop early.
+    pub fn for_each_occurrence(&self, start: &Datetime, mut visit: impl FnMut(Datetime) -> bool) {
+        let Some(start_naive) = start.to_naive() else { return };
+        let start_date = start_naive.date();
+        let time = start_naive.time();
+        let interval = self.interval.max(1) as i64;
+        let until = self.until.and_then(|u| u.to_naive());
+        let mut emitted: u32 = 0;
+
+        let mut emit = |date: NaiveDate| -> bool {
+            let at = date.and_time(time);
+            if at < start_naive {
+                return true;
+            }
+            if until.is_some_and(|u| at > u) {
+                return false;
+            }
+            if self.count.is_some_and(|c| emitted >= c) {
+                return false;
+            }
+            emitted += 1;
+            let dt = Datetime::from_naive(&at);
+            let skipped = self
+                .exceptions
+                .iter()
+                .any(|e| (e.year, e.month, e.day) == (dt.year, dt.month, dt.day));
+            skipped || visit(dt)
+        };
+
+        for step in 0..MAX_CANDIDATES as i64 {
+            let keep_going = match &self.frequency {
+                Frequency::Daily => match start_date.checked_add_signed(Duration::days(step * interval)) {
+                    Some(date) => emit(date),
+                    None => false,
+                },
+                Frequency::Weekly { weekdays } => {
+                    let mut days: Vec<u32> = if weekdays.is_empty() {
+                        vec![start_date.weekday().num_days_from_monday()]
+                    } else {
+                        weekdays.iter().map(|wd| wd.num_days_from_monday()).collect()
+                    };
+                    days.sort_unstable();
+                    days.dedup();
+                    let monday = start_date
+                        - Duration::days(start_date.weekday().num_days_from_monday() as i64);
+                    let week = monday + Duration::weeks(step * interval);
+                    days.into_iter()
+                        .all(|offset| emit(week + Duration::days(offset as i64)))
+                }
+                Frequency::MonthlyByDate { day } => match add_months(start_date, step * interval) {
+                    Some((year, month)) => match NaiveDate::from_ymd_opt(year, month, *day as u32) {
+                        Some(date) => emit(date),
+                        None => true,
+                    },
+                    None => false,
+                },
+                Frequency::MonthlyByWeekday { nth, weekday } => {
+                    match add_months(start_date, step * interval) {
+                        Some((year, month)) => match nth_weekday_of_month(year, month, *nth, *weekday) {
+                            Some(date) => emit(date),
+                            None => true,
+                        },
+                        None => false,
+                    }
+                }
+            };
+            if !keep_going {
+                break;
+            }
+        }
+    }
+
+    /// The first occurrence strictly after `after`, for a series starting at `start`.
+    pub fn next_after(&self, start: &Datetime, after: &Datetime) -> Option<Datetime> {
+        let mut found = None;
+        self.for_each_occurrence(start, |dt| {
+            if dt.cmp(after).is_gt() {
+                found = Some(dt);
+                false
+            } else {
+                true
+            }
+        });
+        found
+    }
+}
+
+pub(crate) fn weekday_code(wd: Weekday) -> &'static str {
+    match wd {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// "MO" or "2TU" / "-1FR" => (nth, weekday); nth is 0 when absent.
+fn parse_by_day(s: &str) -> Option<(i8, Weekday)> {
+    if s.len() < 2 {
+        return None;
+    }
+    let (nth, code) = s.split_at(s.len() - 2);
+    let weekday = match code.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let nth = if nth.is_empty() { 0 } else { nth.parse::<i8>().ok()? };
+    Some((nth, weekday))
+}
+
+/// "YYYYMMDD" or "YYYYMMDDTHHMM" => Datetime (end of day when no time is given).
+fn parse_compact(s: &str) -> Option<Datetime> {
+    let (date, time) = match s.split_once('T') {
+        Some((d, t)) => (d, Some(t)),
+        None => (s, None),
+    };
+    if date.len() != 8 {
+        return None;
+    }
+    let year = date[..4].parse().ok()?;
+    let month = date[4..6].parse().ok()?;
+    let day = date[6..8].parse().ok()?;
+    let (hour, minute) = match time {
+        Some(t) if t.len() >= 4 => (t[..2].parse().ok()?, t[2..4].parse().ok()?),
+        Some(_) => return None,
+        None => (23, 59),
+    };
+    let dt = Datetime::new(year, month, day, hour, minute);
+    if dt.is_valid() { Some(dt) } else { None }
+}
+
+fn format_compact(dt: &Datetime) -> String {
+    format!("{:04}{:02}{:02}T{:02}{:02}", dt.year, dt.month, dt.day, dt.hour, dt.minute)
+}
+
+fn add_months(date: NaiveDate, months: i64) -> Option<(i32, u32)> {
+    let total = date.year() as i64 * 12 + date.month0() as i64 + months;
+    let year = i32::try_from(total.div_euclid(12)).ok()?;
+    Some((year, total.rem_euclid(12) as u32 + 1))
+}
+
+fn nth_weekday_of_month(year: i32, month: u32, nth: i8, weekday: Weekday) -> Option<NaiveDate> {
+    if nth > 0 {
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, nth as u8)
+    } else {
+        // Count backwards from the last day of the month.
+        let days = Datetime::days_in_month(year as u16, month as u8) as u32;
+        let last = NaiveDate::from_ymd_opt(year, month, days)?;
+        let back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+        let date = last - Duration::days(back as i64 + 7 * (-(nth as i64) - 1));
+        (date.month() == month).then_some(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(rule: &Recurrence, start: Datetime, n: usize) -> Vec<Datetime> {
+        let mut out = Vec::new();
+        rule.for_each_occurrence(&start, |dt| {
+            out.push(dt);
+            out.len() < n
+        });
+        out
+    }
+
+    #[test]
+    fn weekly_on_chosen_days() {
+        let rule = Recurrence::parse("FREQ=WEEKLY;BYDAY=MO,WE").unwrap();
+        // 2025-11-05 is a Wednesday.
+        let got = collect(&rule, Datetime::new(2025, 11, 5, 9, 0), 3);
+        assert_eq!(
+            got,
+            vec![
+                Datetime::new(2025, 11, 5, 9, 0),
+                Datetime::new(2025, 11, 10, 9, 0),
+                Datetime::new(2025, 11, 12, 9, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_by_date_skips_short_months() {
+        let rule = Recurrence::parse("FREQ=MONTHLY;BYMONTHDAY=31").unwrap();
+        let got = collect(&rule, Datetime::new(2025, 1, 31, 12, 0), 3);
+        assert_eq!(
+            got,
+            vec![
+                Datetime::new(2025, 1, 31, 12, 0),
+                Datetime::new(2025, 3, 31, 12, 0),
+                Datetime::new(2025, 5, 31, 12, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_by_nth_weekday() {
+        let rule = Recurrence::parse("FREQ=MONTHLY;BYDAY=-1FR").unwrap();
+        let got = collect(&rule, Datetime::new(2025, 10, 1, 17, 0), 2);
+        assert_eq!(got, vec![Datetime::new(2025, 10, 31, 17, 0), Datetime::new(2025, 11, 28, 17, 0)]);
+
+        let rule = Recurrence::parse("FREQ=MONTHLY;BYDAY=2TU").unwrap();
+        let got = collect(&rule, Datetime::new(2025, 10, 1, 17, 0), 1);
+        assert_eq!(got, vec![Datetime::new(2025, 10, 14, 17, 0)]);
+    }
+
+    #[test]
+    fn count_until_and_exceptions() {
+        let rule = Recurrence::parse("FREQ=DAILY;INTERVAL=2;COUNT=3;EXDATE=20250103").unwrap();
+        let got = collect(&rule, Datetime::new(2025, 1, 1, 8, 0), 10);
+        assert_eq!(got, vec![Datetime::new(2025, 1, 1, 8, 0), Datetime::new(2025, 1, 5, 8, 0)]);
+
+        let rule = Recurrence::parse("FREQ=DAILY;UNTIL=20250102").unwrap();
+        assert_eq!(collect(&rule, Datetime::new(2025, 1, 1, 8, 0), 10).len(), 2);
+    }
+
+    #[test]
+    fn rrule_round_trips() {
+        for s in [
+            "FREQ=DAILY;INTERVAL=1",
+            "FREQ=WEEKLY;BYDAY=MO,FR;INTERVAL=2;COUNT=5",
+            "FREQ=MONTHLY;BYMONTHDAY=15;INTERVAL=1;UNTIL=20261231T2359",
+            "FREQ=MONTHLY;BYDAY=-1FR;INTERVAL=1;EXDATE=20251128",
+        ] {
+            assert_eq!(Recurrence::parse(s).unwrap().to_rrule(), s);
+        }
+        assert_eq!(Recurrence::parse(""), None);
+        assert_eq!(Recurrence::parse("FREQ=HOURLY"), None);
+    }
+}
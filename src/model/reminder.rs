@@ -0,0 +1,371 @@
+//! Reminders: how long before its due time a deadline should be brought up, and the scheduler
+//! deciding which reminders are due at each check. The scheduler is pure; the time comes from
+//! a `Clock` so the desktop app can use the system time and tests a hand-driven one.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::timezone::DueZone;
+use super::Deadline;
+
+const HOUR: u32 = 60;
+const DAY: u32 = 24 * HOUR;
+const WEEK: u32 = 7 * DAY;
+
+/// How long before the due time a reminder fires. Written as "30m", "2h", "1d" or "1w".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lead {
+    pub minutes: u32,
+}
+
+/// Reminders for deadlines that do not set their own.
+pub const DEFAULT_LEADS: [Lead; 3] = [Lead { minutes: WEEK }, Lead { minutes: DAY }, Lead { minutes: 2 * HOUR }];
+
+impl Lead {
+    /// "90m", "2h", "1d", "1w" (a bare number is minutes). Zero is rejected: at the due time
+    /// the deadline is reported as overdue instead.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        let digits = s.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let unit = match &s[digits.len()..] {
+            "" | "m" | "min" | "mins" => 1,
+            "h" | "hr" | "hrs" => HOUR,
+            "d" | "day" | "days" => DAY,
+            "w" | "wk" | "week" | "weeks" => WEEK,
+            _ => return None,
+        };
+        let minutes = digits.trim().parse::<u32>().ok()?.checked_mul(unit)?;
+        (minutes > 0).then_some(Self { minutes })
+    }
+
+    /// A list such as "1w, 1d 2h", sorted longest first with duplicates dropped.
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, String> {
+        let mut leads = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| Self::parse(part).ok_or_else(|| format!("unknown reminder '{part}' (try 30m, 2h, 1d or 1w)")))
+            .collect::<Result<Vec<_>, _>>()?;
+        leads.sort_unstable_by(|a, b| b.cmp(a));
+        leads.dedup();
+        Ok(leads)
+    }
+
+    /// "1w, 1d, 2h"; "none" for an empty list.
+    pub fn format_list(leads: &[Self]) -> String {
+        if leads.is_empty() {
+            return "none".to_string();
+        }
+        leads.iter().map(Self::to_string).collect::<Vec<_>>().join(", ")
+    }
+}
+
+impl fmt::Display for Lead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.minutes {
+            m if m % WEEK == 0 => write!(f, "{}w", m / WEEK),
+            m if m % DAY == 0 => write!(f, "{}d", m / DAY),
+            m if m % HOUR == 0 => write!(f, "{}h", m / HOUR),
+            m => write!(f, "{m}m"),
+        }
+    }
+}
+
+impl Serialize for Lead {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Lead {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| D::Error::custom(format!("invalid reminder {s:?}")))
+    }
+}
+
+/// Global reminder settings, the `[reminders]` table of config.toml.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReminderSettings {
+    /// Whether the desktop app shows notifications at all
+    pub enabled: bool,
+    /// Leads for deadlines that do not set their own
+    pub leads: Vec<Lead>,
+    /// How long "Snooze" puts a reminder off
+    pub snooze_minutes: u32,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        Self { enabled: true, leads: DEFAULT_LEADS.to_vec(), snooze_minutes: HOUR }
+    }
+}
+
+/// Source of the current time for the scheduler.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReminderKind {
+    /// One of the deadline's leads has been reached
+    Before(Lead),
+    /// The due time has passed; sent once per due date
+    Overdue,
+    /// A snoozed reminder coming back
+    Snoozed,
+}
+
+/// A notification to show.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reminder {
+    pub deadline_id: String,
+    pub name: String,
+    pub due: DateTime<Utc>,
+    pub kind: ReminderKind,
+}
+
+impl Reminder {
+    /// "Due in 1d 2h (2025-11-28 23:59)" or "Overdue since 2025-11-28 23:59", in the viewer's zone.
+    pub fn body(&self, now: DateTime<Utc>) -> String {
+        let local = DueZone::Local.from_utc(&self.due).to_string();
+        let minutes = (self.due - now).num_minutes();
+        if minutes > 0 {
+            format!("Due in {} ({local})", span(minutes))
+        } else {
+            format!("Overdue since {local}")
+        }
+    }
+}
+
+/// "1d 2h", "2h 5m" or "45m".
+fn span(minutes: i64) -> String {
+    let day = i64::from(DAY);
+    let hour = i64::from(HOUR);
+    match (minutes / day, minutes % day / hour, minutes % hour) {
+        (0, 0, m) => format!("{m}m"),
+        (0, h, m) => format!("{h}h {m}m"),
+        (d, 0, _) => format!("{d}d"),
+        (d, h, _) => format!("{d}d {h}h"),
+    }
+}
+
+/// Decides which reminders to send each time it is polled. Reminders whose time fell between
+/// two polls are sent at the second one; when several leads of a deadline were passed at once
+/// (e.g. while the computer slept) only the closest one is sent.
+pub struct ReminderScheduler<C: Clock> {
+    clock: C,
+    last_check: DateTime<Utc>,
+    /// (deadline id, due time) pairs already reported as overdue
+    overdue_sent: HashSet<(String, i64)>,
+    /// Deadline id => when its reminder comes back
+    snoozed: HashMap<String, DateTime<Utc>>,
+}
+
+impl<C: Clock> ReminderScheduler<C> {
+    /// Leads passed before the scheduler starts are not sent; deadlines already overdue are
+    /// reported at the first poll.
+    pub fn new(clock: C) -> Self {
+        let last_check = clock.now();
+        Self { clock, last_check, overdue_sent: HashSet::new(), snoozed: HashMap::new() }
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Reminders due since the last poll. Deadlines without their own reminders use `defaults`;
+    /// closed and projected deadlines are skipped.
+    pub fn poll(&mut self, deadlines: &[Deadline], defaults: &[Lead]) -> Vec<Reminder> {
+        let now = self.clock.now();
+        let since = std::mem::replace(&mut self.last_check, now);
+        let mut out = Vec::new();
+        for d in deadlines.iter().filter(|d| !d.status.is_closed() && !d.is_projected()) {
+            let Some(due) = d.due_zone.to_utc(&d.due_date) else { continue };
+            let reminder = |kind| Reminder { deadline_id: d.id.clone(), name: d.name.clone(), due, kind };
+
+            if let Some(until) = self.snoozed.get(&d.id).copied() {
+                if until <= now {
+                    self.snoozed.remove(&d.id);
+                    if due <= now {
+                        self.overdue_sent.insert((d.id.clone(), due.timestamp()));
+                    }
+                    out.push(reminder(ReminderKind::Snoozed));
+                }
+                continue;
+            }
+            if due <= now {
+                if self.overdue_sent.insert((d.id.clone(), due.timestamp())) {
+                    out.push(reminder(ReminderKind::Overdue));
+                }
+                continue;
+            }
+            let leads = d.reminders.as_deref().unwrap_or(defaults);
+            let passed = leads
+                .iter()
+                .filter(|lead| {
+                    let at = due - Duration::minutes(i64::from(lead.minutes));
+                    since < at && at <= now
+                })
+                .min();
+            if let Some(lead) = passed {
+                out.push(reminder(ReminderKind::Before(*lead)));
+            }
+        }
+        out
+    }
+
+    /// Hold back the deadline's reminders for `minutes`, then remind again.
+    pub fn snooze(&mut self, deadline_id: &str, minutes: u32) {
+        let until = self.clock.now() + Duration::minutes(i64::from(minutes));
+        self.snoozed.insert(deadline_id.to_string(), until);
+    }
+
+    /// When the next reminder for `deadlines` falls due, to sleep until then.
+    pub fn next_due(&self, deadlines: &[Deadline], defaults: &[Lead]) -> Option<DateTime<Utc>> {
+        let now = self.clock.now();
+        deadlines
+            .iter()
+            .filter(|d| !d.status.is_closed() && !d.is_projected())
+            .flat_map(|d| {
+                let due = d.due_zone.to_utc(&d.due_date);
+                let snoozed = self.snoozed.get(&d.id).copied();
+                let leads = d.reminders.as_deref().unwrap_or(defaults);
+                let before = leads.iter().filter_map(move |lead| {
+                    due.map(|due| due - Duration::minutes(i64::from(lead.minutes)))
+                });
+                match snoozed {
+                    Some(until) => vec![until],
+                    None => before.chain(due).collect(),
+                }
+            })
+            .filter(|at| *at > now)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::datetime::Datetime;
+    use crate::model::Status;
+    use std::cell::Cell;
+
+    struct ManualClock(Cell<DateTime<Utc>>);
+
+    impl ManualClock {
+        fn at(text: &str) -> Self {
+            Self(Cell::new(DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)))
+        }
+        fn advance(&self, minutes: i64) {
+            self.0.set(self.0.get() + Duration::minutes(minutes));
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0.get()
+        }
+    }
+
+    fn deadline(id: &str, due: Datetime) -> Deadline {
+        let mut d = Deadline::new(id.to_string(), id.to_string(), due, 5);
+        d.due_zone = DueZone::Offset(0);
+        d
+    }
+
+    fn kinds(reminders: &[Reminder]) -> Vec<(&str, ReminderKind)> {
+        reminders.iter().map(|r| (r.deadline_id.as_str(), r.kind)).collect()
+    }
+
+    #[test]
+    fn leads_parse_and_format() {
+        assert_eq!(Lead::parse("2h"), Some(Lead { minutes: 120 }));
+        assert_eq!(Lead::parse("90"), Some(Lead { minutes: 90 }));
+        assert_eq!(Lead::parse("1 week"), Some(Lead { minutes: WEEK }));
+        assert_eq!(Lead::parse("0m"), None);
+        assert_eq!(Lead::parse("soon"), None);
+        assert_eq!(Lead { minutes: 90 }.to_string(), "90m");
+        assert_eq!(Lead { minutes: 2 * DAY }.to_string(), "2d");
+
+        let leads = Lead::parse_list("2h, 1w 1d,2h").unwrap();
+        assert_eq!(Lead::format_list(&leads), "1w, 1d, 2h");
+        assert_eq!(leads, DEFAULT_LEADS);
+        assert_eq!(Lead::format_list(&[]), "none");
+        assert!(Lead::parse_list("1d, tomorrow").unwrap_err().contains("tomorrow"));
+        assert_eq!(serde_json::to_string(&leads).unwrap(), r#"["1w","1d","2h"]"#);
+        assert!(serde_json::from_str::<Lead>(r#""1y""#).is_err());
+    }
+
+    #[test]
+    fn sends_each_lead_as_it_passes_then_overdue_once() {
+        let clock = ManualClock::at("2025-11-20T12:00:00Z");
+        let mut scheduler = ReminderScheduler::new(&clock);
+        let mut essay = deadline("essay", Datetime::new(2025, 11, 22, 12, 0));
+        essay.reminders = Some(vec![Lead { minutes: DAY }, Lead { minutes: 2 * HOUR }]);
+        let list = vec![essay, deadline("lab", Datetime::new(2025, 11, 29, 6, 0))];
+
+        assert!(scheduler.poll(&list, &DEFAULT_LEADS).is_empty());
+        assert_eq!(scheduler.next_due(&list, &DEFAULT_LEADS), Some(clock.now() + Duration::days(1)));
+
+        clock.advance(24 * 60);
+        assert_eq!(kinds(&scheduler.poll(&list, &DEFAULT_LEADS)), vec![("essay", ReminderKind::Before(Lead { minutes: DAY }))]);
+        assert!(scheduler.poll(&list, &DEFAULT_LEADS).is_empty());
+
+        // Asleep past both the 2h reminder and the lab's one-week default: closest lead only.
+        clock.advance(23 * 60);
+        let sent = scheduler.poll(&list, &DEFAULT_LEADS);
+        assert_eq!(
+            kinds(&sent),
+            vec![("essay", ReminderKind::Before(Lead { minutes: 2 * HOUR })), ("lab", ReminderKind::Before(Lead { minutes: WEEK }))]
+        );
+        assert_eq!(sent[0].body(clock.now()), "Due in 1h 0m (".to_string() + &list[0].local_due().to_string() + ")");
+
+        clock.advance(60);
+        assert_eq!(kinds(&scheduler.poll(&list, &DEFAULT_LEADS)), vec![("essay", ReminderKind::Overdue)]);
+        clock.advance(60);
+        assert!(scheduler.poll(&list, &DEFAULT_LEADS).is_empty());
+    }
+
+    #[test]
+    fn overdue_at_startup_snooze_and_closed_deadlines() {
+        let clock = ManualClock::at("2025-11-20T12:00:00Z");
+        let mut scheduler = ReminderScheduler::new(&clock);
+        let mut done = deadline("done", Datetime::new(2025, 11, 19, 12, 0));
+        done.status = Status::Completed;
+        let mut quiet = deadline("quiet", Datetime::new(2025, 11, 20, 13, 0));
+        quiet.reminders = Some(Vec::new());
+        let list = vec![deadline("late", Datetime::new(2025, 11, 19, 12, 0)), done, quiet];
+
+        assert_eq!(kinds(&scheduler.poll(&list, &DEFAULT_LEADS)), vec![("late", ReminderKind::Overdue)]);
+
+        scheduler.snooze("late", 30);
+        clock.advance(20);
+        assert!(scheduler.poll(&list, &DEFAULT_LEADS).is_empty());
+        clock.advance(10);
+        let sent = scheduler.poll(&list, &DEFAULT_LEADS);
+        assert_eq!(kinds(&sent), vec![("late", ReminderKind::Snoozed)]);
+        assert!(sent[0].body(clock.now()).starts_with("Overdue since"));
+        clock.advance(30);
+        // The quiet deadline turns overdue without ever having reminded.
+        assert_eq!(kinds(&scheduler.poll(&list, &DEFAULT_LEADS)), vec![("quiet", ReminderKind::Overdue)]);
+    }
+}
@@ -0,0 +1,66 @@
+//! Moving a deadline to another day or hour from the calendar, by drag and drop or from the
+//! keyboard. Targets are picked in the viewer's zone; the result is the new due date in the
+//! deadline's own zone, ready to be stored as its `due_text`.
+
+use chrono::NaiveDate;
+
+use super::datetime::Datetime;
+use super::timezone::DueZone;
+use super::Deadline;
+
+/// New due date for `d` moved to local `day`, keeping its local time of day. With `hour`, the
+/// deadline moves into that hour instead, keeping its minute. `None` if nothing would change.
+pub fn due_moved_to(d: &Deadline, day: NaiveDate, hour: Option<u8>) -> Option<Datetime> {
+    let local = d.local_due();
+    let at = day.and_hms_opt(hour.unwrap_or(local.hour).into(), local.minute.into(), 0)?;
+    to_own_zone(d, Datetime::from_naive(&at))
+}
+
+/// New due date for `d` moved `minutes` later (earlier if negative) on the viewer's clock.
+pub fn due_shifted(d: &Deadline, minutes: i64) -> Option<Datetime> {
+    to_own_zone(d, d.local_due().shifted_minutes(minutes)?)
+}
+
+fn to_own_zone(d: &Deadline, local: Datetime) -> Option<Datetime> {
+    let due = local.convert(&DueZone::Local, &d.due_zone);
+    (due != d.due_date).then_some(due)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deadline(due: Datetime, zone: DueZone) -> Deadline {
+        let mut d = Deadline::new("id".to_string(), "Essay".to_string(), due, 5);
+        d.due_zone = zone;
+        d
+    }
+
+    #[test]
+    fn moving_to_a_day_keeps_the_time_of_day() {
+        let d = deadline(Datetime::new(2025, 11, 26, 23, 59), DueZone::Local);
+        let day = NaiveDate::from_ymd_opt(2025, 12, 3).unwrap();
+        assert_eq!(due_moved_to(&d, day, None), Some(Datetime::new(2025, 12, 3, 23, 59)));
+        assert_eq!(due_moved_to(&d, day, Some(9)), Some(Datetime::new(2025, 12, 3, 9, 59)));
+        // Dropping it back where it was changes nothing.
+        let same = NaiveDate::from_ymd_opt(2025, 11, 26).unwrap();
+        assert_eq!(due_moved_to(&d, same, None), None);
+        assert_eq!(due_shifted(&d, 0), None);
+        assert_eq!(due_shifted(&d, 60), Some(Datetime::new(2025, 11, 27, 0, 59)));
+    }
+
+    #[test]
+    fn targets_are_local_but_results_stay_in_the_deadlines_zone() {
+        let zone = DueZone::parse("AoE").unwrap();
+        let d = deadline(Datetime::new(2025, 11, 26, 23, 59), zone.clone());
+        let local = d.local_due();
+        let day = local.to_naive().unwrap().date() + chrono::Duration::days(2);
+
+        let moved = due_moved_to(&d, day, None).unwrap();
+        let shown = moved.convert(&zone, &DueZone::Local);
+        assert_eq!(shown.to_naive().unwrap().date(), day);
+        assert_eq!((shown.hour, shown.minute), (local.hour, local.minute));
+        // Two local days later is two days later in any fixed-offset zone.
+        assert_eq!(moved, Datetime::new(2025, 11, 28, 23, 59));
+    }
+}
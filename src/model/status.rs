@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a deadline is in its lifecycle, independent of the progress number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    #[default]
+    Open,
+    /// Cannot move on until something else happens
+    Blocked,
+    /// Waiting on someone else (a reply, feedback, a teammate)
+    Waiting,
+    /// Handed in, not graded or confirmed yet
+    Submitted,
+    Completed,
+    Cancelled,
+}
+
+impl Status {
+    pub const ALL: [Status; 6] = [
+        Status::Open,
+        Status::Blocked,
+        Status::Waiting,
+        Status::Submitted,
+        Status::Completed,
+        Status::Cancelled,
+    ];
+
+    /// Stable identifier for storage and filters.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Open => "open",
+            Status::Blocked => "blocked",
+            Status::Waiting => "waiting",
+            Status::Submitted => "submitted",
+            Status::Completed => "completed",
+            Status::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_ascii_lowercase();
+        Self::ALL.into_iter().find(|st| st.as_str() == s)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Open => "Open",
+            Status::Blocked => "Blocked",
+            Status::Waiting => "Waiting",
+            Status::Submitted => "Submitted",
+            Status::Completed => "Completed",
+            Status::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Completed and cancelled deadlines need no more work and are not ranked by urgency.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Status::Completed | Status::Cancelled)
+    }
+
+    /// Statuses reachable from this one. Closed deadlines can only be reopened, and a
+    /// blocked one has to be unblocked before it can be submitted or completed.
+    pub fn next(&self) -> &'static [Status] {
+        match self {
+            Status::Open => &[Status::Blocked, Status::Waiting, Status::Submitted, Status::Completed, Status::Cancelled],
+            Status::Blocked => &[Status::Open, Status::Waiting, Status::Cancelled],
+            Status::Waiting => &[Status::Open, Status::Blocked, Status::Submitted, Status::Completed, Status::Cancelled],
+            Status::Submitted => &[Status::Open, Status::Completed, Status::Cancelled],
+            Status::Completed | Status::Cancelled => &[Status::Open],
+        }
+    }
+
+    /// Staying put is always allowed.
+    pub fn can_become(&self, next: Status) -> bool {
+        *self == next || self.next().contains(&next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closed_statuses_only_reopen() {
+        for closed in [Status::Completed, Status::Cancelled] {
+            assert!(closed.is_closed());
+            assert!(closed.can_become(Status::Open));
+            assert!(!closed.can_become(Status::Submitted));
+        }
+        assert!(!Status::Blocked.can_become(Status::Completed));
+        assert!(Status::Waiting.can_become(Status::Submitted));
+        assert!(Status::Submitted.can_become(Status::Submitted));
+    }
+
+    #[test]
+    fn identifiers_round_trip() {
+        for s in Status::ALL {
+            assert_eq!(Status::parse(s.as_str()), Some(s));
+            assert_eq!(serde_json::to_string(&s).unwrap(), format!("\"{}\"", s.as_str()));
+        }
+        assert_eq!(Status::parse(" Completed "), Some(Status::Completed));
+        assert_eq!(Status::parse("done"), None);
+    }
+
+    #[test]
+    fn closed_deadlines_have_no_urgency() {
+        use crate::model::datetime::Datetime;
+        use crate::model::urgency::UrgencyStrategy;
+        use crate::model::Deadline;
+
+        let mut d = Deadline::new("1".into(), "Essay".into(), Datetime::now(), 8);
+        assert!(d.update_urgency(&UrgencyStrategy::Classic) > 0.0);
+        assert!(!d.sync_status_with_progress());
+        d.progress = 100;
+        d.status = Status::Blocked;
+        assert!(!d.sync_status_with_progress());
+        d.status = Status::Submitted;
+        assert!(d.sync_status_with_progress());
+        assert_eq!(d.status, Status::Completed);
+        assert_eq!(d.update_urgency(&UrgencyStrategy::Classic), 0.0);
+        d.progress = 60;
+        assert!(d.sync_status_with_progress());
+        assert_eq!(d.status, Status::Open);
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::prelude::*;
+use chrono_tz::Tz;
+
+use super::datetime::Datetime;
+
+/// The zone a due date's wall-clock time is expressed in.
+/// Persisted as text in `HomeworkRecord::due_zone` via `to_string` / `parse`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DueZone {
+    /// Whatever zone the viewer's machine is in.
+    Local,
+    /// Fixed offset east of UTC, in minutes. "AoE" (Anywhere on Earth) is -12:00.
+    Offset(i32),
+    /// IANA zone such as "Europe/Berlin"; follows daylight saving rules.
+    Named(Tz),
+}
+
+const AOE_OFFSET_MINUTES: i32 = -12 * 60;
+
+impl DueZone {
+    /// Resolve the viewer's zone to something that can be persisted,
+    /// preferring the IANA name and falling back to the current UTC offset.
+    pub fn local() -> Self {
+        iana_time_zone::get_timezone()
+            .ok()
+            .and_then(|name| name.parse::<Tz>().ok())
+            .map(DueZone::Named)
+            .unwrap_or_else(|| DueZone::Offset(Local::now().offset().local_minus_utc() / 60))
+    }
+
+    /// Accepts "" / "local", "UTC" / "Z", "AoE", "+HH:MM" / "-HH:MM" (optionally prefixed
+    /// with "UTC") and IANA names.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if s.is_empty() || s.eq_ignore_ascii_case("local") {
+            return Some(DueZone::Local);
+        }
+        if s.eq_ignore_ascii_case("utc") || s == "Z" {
+            return Some(DueZone::Offset(0));
+        }
+        if s.eq_ignore_ascii_case("aoe") {
+            return Some(DueZone::Offset(AOE_OFFSET_MINUTES));
+        }
+        let offset = s
+            .strip_prefix("UTC")
+            .or_else(|| s.strip_prefix("utc"))
+            .unwrap_or(s);
+        if offset.starts_with('+') || offset.starts_with('-') {
+            return parse_offset(offset).map(DueZone::Offset);
+        }
+        s.parse::<Tz>().ok().map(DueZone::Named)
+    }
+
+    /// Short label for display, e.g. "AoE", "UTC+05:30" or "Europe/Berlin".
+    pub fn label(&self) -> String {
+        match self {
+            DueZone::Local => "local".to_string(),
+            DueZone::Offset(0) => "UTC".to_string(),
+            DueZone::Offset(AOE_OFFSET_MINUTES) => "AoE".to_string(),
+            DueZone::Offset(m) => format!("UTC{}", format_offset(*m)),
+            DueZone::Named(tz) => tz.name().to_string(),
+        }
+    }
+
+    /// The instant a wall-clock time in this zone refers to.
+    /// Times skipped by a DST jump resolve to the same wall time one hour later.
+    pub fn to_utc(&self, dt: &Datetime) -> Option<DateTime<Utc>> {
+        let naive = dt.to_naive()?;
+        let resolve = |naive: NaiveDateTime| -> Option<DateTime<Utc>> {
+            match self {
+                DueZone::Local => Local
+                    .from_local_datetime(&naive)
+                    .earliest()
+                    .map(|t| t.with_timezone(&Utc)),
+                DueZone::Offset(m) => FixedOffset::east_opt(m * 60)?
+                    .from_local_datetime(&naive)
+                    .single()
+                    .map(|t| t.with_timezone(&Utc)),
+                DueZone::Named(tz) => tz
+                    .from_local_datetime(&naive)
+                    .earliest()
+                    .map(|t| t.with_timezone(&Utc)),
+            }
+        };
+        resolve(naive).or_else(|| resolve(naive + chrono::Duration::hours(1)))
+    }
+
+    /// The wall-clock time of an instant in this zone.
+    pub fn from_utc(&self, utc: &DateTime<Utc>) -> Datetime {
+        let naive = match self {
+            DueZone::Local => utc.with_timezone(&Local).naive_local(),
+            DueZone::Offset(m) => match FixedOffset::east_opt(m * 60) {
+                Some(offset) => utc.with_timezone(&offset).naive_local(),
+                None => utc.naive_utc(),
+            },
+            DueZone::Named(tz) => utc.with_timezone(tz).naive_local(),
+        };
+        Datetime::from_naive(&naive)
+    }
+}
+
+impl std::fmt::Display for DueZone {
+    /// Canonical persisted form, accepted back by `DueZone::parse`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DueZone::Local => Ok(()),
+            DueZone::Offset(0) => write!(f, "UTC"),
+            DueZone::Offset(AOE_OFFSET_MINUTES) => write!(f, "AoE"),
+            DueZone::Offset(m) => write!(f, "{}", format_offset(*m)),
+            DueZone::Named(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+fn parse_offset(s: &str) -> Option<i32> {
+    let (sign, rest) = match s.split_at(1) {
+        ("+", rest) => (1, rest),
+        ("-", rest) => (-1, rest),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+        None => (rest.parse::<i32>().ok()?, 0),
+    };
+    if hours > 14 || !(0..60).contains(&minutes) {
+        return None;
+    }
+    Some(sign * (hours * 60 + minutes))
+}
+
+fn format_offset(minutes: i32) -> String {
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let abs = minutes.abs();
+    format!("{}{:02}:{:02}", sign, abs / 60, abs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_round_trips() {
+        for s in ["", "UTC", "AoE", "+05:30", "-03:00", "Europe/Berlin", "America/New_York"] {
+            let zone = DueZone::parse(s).unwrap();
+            assert_eq!(zone.to_string(), s);
+        }
+        assert_eq!(DueZone::parse("UTC+8"), Some(DueZone::Offset(480)));
+        assert_eq!(DueZone::parse("-12:00"), Some(DueZone::Offset(-720)));
+        assert_eq!(DueZone::parse("Mars/Olympus_Mons"), None);
+        assert_eq!(DueZone::parse("+25:00"), None);
+    }
+
+    #[test]
+    fn dst_gap_resolves_forward() {
+        let berlin = DueZone::parse("Europe/Berlin").unwrap();
+        // 02:30 does not exist on 2025-03-30 in Berlin.
+        let utc = berlin.to_utc(&Datetime::new(2025, 3, 30, 2, 30)).unwrap();
+        assert_eq!(DueZone::Offset(0).from_utc(&utc), Datetime::new(2025, 3, 30, 1, 30));
+    }
+}
@@ -0,0 +1,208 @@
+use super::Deadline;
+use serde::{Deserialize, Serialize};
+
+/// A way of turning a deadline into an urgency score.
+/// Scores are normalized to 0..=1 so colours and sorting do not depend on the strategy.
+pub trait UrgencyModel {
+    /// Normalized urgency in 0..=1 given the hours left until due (negative when overdue).
+    fn score(&self, deadline: &Deadline, hours_left: f32) -> f32;
+}
+
+/// The original formula `difficulty * (100 - progress) / hours_left`,
+/// scaled so that a raw value of `saturation` or more maps to 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClassicUrgency {
+    pub saturation: f32,
+}
+
+impl Default for ClassicUrgency {
+    fn default() -> Self {
+        Self { saturation: 10.0 }
+    }
+}
+
+impl UrgencyModel for ClassicUrgency {
+    fn score(&self, deadline: &Deadline, hours_left: f32) -> f32 {
+        let remaining = 100.0 - deadline.progress as f32;
+        if remaining <= 0.0 {
+            return 0.0;
+        }
+        let delta = 0.0001; // safe.
+        let hours_left_safe = if hours_left < delta { delta } else { hours_left };
+        let raw = deadline.difficulty as f32 * remaining / hours_left_safe;
+        (raw / self.saturation).clamp(0.0, 1.0)
+    }
+}
+
+/// Time pressure follows a logistic curve centred on `midpoint_hours`, weighted by
+/// how much (and how hard) work is left. Stays bounded near and past the due date.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogisticUrgency {
+    pub midpoint_hours: f32,
+    pub steepness_hours: f32,
+}
+
+impl Default for LogisticUrgency {
+    fn default() -> Self {
+        Self { midpoint_hours: 48.0, steepness_hours: 24.0 }
+    }
+}
+
+impl UrgencyModel for LogisticUrgency {
+    fn score(&self, deadline: &Deadline, hours_left: f32) -> f32 {
+        let work_left = 1.0 - deadline.progress.min(100) as f32 / 100.0;
+        let weight = 0.5 + 0.5 * (deadline.difficulty.min(10) as f32 / 10.0);
+        let pressure = 1.0 / (1.0 + ((hours_left - self.midpoint_hours) / self.steepness_hours).exp());
+        (pressure * weight * work_left).clamp(0.0, 1.0)
+    }
+}
+
+/// Estimated effort left (difficulty points × `hours_per_difficulty`) compared with the
+/// working hours remaining before the due date. 1 means there is no slack left.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EffortUrgency {
+    pub hours_per_difficulty: f32,
+    pub working_hours_per_day: f32,
+}
+
+impl Default for EffortUrgency {
+    fn default() -> Self {
+        Self { hours_per_difficulty: 2.0, working_hours_per_day: 8.0 }
+    }
+}
+
+impl EffortUrgency {
+    /// Estimated hours of work still to do on `deadline`.
+    pub fn hours_left(&self, deadline: &Deadline) -> f32 {
+        let work_left = 1.0 - deadline.progress.min(100) as f32 / 100.0;
+        deadline.difficulty as f32 * self.hours_per_difficulty * work_left
+    }
+}
+
+impl UrgencyModel for EffortUrgency {
+    fn score(&self, deadline: &Deadline, hours_left: f32) -> f32 {
+        let effort = self.hours_left(deadline);
+        if effort <= 0.0 {
+            return 0.0;
+        }
+        let available = hours_left * self.working_hours_per_day / 24.0;
+        if available <= 0.0 {
+            return 1.0;
+        }
+        (effort / available).clamp(0.0, 1.0)
+    }
+}
+
+/// User-selectable built-in strategies, with their default parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UrgencyStrategy {
+    #[default]
+    Classic,
+    Logistic,
+    Effort,
+}
+
+impl UrgencyStrategy {
+    pub const ALL: [UrgencyStrategy; 3] = [
+        UrgencyStrategy::Classic,
+        UrgencyStrategy::Logistic,
+        UrgencyStrategy::Effort,
+    ];
+
+    /// Stable identifier for settings storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UrgencyStrategy::Classic => "classic",
+            UrgencyStrategy::Logistic => "logistic",
+            UrgencyStrategy::Effort => "effort",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.as_str() == s.trim())
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            UrgencyStrategy::Classic => "Classic (difficulty × work left ÷ hours)",
+            UrgencyStrategy::Logistic => "Logistic (capped curve around 2 days)",
+            UrgencyStrategy::Effort => "Effort vs. working hours left",
+        }
+    }
+}
+
+impl UrgencyModel for UrgencyStrategy {
+    fn score(&self, deadline: &Deadline, hours_left: f32) -> f32 {
+        match self {
+            UrgencyStrategy::Classic => ClassicUrgency::default().score(deadline, hours_left),
+            UrgencyStrategy::Logistic => LogisticUrgency::default().score(deadline, hours_left),
+            UrgencyStrategy::Effort => EffortUrgency::default().score(deadline, hours_left),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::datetime::Datetime;
+
+    fn deadline(difficulty: u8, progress: u8) -> Deadline {
+        let mut d = Deadline::new("id".to_string(), "Essay".to_string(), Datetime::new(2025, 11, 28, 23, 59), difficulty);
+        d.progress = progress;
+        d
+    }
+
+    #[test]
+    fn every_strategy_scores_within_zero_to_one() {
+        let hours = [-48.0, -0.5, 0.0, 0.01, 1.0, 24.0, 72.0, 24.0 * 365.0];
+        for strategy in UrgencyStrategy::ALL {
+            for difficulty in [0, 1, 5, 10, 255] {
+                for progress in [0, 50, 99, 100, 255] {
+                    for h in hours {
+                        let score = strategy.score(&deadline(difficulty, progress), h);
+                        assert!((0.0..=1.0).contains(&score), "{} gave {score} for {difficulty}/{progress}/{h}", strategy.as_str());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn closer_due_dates_score_higher() {
+        let d = deadline(5, 20);
+        for strategy in UrgencyStrategy::ALL {
+            assert!(strategy.score(&d, 12.0) >= strategy.score(&d, 240.0), "{}", strategy.as_str());
+        }
+        assert!(ClassicUrgency::default().score(&d, 240.0) < 1.0);
+        assert!(LogisticUrgency::default().score(&d, 12.0) > LogisticUrgency::default().score(&d, 240.0));
+    }
+
+    #[test]
+    fn overdue_work_is_most_urgent_and_finished_work_not_at_all() {
+        let open = deadline(5, 20);
+        assert_eq!(ClassicUrgency::default().score(&open, -3.0), 1.0);
+        assert_eq!(EffortUrgency::default().score(&open, -3.0), 1.0);
+        // The logistic curve flattens out rather than jumping to 1.
+        let logistic = LogisticUrgency::default();
+        assert!(logistic.score(&open, -3.0) >= logistic.score(&open, 3.0));
+
+        let done = deadline(5, 100);
+        for strategy in UrgencyStrategy::ALL {
+            assert_eq!(strategy.score(&done, -3.0), 0.0, "{}", strategy.as_str());
+            assert_eq!(strategy.score(&done, 24.0), 0.0, "{}", strategy.as_str());
+        }
+        assert_eq!(EffortUrgency::default().hours_left(&done), 0.0);
+        assert_eq!(EffortUrgency::default().hours_left(&deadline(5, 50)), 5.0);
+    }
+
+    #[test]
+    fn strategy_names_round_trip() {
+        for strategy in UrgencyStrategy::ALL {
+            assert_eq!(UrgencyStrategy::parse(strategy.as_str()), Some(strategy));
+            assert_eq!(UrgencyStrategy::parse(&format!(" {} ", strategy.as_str())), Some(strategy));
+        }
+        assert_eq!(UrgencyStrategy::parse("Classic"), None);
+        assert_eq!(UrgencyStrategy::parse("panic"), None);
+    }
+}
@@ -0,0 +1,211 @@
+//! How much work is due when: remaining effort per day for the workload heatmap, and the days
+//! and weeks that ask for more than the daily capacity, with which deadlines to start earlier.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::agenda::{local_date, occurrences_between};
+use super::urgency::EffortUrgency;
+use super::Deadline;
+
+/// The `[workload]` table of config.toml.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkloadSettings {
+    /// Hours of work a day can take; a week can take seven times as much
+    pub daily_capacity_hours: u32,
+}
+
+impl Default for WorkloadSettings {
+    fn default() -> Self {
+        Self { daily_capacity_hours: 6 }
+    }
+}
+
+impl WorkloadSettings {
+    pub const PRESETS: [u32; 6] = [2, 4, 6, 8, 10, 12];
+
+    pub fn daily_capacity(&self) -> f32 {
+        self.daily_capacity_hours.max(1) as f32
+    }
+}
+
+/// Hours of work left on `d`, estimated as in the effort urgency model from its difficulty
+/// and progress. Closed deadlines have none left.
+pub fn remaining_hours(d: &Deadline) -> f32 {
+    if d.status.is_closed() {
+        0.0
+    } else {
+        EffortUrgency::default().hours_left(d)
+    }
+}
+
+/// Work due on one local day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayLoad {
+    pub date: NaiveDate,
+    pub hours: f32,
+    /// Open deadlines (and projected occurrences) due that day
+    pub deadlines: Vec<Deadline>,
+}
+
+/// Work due on each local day of `from..=to`, including days with nothing due.
+pub fn daily_load(deadlines: &[Deadline], from: NaiveDate, to: NaiveDate) -> Vec<DayLoad> {
+    let mut days: Vec<DayLoad> = from
+        .iter_days()
+        .take_while(|date| *date <= to)
+        .map(|date| DayLoad { date, hours: 0.0, deadlines: Vec::new() })
+        .collect();
+    for d in occurrences_between(deadlines, from, to) {
+        let hours = remaining_hours(&d);
+        let Some(date) = local_date(&d) else { continue };
+        if hours <= 0.0 {
+            continue;
+        }
+        if let Some(day) = days.get_mut((date - from).num_days() as usize) {
+            day.hours += hours;
+            day.deadlines.push(d);
+        }
+    }
+    days
+}
+
+/// Heatmap shade from 0 (nothing due) to 4 (more than twice the daily capacity).
+pub fn heat_level(hours: f32, daily_capacity: f32) -> u8 {
+    if hours <= 0.0 {
+        return 0;
+    }
+    match hours / daily_capacity.max(1.0) {
+        r if r <= 0.5 => 1,
+        r if r <= 1.0 => 2,
+        r if r <= 2.0 => 3,
+        _ => 4,
+    }
+}
+
+/// A day or week with more work due than fits into it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overload {
+    /// First day of the stretch
+    pub from: NaiveDate,
+    /// 1 for a day, 7 for a week
+    pub days: usize,
+    pub hours: f32,
+    pub capacity: f32,
+    /// Deadlines to start earlier so the rest fits, with the day to start each by
+    pub start_earlier: Vec<(Deadline, NaiveDate)>,
+}
+
+impl Overload {
+    pub fn is_week(&self) -> bool {
+        self.days > 1
+    }
+}
+
+/// Days from `today` on, then whole weeks, with more work due than the capacity allows.
+/// `loads` are chunked into weeks from their first day, so start them on a week boundary.
+pub fn overloads(loads: &[DayLoad], daily_capacity: f32, today: NaiveDate) -> Vec<Overload> {
+    let daily_capacity = daily_capacity.max(1.0);
+    let days = loads
+        .iter()
+        .filter(|day| day.date >= today && day.hours > daily_capacity)
+        .map(|day| overload(std::slice::from_ref(day), daily_capacity, today));
+    let weeks = loads
+        .chunks(7)
+        .filter(|week| week.iter().map(|d| d.hours).sum::<f32>() > daily_capacity * week.len() as f32)
+        .map(|week| overload(week, daily_capacity, today));
+    days.chain(weeks).collect()
+}
+
+fn overload(span: &[DayLoad], daily_capacity: f32, today: NaiveDate) -> Overload {
+    let capacity = daily_capacity * span.len() as f32;
+    let hours: f32 = span.iter().map(|d| d.hours).sum();
+
+    // Untouched work first, then the biggest pieces, until what is left fits.
+    let mut candidates: Vec<(&Deadline, f32)> = span
+        .iter()
+        .flat_map(|day| day.deadlines.iter())
+        .map(|d| (d, remaining_hours(d)))
+        .collect();
+    candidates.sort_by(|a, b| (a.0.progress > 0).cmp(&(b.0.progress > 0)).then(b.1.total_cmp(&a.1)));
+    let mut excess = hours - capacity;
+    let mut start_earlier = Vec::new();
+    for (d, h) in candidates {
+        if excess <= 0.0 {
+            break;
+        }
+        excess -= h;
+        let Some(due) = local_date(d) else { continue };
+        let lead_days = (h / daily_capacity).ceil() as i64;
+        start_earlier.push((d.clone(), (due - Duration::days(lead_days)).max(today)));
+    }
+
+    Overload { from: span[0].date, days: span.len(), hours, capacity, start_earlier }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::datetime::Datetime;
+    use crate::model::timezone::DueZone;
+    use crate::model::Status;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn deadline(name: &str, due: Datetime, difficulty: u8, progress: u8) -> Deadline {
+        let mut d = Deadline::new(name.to_string(), name.to_string(), due, difficulty);
+        d.due_zone = DueZone::Local;
+        d.progress = progress;
+        d
+    }
+
+    #[test]
+    fn load_counts_remaining_effort_per_day() {
+        let mut done = deadline("Done", Datetime::new(2025, 11, 25, 12, 0), 9, 100);
+        done.status = Status::Completed;
+        let list = vec![
+            deadline("Essay", Datetime::new(2025, 11, 25, 23, 59), 5, 0),
+            deadline("Lab", Datetime::new(2025, 11, 25, 9, 0), 4, 50),
+            deadline("Quiz", Datetime::new(2025, 11, 27, 9, 0), 1, 0),
+            done,
+        ];
+        let loads = daily_load(&list, date(2025, 11, 24), date(2025, 11, 30));
+        assert_eq!(loads.len(), 7);
+        let hours: Vec<f32> = loads.iter().map(|d| d.hours).collect();
+        assert_eq!(hours, vec![0.0, 14.0, 0.0, 2.0, 0.0, 0.0, 0.0]);
+        assert_eq!(loads[1].deadlines.len(), 2);
+
+        assert_eq!(heat_level(0.0, 6.0), 0);
+        assert_eq!(heat_level(2.0, 6.0), 1);
+        assert_eq!(heat_level(6.0, 6.0), 2);
+        assert_eq!(heat_level(14.0, 6.0), 4);
+    }
+
+    #[test]
+    fn overloaded_days_suggest_starting_untouched_work_earlier() {
+        let today = date(2025, 11, 24);
+        let list = vec![
+            deadline("Essay", Datetime::new(2025, 11, 27, 23, 59), 5, 0),
+            deadline("Lab", Datetime::new(2025, 11, 27, 9, 0), 6, 50),
+            deadline("Quiz", Datetime::new(2025, 11, 27, 10, 0), 1, 0),
+        ];
+        let loads = daily_load(&list, today, today + Duration::days(6));
+        let found = overloads(&loads, 6.0, today);
+
+        // 10 + 6 + 2 hours on Thursday: the untouched essay should start two days ahead and
+        // the (also untouched) quiz a day ahead; the half-done lab then fits.
+        assert_eq!(found.len(), 1);
+        let day = &found[0];
+        assert!(!day.is_week());
+        assert_eq!((day.from, day.hours, day.capacity), (date(2025, 11, 27), 18.0, 6.0));
+        let starts: Vec<(&str, NaiveDate)> = day.start_earlier.iter().map(|(d, s)| (d.name.as_str(), *s)).collect();
+        assert_eq!(starts, vec![("Essay", date(2025, 11, 25)), ("Quiz", date(2025, 11, 26))]);
+
+        // At 2 hours a day the week is over capacity too, and start dates never lie in the past.
+        let found = overloads(&loads, 2.0, today);
+        assert!(found.last().unwrap().is_week());
+        assert!(found.iter().flat_map(|o| &o.start_earlier).all(|(_, s)| *s >= today));
+    }
+}
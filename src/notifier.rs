@@ -0,0 +1,125 @@
+//! Native notifications for reminders in the desktop build. `ReminderScheduler` decides what
+//! is due; this loop feeds it the open workspace's deadlines and shows what it returns.
+//!
+//! "Snooze" and "Mark done" buttons need a notification server with action support (Linux);
+//! elsewhere the notification is informational only.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use dioxus::prelude::*;
+use futures_util::future::{select, Either};
+use notify_rust::Notification;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use deadline_tracker::model::reminder::{Reminder, ReminderKind, ReminderScheduler, ReminderSettings, SystemClock};
+use deadline_tracker::persistence::HomeworkRepo;
+
+/// Longest wait between checks, so edits and clock changes are picked up.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Button chosen on a notification.
+enum Action {
+    Snooze(String),
+    Done(String),
+}
+
+/// Show reminders for `repo` until the owning component unmounts.
+pub async fn run_reminders(repo: Arc<dyn HomeworkRepo>, settings: Signal<ReminderSettings>) {
+    let (actions, mut chosen) = unbounded_channel();
+    let mut scheduler = ReminderScheduler::new(SystemClock);
+    loop {
+        // Peek: reading would restart the loop, and with it the scheduler, on every settings change.
+        let current = settings.peek().clone();
+        let deadlines: Vec<_> = match repo.list() {
+            Ok(records) => records.iter().map(|r| r.to_deadline()).collect(),
+            Err(e) => {
+                eprintln!("Reminders: could not load deadlines: {e}");
+                Vec::new()
+            }
+        };
+        // Polled even when disabled, so turning notifications back on does not replay old ones.
+        for reminder in scheduler.poll(&deadlines, &current.leads) {
+            if current.enabled {
+                show(&reminder, &reminder.body(scheduler.now()), current.snooze_minutes, actions.clone());
+            }
+        }
+
+        let wait = scheduler
+            .next_due(&deadlines, &current.leads)
+            .and_then(|at| (at - Utc::now()).to_std().ok())
+            .map_or(CHECK_INTERVAL, |d| d.min(CHECK_INTERVAL));
+        let sleep = std::pin::pin!(tokio::time::sleep(wait));
+        let next_action = std::pin::pin!(chosen.recv());
+        match select(sleep, next_action).await {
+            Either::Right((Some(Action::Snooze(uid)), _)) => scheduler.snooze(&uid, current.snooze_minutes),
+            Either::Right((Some(Action::Done(uid)), _)) => {
+                if let Err(e) = mark_done(repo.as_ref(), &uid) {
+                    eprintln!("Reminders: could not mark {uid} done: {e}");
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn mark_done(repo: &dyn HomeworkRepo, uid: &str) -> anyhow::Result<()> {
+    if let Some(mut rec) = repo.get(uid)? {
+        let mut d = rec.to_deadline();
+        d.mark_done();
+        rec.apply_deadline(&d, Utc::now().timestamp())?;
+        repo.update(rec)?;
+    }
+    Ok(())
+}
+
+fn show(reminder: &Reminder, body: &str, snooze_minutes: u32, actions: UnboundedSender<Action>) {
+    let summary = match reminder.kind {
+        ReminderKind::Overdue => format!("Overdue: {}", reminder.name),
+        _ => reminder.name.clone(),
+    };
+    let mut notification = Notification::new();
+    notification.appname("Deadline Tracker").summary(&summary).body(body);
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        notification
+            .action("snooze", &format!("Snooze {}", snooze_label(snooze_minutes)))
+            .action("done", "Mark done");
+        let uid = reminder.deadline_id.clone();
+        match notification.show() {
+            // Waiting blocks until the notification is answered or dismissed.
+            Ok(handle) => {
+                std::thread::spawn(move || {
+                    handle.wait_for_action(|action| {
+                        let chosen = match action {
+                            "snooze" => Action::Snooze(uid),
+                            "done" => Action::Done(uid),
+                            _ => return,
+                        };
+                        let _ = actions.send(chosen);
+                    });
+                });
+            }
+            Err(e) => eprintln!("Reminders: could not show a notification: {e}"),
+        }
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        let _ = (snooze_minutes, actions);
+        if let Err(e) = notification.show() {
+            eprintln!("Reminders: could not show a notification: {e}");
+        }
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn snooze_label(minutes: u32) -> String {
+    match minutes {
+        60 => "1 hour".to_string(),
+        m if m % 60 == 0 => format!("{} hours", m / 60),
+        m => format!("{m} min"),
+    }
+}
@@ -0,0 +1,277 @@
+//! The archive: completed deadlines stay in the main list, calendar and urgency sort for an
+//! `ArchiveDelay` after they are finished and then only show up here, grouped by the month
+//! they were completed in and compared with when they were due.
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, NaiveDate};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::model::datetime::Datetime;
+use crate::model::timezone::DueZone;
+use crate::persistence::query::ArchiveFilter;
+use crate::persistence::types::HomeworkRecord;
+
+const DAY_SECONDS: i64 = 24 * 60 * 60;
+
+/// How long a completed deadline stays in the main list before it moves to the archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArchiveDelay {
+    /// `None` keeps completed deadlines in the main list.
+    pub days: Option<u32>,
+}
+
+impl Default for ArchiveDelay {
+    fn default() -> Self {
+        Self { days: Some(7) }
+    }
+}
+
+impl ArchiveDelay {
+    /// Choices offered in the settings panel.
+    pub const PRESETS: [Option<u32>; 5] = [Some(0), Some(1), Some(7), Some(30), None];
+
+    /// Records completed at or before this Unix time are archived.
+    pub fn cutoff(&self, now_ts: i64) -> Option<i64> {
+        self.days.map(|d| now_ts - i64::from(d) * DAY_SECONDS)
+    }
+
+    /// Query filter for the main list: everything not archived yet.
+    pub fn exclude(&self, now_ts: i64) -> ArchiveFilter {
+        match self.cutoff(now_ts) {
+            Some(cutoff) => ArchiveFilter::Exclude { cutoff },
+            None => ArchiveFilter::Include,
+        }
+    }
+
+    /// Query filter for the archive itself; empty while archiving is off.
+    pub fn only(&self, now_ts: i64) -> ArchiveFilter {
+        ArchiveFilter::Only { cutoff: self.cutoff(now_ts).unwrap_or(i64::MIN) }
+    }
+
+    pub fn label(&self) -> String {
+        match self.days {
+            Some(0) => "Right away".to_string(),
+            Some(1) => "After 1 day".to_string(),
+            Some(d) => format!("After {} days", d),
+            None => "Never".to_string(),
+        }
+    }
+}
+
+// Written to config.toml as a number of days, or "never".
+impl Serialize for ArchiveDelay {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.days {
+            Some(d) => serializer.serialize_u32(d),
+            None => serializer.serialize_str("never"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ArchiveDelay {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Days(u32),
+            Word(String),
+        }
+        match Repr::deserialize(deserializer)? {
+            Repr::Days(d) => Ok(Self { days: Some(d) }),
+            Repr::Word(w) if w.trim().eq_ignore_ascii_case("never") => Ok(Self { days: None }),
+            Repr::Word(w) => Err(D::Error::custom(format!("expected a number of days or \"never\", got {w:?}"))),
+        }
+    }
+}
+
+/// An archived record with its planned and actual completion time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArchiveEntry {
+    pub record: HomeworkRecord,
+    /// Due date in the viewer's zone
+    pub planned: Datetime,
+    /// Completion time in the viewer's zone
+    pub completed: Datetime,
+    /// Minutes from due to completion; negative when finished early
+    pub lateness_minutes: i64,
+}
+
+impl ArchiveEntry {
+    /// `None` unless the record has a completion time and a readable due date.
+    pub fn new(record: HomeworkRecord, viewer: &DueZone) -> Option<Self> {
+        let completed_at = DateTime::from_timestamp(record.completed_at?, 0)?;
+        let zone = DueZone::parse(&record.due_zone).unwrap_or(DueZone::Local);
+        let due_at = zone.to_utc(&Datetime::from_string(&record.due_text)?)?;
+        Some(Self {
+            planned: viewer.from_utc(&due_at),
+            completed: viewer.from_utc(&completed_at),
+            lateness_minutes: (completed_at - due_at).num_minutes(),
+            record,
+        })
+    }
+
+    pub fn is_on_time(&self) -> bool {
+        self.lateness_minutes <= 0
+    }
+
+    /// "YYYY-MM" of the month it was completed in.
+    pub fn month_key(&self) -> String {
+        format!("{:04}-{:02}", self.completed.year, self.completed.month)
+    }
+}
+
+/// Archive entries for `records`, most recently completed first.
+pub fn archive_entries(records: Vec<HomeworkRecord>, viewer: &DueZone) -> Vec<ArchiveEntry> {
+    let mut entries: Vec<ArchiveEntry> =
+        records.into_iter().filter_map(|r| ArchiveEntry::new(r, viewer)).collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.record.completed_at));
+    entries
+}
+
+/// Completion months present in `entries`, newest first.
+pub fn months(entries: &[ArchiveEntry]) -> Vec<String> {
+    let keys: BTreeSet<String> = entries.iter().map(ArchiveEntry::month_key).collect();
+    keys.into_iter().rev().collect()
+}
+
+/// Tags used by `entries`, alphabetically.
+pub fn tags(entries: &[ArchiveEntry]) -> Vec<String> {
+    let tags: BTreeSet<&str> = entries.iter().flat_map(|e| e.record.tags.iter().map(String::as_str)).collect();
+    tags.into_iter().map(str::to_string).collect()
+}
+
+/// "2026-03" => "March 2026".
+pub fn month_label(key: &str) -> String {
+    NaiveDate::parse_from_str(&format!("{key}-01"), "%Y-%m-%d")
+        .map(|d| d.format("%B %Y").to_string())
+        .unwrap_or_else(|_| key.to_string())
+}
+
+/// "3d 4h early", "45m late" or "on time".
+pub fn describe_lateness(minutes: i64) -> String {
+    let span = |m: i64| match (m / (24 * 60), m / 60 % 24, m % 60) {
+        (0, 0, m) => format!("{m}m"),
+        (0, h, m) => format!("{h}h {m}m"),
+        (d, 0, _) => format!("{d}d"),
+        (d, h, _) => format!("{d}d {h}h"),
+    };
+    match minutes {
+        0 => "on time".to_string(),
+        m if m < 0 => format!("{} early", span(-m)),
+        m => format!("{} late", span(m)),
+    }
+}
+
+/// How a set of archived deadlines went, for the review at the top of the archive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    pub count: usize,
+    /// Completed no later than they were due
+    pub on_time: usize,
+    /// Typical lateness (median, negative = early); `None` without entries
+    pub median_lateness_minutes: Option<i64>,
+}
+
+impl ArchiveSummary {
+    pub fn of<'a>(entries: impl IntoIterator<Item = &'a ArchiveEntry>) -> Self {
+        let mut lateness: Vec<i64> = Vec::new();
+        let mut on_time = 0;
+        for e in entries {
+            lateness.push(e.lateness_minutes);
+            on_time += usize::from(e.is_on_time());
+        }
+        lateness.sort_unstable();
+        let n = lateness.len();
+        let median = match n {
+            0 => None,
+            _ if n % 2 == 1 => Some(lateness[n / 2]),
+            _ => Some((lateness[n / 2 - 1] + lateness[n / 2]) / 2),
+        };
+        Self { count: n, on_time, median_lateness_minutes: median }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Status;
+
+    fn completed(name: &str, due_text: &str, due_zone: &str, completed_at: &str) -> HomeworkRecord {
+        let completed_at = DateTime::parse_from_rfc3339(completed_at).unwrap().timestamp();
+        HomeworkRecord {
+            uid: name.to_string(),
+            name: name.to_string(),
+            due_text: due_text.to_string(),
+            due_zone: due_zone.to_string(),
+            difficulty: 5,
+            progress: 100,
+            tags: vec!["course".to_string()],
+            milestones: Vec::new(),
+            recurrence: String::new(),
+            external_uid: String::new(),
+            deleted: false,
+            deleted_at: None,
+            status: Status::Completed,
+            completed_at: Some(completed_at),
+            cancelled_at: None,
+            reminders: None,
+            created_at: 0,
+            updated_at: completed_at,
+            schema_version: crate::persistence::types::CURRENT_SCHEMA_VERSION,
+        }
+    }
+
+    #[test]
+    fn entries_compare_completion_with_the_due_time_across_zones() {
+        let utc = DueZone::Offset(0);
+        let entries = archive_entries(
+            vec![
+                // Due 23:59 AoE = 11:59 UTC the next day; finished 2h before that.
+                completed("Essay", "2026-03-02 23:59", "AoE", "2026-03-03T09:59:00Z"),
+                completed("Lab", "2026-02-27 12:00", "UTC", "2026-03-01T13:30:00Z"),
+            ],
+            &utc,
+        );
+        assert_eq!(entries.iter().map(|e| e.record.name.as_str()).collect::<Vec<_>>(), vec!["Essay", "Lab"]);
+        assert_eq!(entries[0].lateness_minutes, -120);
+        assert_eq!(entries[0].planned.to_string(), "2026-03-03 11:59");
+        assert_eq!(describe_lateness(entries[0].lateness_minutes), "2h 0m early");
+        assert_eq!(describe_lateness(entries[1].lateness_minutes), "2d 1h late");
+        assert_eq!(months(&entries), vec!["2026-03"]);
+        assert_eq!(month_label("2026-03"), "March 2026");
+
+        let summary = ArchiveSummary::of(&entries);
+        assert_eq!(summary.count, 2);
+        assert_eq!(summary.on_time, 1);
+        assert_eq!(summary.median_lateness_minutes, Some((-120 + 2 * 24 * 60 + 90) / 2));
+        assert_eq!(ArchiveSummary::of(&[]).median_lateness_minutes, None);
+    }
+
+    #[test]
+    fn delay_decides_what_is_archived() {
+        let rec = completed("Essay", "2026-03-02 23:59", "UTC", "2026-03-03T00:00:00Z");
+        let done = rec.completed_at.unwrap();
+        let week = ArchiveDelay::default();
+        assert!(week.exclude(done + DAY_SECONDS).matches(&rec));
+        assert!(!week.only(done + DAY_SECONDS).matches(&rec));
+        assert!(week.only(done + 7 * DAY_SECONDS).matches(&rec));
+        assert!(ArchiveDelay { days: Some(0) }.only(done).matches(&rec));
+
+        let never = ArchiveDelay { days: None };
+        assert_eq!(never.exclude(i64::MAX), ArchiveFilter::Include);
+        assert!(!never.only(i64::MAX).matches(&rec));
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Wrapper {
+            delay: ArchiveDelay,
+        }
+        for (text, days) in [("delay = 30\n", Some(30)), ("delay = \"never\"\n", None)] {
+            let parsed: Wrapper = toml::from_str(text).unwrap();
+            assert_eq!(parsed.delay.days, days);
+            assert_eq!(toml::to_string(&parsed).unwrap(), text);
+        }
+        assert!(toml::from_str::<Wrapper>("delay = \"soon\"\n").is_err());
+    }
+}
@@ -0,0 +1,426 @@
+//! Behaviour every `HomeworkRepo` implementation must share, run against each backend.
+//! A new backend gets the whole suite by adding one line to the `conformance!` list below.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use ulid::Ulid;
+
+use crate::model::reminder::Lead;
+use crate::model::Status;
+
+use crate::persistence::events::RepoEvent;
+use crate::persistence::history::HistoryRepo;
+use crate::persistence::json::JsonRepo;
+use crate::persistence::memory::MemoryRepo;
+use crate::persistence::query::{ArchiveFilter, Query, QuerySort, SortField};
+use crate::persistence::repo::{HomeworkRepo, RepoError};
+use crate::persistence::sqlite::SqliteRepo;
+use crate::persistence::types::{MilestoneRecord, NewHomework, Patch, CURRENT_SCHEMA_VERSION};
+use crate::persistence::workspace::MergedRepo;
+
+/// Fresh data directory, removed again when dropped.
+pub(crate) struct TempDir(pub(crate) PathBuf);
+
+impl TempDir {
+    pub(crate) fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("deadline-tracker-test-{}", Ulid::new()));
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Open deadline due at `due` ("YYYY-MM-DD HH:MM") in `zone`, tagged "course".
+pub(crate) fn new_homework(name: &str, due: &str, zone: &str) -> NewHomework {
+    NewHomework {
+        name: name.to_string(),
+        due_text: due.to_string(),
+        due_zone: zone.to_string(),
+        difficulty: 5,
+        progress: 0,
+        tags: vec!["course".to_string()],
+        milestones: Vec::new(),
+        recurrence: String::new(),
+        external_uid: String::new(),
+        status: Status::Open,
+        reminders: None,
+    }
+}
+
+fn names(repo: &dyn HomeworkRepo) -> Vec<String> {
+    repo.list().unwrap().into_iter().map(|r| r.name).collect()
+}
+
+fn create_assigns_uid_and_timestamps(repo: &dyn HomeworkRepo) {
+    let before = Utc::now().timestamp();
+    let rec = repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+    assert!(!rec.uid.is_empty());
+    assert!(rec.created_at >= before);
+    assert_eq!(rec.created_at, rec.updated_at);
+    assert!(!rec.deleted);
+    assert_eq!(rec.deleted_at, None);
+    assert_eq!(rec.schema_version, CURRENT_SCHEMA_VERSION);
+    assert_eq!(repo.get(&rec.uid).unwrap(), Some(rec));
+}
+
+fn get_unknown_uid_is_none(repo: &dyn HomeworkRepo) {
+    assert_eq!(repo.get("missing").unwrap(), None);
+}
+
+fn list_keeps_insertion_order(repo: &dyn HomeworkRepo) {
+    // Later due dates first, so a backend sorting by due_text would fail.
+    let a = repo.create(new_homework("A", "2025-12-31 23:59", "UTC")).unwrap();
+    repo.create(new_homework("B", "2025-01-01 00:00", "UTC")).unwrap();
+    repo.create(new_homework("C", "2025-06-15 12:00", "UTC")).unwrap();
+    assert_eq!(names(repo), vec!["A", "B", "C"]);
+
+    // Updating a record does not move it.
+    repo.patch(&a.uid, Patch { progress: Some(50), ..Default::default() }).unwrap();
+    assert_eq!(names(repo), vec!["A", "B", "C"]);
+}
+
+fn list_excludes_deleted(repo: &dyn HomeworkRepo) {
+    let a = repo.create(new_homework("A", "2025-11-28 23:59", "UTC")).unwrap();
+    repo.create(new_homework("B", "2025-11-29 23:59", "UTC")).unwrap();
+    repo.delete(&a.uid).unwrap();
+    assert_eq!(names(repo), vec!["B"]);
+
+    // Deleted records stay reachable by uid and show up in the trash.
+    let deleted = repo.get(&a.uid).unwrap().unwrap();
+    assert!(deleted.deleted);
+    assert!(deleted.deleted_at.is_some());
+    let trash: Vec<String> = repo.list_deleted().unwrap().into_iter().map(|r| r.uid).collect();
+    assert_eq!(trash, vec![a.uid]);
+}
+
+fn update_replaces_fields_and_bumps_timestamp(repo: &dyn HomeworkRepo) {
+    let mut rec = repo.create(new_homework("Lab", "2025-11-28 23:59", "UTC")).unwrap();
+    rec.name = "Lab 2".to_string();
+    rec.tags = vec!["os".to_string(), "lab".to_string()];
+    rec.updated_at = 0;
+    let updated = repo.update(rec.clone()).unwrap();
+    assert!(updated.updated_at >= rec.created_at);
+    assert_eq!(updated.name, "Lab 2");
+    assert_eq!(repo.get(&rec.uid).unwrap(), Some(updated));
+}
+
+fn patch_changes_only_given_fields(repo: &dyn HomeworkRepo) {
+    let rec = repo.create(new_homework("Quiz", "2025-11-28 23:59", "UTC")).unwrap();
+    let patched = repo
+        .patch(&rec.uid, Patch { progress: Some(40), difficulty: Some(8), ..Default::default() })
+        .unwrap();
+    assert_eq!(patched.progress, 40);
+    assert_eq!(patched.difficulty, 8);
+    assert_eq!(patched.name, rec.name);
+    assert_eq!(patched.tags, rec.tags);
+    assert!(patched.updated_at >= rec.updated_at);
+    assert_eq!(repo.get(&rec.uid).unwrap(), Some(patched));
+}
+
+fn restore_and_purge(repo: &dyn HomeworkRepo) {
+    let a = repo.create(new_homework("A", "2025-11-28 23:59", "UTC")).unwrap();
+    let b = repo.create(new_homework("B", "2025-11-29 23:59", "UTC")).unwrap();
+    repo.delete(&a.uid).unwrap();
+    repo.delete(&b.uid).unwrap();
+
+    let restored = repo.restore(&a.uid).unwrap();
+    assert!(!restored.deleted);
+    assert_eq!(restored.deleted_at, None);
+    assert_eq!(names(repo), vec!["A"]);
+
+    repo.purge(&b.uid).unwrap();
+    assert_eq!(repo.get(&b.uid).unwrap(), None);
+    assert!(repo.list_deleted().unwrap().is_empty());
+
+    repo.delete(&a.uid).unwrap();
+    assert_eq!(repo.purge_deleted_before(0).unwrap(), 0);
+    assert_eq!(repo.purge_deleted_before(i64::MAX).unwrap(), 1);
+    assert_eq!(repo.get(&a.uid).unwrap(), None);
+}
+
+fn unknown_uid_is_not_found(repo: &dyn HomeworkRepo) {
+    let mut ghost = repo.create(new_homework("Ghost", "2025-11-28 23:59", "UTC")).unwrap();
+    ghost.uid = "missing".to_string();
+    assert_eq!(repo.update(ghost), Err(RepoError::NotFound));
+    assert_eq!(repo.patch("missing", Patch::default()), Err(RepoError::NotFound));
+    assert_eq!(repo.delete("missing"), Err(RepoError::NotFound));
+    assert_eq!(repo.restore("missing"), Err(RepoError::NotFound));
+    assert_eq!(repo.purge("missing"), Err(RepoError::NotFound));
+}
+
+fn query_filters(repo: &dyn HomeworkRepo) {
+    let mut os = new_homework("OS lab", "2025-11-28 23:59", "UTC");
+    os.tags = vec!["os".to_string(), "lab".to_string()];
+    os.progress = 20;
+    os.difficulty = 8;
+    let os = repo.create(os).unwrap();
+    let mut essay = new_homework("History essay", "2025-12-05 12:00", "UTC");
+    essay.tags = vec!["writing".to_string()];
+    essay.progress = 80;
+    repo.create(essay).unwrap();
+    let mut quiz = new_homework("Networks quiz", "2025-12-01 09:00", "UTC");
+    quiz.tags = vec!["net".to_string(), "Lab".to_string()];
+    let quiz = repo.create(quiz).unwrap();
+
+    let find = |q: Query| -> Vec<String> { repo.query(&q).unwrap().into_iter().map(|r| r.name).collect() };
+
+    assert_eq!(find(Query::default()), names(repo));
+    assert_eq!(find(Query { tags: vec!["LAB".to_string()], ..Default::default() }), vec!["OS lab", "Networks quiz"]);
+    assert_eq!(find(Query { tags: vec!["lab".to_string(), "os".to_string()], ..Default::default() }), vec!["OS lab"]);
+    assert_eq!(
+        find(Query {
+            due_from: Some("2025-12-01 00:00".to_string()),
+            due_to: Some("2025-12-01 23:59".to_string()),
+            ..Default::default()
+        }),
+        vec!["Networks quiz"]
+    );
+    assert_eq!(find(Query { progress: Some(50..=100), ..Default::default() }), vec!["History essay"]);
+    assert_eq!(find(Query { difficulty: Some(6..=10), ..Default::default() }), vec!["OS lab"]);
+    // Text matches names and tags, case-insensitively.
+    assert_eq!(find(Query { text: Some(" ESSAY ".to_string()), ..Default::default() }), vec!["History essay"]);
+    assert_eq!(find(Query { text: Some("net".to_string()), ..Default::default() }), vec!["Networks quiz"]);
+    assert_eq!(find(Query { text: Some("%".to_string()), ..Default::default() }), Vec::<String>::new());
+
+    repo.delete(&quiz.uid).unwrap();
+    assert_eq!(find(Query { tags: vec!["lab".to_string()], ..Default::default() }), vec!["OS lab"]);
+    let with_deleted = Query { tags: vec!["lab".to_string()], include_deleted: true, ..Default::default() };
+    assert_eq!(find(with_deleted), vec!["OS lab", "Networks quiz"]);
+    assert_eq!(repo.query(&Query::default()).unwrap()[0].uid, os.uid);
+}
+
+fn query_sorts_and_paginates(repo: &dyn HomeworkRepo) {
+    for (name, due, progress) in [
+        ("b", "2025-12-03 10:00", 50),
+        ("a", "2025-12-01 10:00", 50),
+        ("c", "2025-12-02 10:00", 10),
+    ] {
+        let mut new = new_homework(name, due, "UTC");
+        new.progress = progress;
+        repo.create(new).unwrap();
+    }
+    let find = |q: Query| -> Vec<String> { repo.query(&q).unwrap().into_iter().map(|r| r.name).collect() };
+
+    assert_eq!(find(Query { sort: vec![QuerySort::asc(SortField::Due)], ..Default::default() }), vec!["a", "c", "b"]);
+    assert_eq!(find(Query { sort: vec![QuerySort::desc(SortField::Name)], ..Default::default() }), vec!["c", "b", "a"]);
+    // Ties on the first key fall back to the next key, then to insertion order.
+    assert_eq!(
+        find(Query {
+            sort: vec![QuerySort::desc(SortField::Progress), QuerySort::asc(SortField::Due)],
+            ..Default::default()
+        }),
+        vec!["a", "b", "c"]
+    );
+    assert_eq!(find(Query { sort: vec![QuerySort::desc(SortField::Progress)], ..Default::default() }), vec!["b", "a", "c"]);
+
+    let page = |offset, limit| Query { sort: vec![QuerySort::asc(SortField::Name)], offset, limit, ..Default::default() };
+    assert_eq!(find(page(0, Some(2))), vec!["a", "b"]);
+    assert_eq!(find(page(2, Some(2))), vec!["c"]);
+    assert_eq!(find(page(1, None)), vec!["b", "c"]);
+}
+
+fn status_changes_follow_transitions(repo: &dyn HomeworkRepo) {
+    let rec = repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+    assert_eq!((rec.status, rec.completed_at, rec.cancelled_at), (Status::Open, None, None));
+    let status = |s| Patch { status: Some(s), ..Default::default() };
+
+    let blocked = repo.patch(&rec.uid, status(Status::Blocked)).unwrap();
+    assert_eq!(blocked.status, Status::Blocked);
+    // A rejected change leaves the whole patch unapplied.
+    let bad = Patch { name: Some("Renamed".to_string()), ..status(Status::Completed) };
+    assert!(matches!(repo.patch(&rec.uid, bad), Err(RepoError::Invalid(_))));
+    assert_eq!(repo.get(&rec.uid).unwrap(), Some(blocked));
+
+    repo.patch(&rec.uid, status(Status::Open)).unwrap();
+    let done = repo.patch(&rec.uid, status(Status::Completed)).unwrap();
+    assert!(done.completed_at.is_some());
+    let reopened = repo.patch(&rec.uid, status(Status::Open)).unwrap();
+    assert_eq!((reopened.completed_at, reopened.cancelled_at), (None, None));
+
+    let mut dropped = new_homework("Dropped", "2025-12-01 09:00", "UTC");
+    dropped.status = Status::Cancelled;
+    let dropped = repo.create(dropped).unwrap();
+    assert_eq!(dropped.cancelled_at, Some(dropped.created_at));
+
+    let find = |q: Query| -> Vec<String> { repo.query(&q).unwrap().into_iter().map(|r| r.name).collect() };
+    assert_eq!(find(Query { status: vec![Status::Cancelled], ..Default::default() }), vec!["Dropped"]);
+    assert_eq!(find(Query { status: vec![Status::Open, Status::Cancelled], ..Default::default() }), vec!["Essay", "Dropped"]);
+    assert_eq!(find(Query { status: vec![Status::Waiting], ..Default::default() }), Vec::<String>::new());
+}
+
+fn reminders_round_trip(repo: &dyn HomeworkRepo) {
+    let mut payload = new_homework("Essay", "2025-11-28 23:59", "UTC");
+    payload.reminders = Some(vec![Lead { minutes: 24 * 60 }, Lead { minutes: 30 }]);
+    let rec = repo.create(payload).unwrap();
+    assert_eq!(repo.get(&rec.uid).unwrap().unwrap().reminders, rec.reminders);
+
+    // An empty list (no reminders) is kept apart from the defaults.
+    let quiet = repo.patch(&rec.uid, Patch { reminders: Some(Some(Vec::new())), ..Default::default() }).unwrap();
+    assert_eq!(quiet.reminders, Some(Vec::new()));
+    assert_eq!(repo.get(&rec.uid).unwrap().unwrap().reminders, Some(Vec::new()));
+    let defaults = repo.patch(&rec.uid, Patch { reminders: Some(None), ..Default::default() }).unwrap();
+    assert_eq!(defaults.reminders, None);
+    assert_eq!(repo.get(&rec.uid).unwrap().unwrap().reminders, None);
+}
+
+fn text_search_and_name_order_fold_unicode_case(repo: &dyn HomeworkRepo) {
+    for name in ["Übung 3", "ÉTUDE de cas", "Édith", "ébène"] {
+        repo.create(new_homework(name, "2025-11-28 23:59", "UTC")).unwrap();
+    }
+    let find = |q: Query| -> Vec<String> { repo.query(&q).unwrap().into_iter().map(|r| r.name).collect() };
+
+    assert_eq!(find(Query { text: Some("übung".to_string()), ..Default::default() }), vec!["Übung 3"]);
+    assert_eq!(find(Query { text: Some("ÜBUNG".to_string()), ..Default::default() }), vec!["Übung 3"]);
+    assert_eq!(find(Query { text: Some("étude".to_string()), ..Default::default() }), vec!["ÉTUDE de cas"]);
+    assert_eq!(find(Query { text: Some("é".to_string()), ..Default::default() }), vec!["ÉTUDE de cas", "Édith", "ébène"]);
+    let by_name = Query { sort: vec![QuerySort::asc(SortField::Name)], ..Default::default() };
+    assert_eq!(find(by_name), vec!["ébène", "Édith", "ÉTUDE de cas", "Übung 3"]);
+}
+
+fn due_order_follows_the_instant_not_the_wall_clock(repo: &dyn HomeworkRepo) {
+    // 23:59 in Berlin (22:59 UTC) is due before 08:00 the next day in Tokyo (23:00 UTC),
+    // and both before 20:00 in New York (01:00 UTC the next day).
+    repo.create(new_homework("New York", "2025-11-28 20:00", "America/New_York")).unwrap();
+    repo.create(new_homework("Berlin", "2025-11-28 23:59", "Europe/Berlin")).unwrap();
+    repo.create(new_homework("Tokyo", "2025-11-29 08:00", "Asia/Tokyo")).unwrap();
+    let find = |q: Query| -> Vec<String> { repo.query(&q).unwrap().into_iter().map(|r| r.name).collect() };
+
+    let by_due = Query { sort: vec![QuerySort::asc(SortField::Due)], ..Default::default() };
+    assert_eq!(find(by_due), vec!["Berlin", "Tokyo", "New York"]);
+    // Due bounds are in UTC.
+    let on_the_28th = Query {
+        due_from: Some("2025-11-28 00:00".to_string()),
+        due_to: Some("2025-11-28 23:59".to_string()),
+        sort: vec![QuerySort::asc(SortField::Due)],
+        ..Default::default()
+    };
+    assert_eq!(find(on_the_28th), vec!["Berlin", "Tokyo"]);
+}
+
+fn milestones_drive_progress(repo: &dyn HomeworkRepo) {
+    let milestone = |title: &str, weight, done| MilestoneRecord {
+        title: title.to_string(),
+        weight,
+        done,
+        due_text: String::new(),
+    };
+    let mut payload = new_homework("Essay", "2025-11-28 23:59", "UTC");
+    payload.milestones = vec![milestone("Outline", 3, true), milestone("Draft", 1, false)];
+    let essay = repo.create(payload).unwrap();
+    assert_eq!(essay.progress, 75);
+    let mut plain = new_homework("Quiz", "2025-12-01 09:00", "UTC");
+    plain.progress = 80;
+    repo.create(plain).unwrap();
+
+    // A progress of its own is overruled by the milestones.
+    let patched = repo.patch(&essay.uid, Patch { progress: Some(10), ..Default::default() }).unwrap();
+    assert_eq!(patched.progress, 75);
+    let sorted = Query { sort: vec![QuerySort::asc(SortField::Progress)], ..Default::default() };
+    let sorted: Vec<String> = repo.query(&sorted).unwrap().into_iter().map(|r| r.name).collect();
+    assert_eq!(sorted, vec!["Essay", "Quiz"]);
+
+    let mut rec = repo.get(&essay.uid).unwrap().unwrap();
+    rec.milestones[1].done = true;
+    rec.progress = 0;
+    assert_eq!(repo.update(rec).unwrap().progress, 100);
+    assert_eq!(repo.get(&essay.uid).unwrap().unwrap().progress, 100);
+}
+
+fn query_archive_filter(repo: &dyn HomeworkRepo) {
+    let essay = repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+    repo.create(new_homework("Quiz", "2025-12-01 09:00", "UTC")).unwrap();
+    let done = Patch { status: Some(Status::Completed), ..Default::default() };
+    let completed_at = repo.patch(&essay.uid, done).unwrap().completed_at.unwrap();
+
+    let find = |archive| -> Vec<String> {
+        repo.query(&Query { archive, ..Default::default() }).unwrap().into_iter().map(|r| r.name).collect()
+    };
+    assert_eq!(find(ArchiveFilter::Exclude { cutoff: completed_at }), vec!["Quiz"]);
+    assert_eq!(find(ArchiveFilter::Only { cutoff: completed_at }), vec!["Essay"]);
+    assert_eq!(find(ArchiveFilter::Exclude { cutoff: completed_at - 1 }), vec!["Essay", "Quiz"]);
+    assert_eq!(find(ArchiveFilter::Only { cutoff: completed_at - 1 }), Vec::<String>::new());
+}
+
+fn mutations_emit_events(repo: &dyn HomeworkRepo) {
+    let seen: Arc<Mutex<Vec<RepoEvent>>> = Arc::default();
+    let sink = seen.clone();
+    let id = repo.changes().subscribe(Box::new(move |e| sink.lock().unwrap().push(e.clone())));
+    let take = || std::mem::take(&mut *seen.lock().unwrap());
+
+    let rec = repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+    assert_eq!(take(), vec![RepoEvent::Created(rec.clone())]);
+    let patched = repo.patch(&rec.uid, Patch { progress: Some(10), ..Default::default() }).unwrap();
+    assert_eq!(take(), vec![RepoEvent::Updated(patched.clone())]);
+    let updated = repo.update(patched).unwrap();
+    assert_eq!(take(), vec![RepoEvent::Updated(updated)]);
+
+    repo.delete(&rec.uid).unwrap();
+    let deleted = repo.get(&rec.uid).unwrap().unwrap();
+    assert_eq!(take(), vec![RepoEvent::Deleted(deleted)]);
+    let restored = repo.restore(&rec.uid).unwrap();
+    assert_eq!(take(), vec![RepoEvent::Updated(restored)]);
+    repo.purge(&rec.uid).unwrap();
+    assert_eq!(take(), vec![RepoEvent::Purged(rec.uid.clone())]);
+
+    // Failed mutations and reads are silent.
+    let _ = repo.patch("missing", Patch::default());
+    let _ = repo.list();
+    assert!(take().is_empty());
+
+    repo.changes().unsubscribe(id);
+    repo.create(new_homework("Quiz", "2025-11-28 23:59", "UTC")).unwrap();
+    assert!(take().is_empty());
+}
+
+macro_rules! conformance {
+    ($($backend:ident => $make:expr;)*) => {
+        $(
+            mod $backend {
+                use super::*;
+
+                fn run(check: fn(&dyn HomeworkRepo)) {
+                    let dir = TempDir::new();
+                    let make: &dyn Fn(&TempDir) -> Arc<dyn HomeworkRepo> = &$make;
+                    check(make(&dir).as_ref());
+                }
+
+                #[test] fn create_assigns_uid_and_timestamps() { run(super::create_assigns_uid_and_timestamps) }
+                #[test] fn get_unknown_uid_is_none() { run(super::get_unknown_uid_is_none) }
+                #[test] fn list_keeps_insertion_order() { run(super::list_keeps_insertion_order) }
+                #[test] fn list_excludes_deleted() { run(super::list_excludes_deleted) }
+                #[test] fn update_replaces_fields_and_bumps_timestamp() { run(super::update_replaces_fields_and_bumps_timestamp) }
+                #[test] fn patch_changes_only_given_fields() { run(super::patch_changes_only_given_fields) }
+                #[test] fn restore_and_purge() { run(super::restore_and_purge) }
+                #[test] fn unknown_uid_is_not_found() { run(super::unknown_uid_is_not_found) }
+                #[test] fn query_filters() { run(super::query_filters) }
+                #[test] fn query_sorts_and_paginates() { run(super::query_sorts_and_paginates) }
+                #[test] fn status_changes_follow_transitions() { run(super::status_changes_follow_transitions) }
+                #[test] fn reminders_round_trip() { run(super::reminders_round_trip) }
+                #[test] fn text_search_and_name_order_fold_unicode_case() { run(super::text_search_and_name_order_fold_unicode_case) }
+                #[test] fn due_order_follows_the_instant_not_the_wall_clock() { run(super::due_order_follows_the_instant_not_the_wall_clock) }
+                #[test] fn milestones_drive_progress() { run(super::milestones_drive_progress) }
+                #[test] fn query_archive_filter() { run(super::query_archive_filter) }
+                #[test] fn mutations_emit_events() { run(super::mutations_emit_events) }
+            }
+        )*
+    };
+}
+
+conformance! {
+    memory => |_| Arc::new(MemoryRepo::new());
+    json => |dir| Arc::new(JsonRepo::new(dir.0.clone()).unwrap());
+    sqlite => |_| Arc::new(SqliteRepo::in_memory().unwrap());
+    sqlite_file => |dir| Arc::new(SqliteRepo::new(dir.0.clone()).unwrap());
+    history => |_| Arc::new(HistoryRepo::new(Arc::new(MemoryRepo::new())));
+    merged => |dir| Arc::new(MergedRepo::new(vec![
+        ("primary".to_string(), Arc::new(JsonRepo::new(dir.0.clone()).unwrap())),
+        ("other".to_string(), Arc::new(MemoryRepo::new())),
+    ]));
+}
@@ -0,0 +1,62 @@
+//! Change notifications emitted by repositories after each successful mutation.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::persistence::types::HomeworkRecord;
+
+/// What changed in a repository. Records are the state after the change.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepoEvent {
+    Created(HomeworkRecord),
+    /// Any edit, including a restore from the trash
+    Updated(HomeworkRecord),
+    /// Moved to the trash (`deleted` is set)
+    Deleted(HomeworkRecord),
+    /// Removed for good
+    Purged(String),
+    /// Storage was replaced wholesale (e.g. restored from a backup); re-read everything
+    Reloaded,
+    /// Another program edited the storage and those edits were read or merged; re-read everything
+    Refreshed,
+}
+
+impl RepoEvent {
+    pub fn uid(&self) -> Option<&str> {
+        match self {
+            RepoEvent::Created(r) | RepoEvent::Updated(r) | RepoEvent::Deleted(r) => Some(&r.uid),
+            RepoEvent::Purged(uid) => Some(uid),
+            RepoEvent::Reloaded | RepoEvent::Refreshed => None,
+        }
+    }
+}
+
+pub type SubscriptionId = u64;
+pub type Listener = Box<dyn Fn(&RepoEvent) + Send + Sync>;
+
+/// Subscriber list owned by each repository.
+/// Listeners run synchronously on the mutating thread and must not call back into the
+/// repository; hand the event off (e.g. to a channel) instead.
+#[derive(Default)]
+pub struct ChangeBus {
+    listeners: Mutex<Vec<(SubscriptionId, Listener)>>,
+    next_id: AtomicU64,
+}
+
+impl ChangeBus {
+    pub fn subscribe(&self, listener: Listener) -> SubscriptionId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.listeners.lock().unwrap().push((id, listener));
+        id
+    }
+
+    pub fn unsubscribe(&self, id: SubscriptionId) {
+        self.listeners.lock().unwrap().retain(|(i, _)| *i != id);
+    }
+
+    pub fn emit(&self, event: RepoEvent) {
+        for (_, listener) in self.listeners.lock().unwrap().iter() {
+            listener(&event);
+        }
+    }
+}
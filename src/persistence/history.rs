@@ -0,0 +1,321 @@
+//! Undo/redo log around any `HomeworkRepo`.
+//! Every mutation is recorded with the full record before and after it, so undoing never
+//! depends on how the wrapped backend implements patches or soft deletes.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::persistence::events::ChangeBus;
+use crate::persistence::query::Query;
+use crate::persistence::repo::{HomeworkRepo, RepoResult};
+use crate::persistence::types::{HomeworkRecord, NewHomework, Patch};
+
+/// Oldest operations are dropped once the undo stack grows past this.
+const HISTORY_LIMIT: usize = 100;
+
+/// Updates to the same record closer together than this undo as one step.
+const MERGE_WINDOW: Duration = Duration::from_secs(2);
+
+/// One invertible repository mutation. Patches are recorded as updates.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operation {
+    Create { record: Box<HomeworkRecord> },
+    Update { before: Box<HomeworkRecord>, after: Box<HomeworkRecord> },
+    Delete { before: Box<HomeworkRecord> },
+}
+
+impl Operation {
+    pub fn uid(&self) -> &str {
+        match self {
+            Operation::Create { record } => &record.uid,
+            Operation::Update { after, .. } => &after.uid,
+            Operation::Delete { before } => &before.uid,
+        }
+    }
+
+    /// Short past-tense description, e.g. "Deleted 'OS lab 3'".
+    pub fn describe(&self) -> String {
+        match self {
+            Operation::Create { record } => format!("Created '{}'", record.name),
+            Operation::Update { after, .. } => format!("Edited '{}'", after.name),
+            Operation::Delete { before } => format!("Deleted '{}'", before.name),
+        }
+    }
+
+    fn undo(&self, repo: &dyn HomeworkRepo) -> RepoResult<()> {
+        match self {
+            Operation::Create { record } => repo.delete(&record.uid),
+            Operation::Update { before, .. } | Operation::Delete { before } => {
+                repo.update((**before).clone()).map(|_| ())
+            }
+        }
+    }
+
+    fn redo(&self, repo: &dyn HomeworkRepo) -> RepoResult<()> {
+        match self {
+            Operation::Create { record } => repo.update((**record).clone()).map(|_| ()),
+            Operation::Update { after, .. } => repo.update((**after).clone()).map(|_| ()),
+            Operation::Delete { before } => repo.delete(&before.uid),
+        }
+    }
+}
+
+#[derive(Default)]
+struct OpLog {
+    undo: Vec<Operation>,
+    redo: Vec<Operation>,
+    // When the top of `undo` was last extended by `update`; `None` once anything else happened
+    last_update: Option<Instant>,
+}
+
+/// Repository decorator that records mutations for multi-level undo and redo.
+/// A new mutation clears the redo stack, as in text editors.
+pub struct HistoryRepo {
+    inner: Arc<dyn HomeworkRepo>,
+    log: Mutex<OpLog>,
+}
+
+impl HistoryRepo {
+    pub fn new(inner: Arc<dyn HomeworkRepo>) -> Self {
+        Self { inner, log: Mutex::new(OpLog::default()) }
+    }
+
+    fn record(&self, op: Operation) {
+        let mut log = self.log.lock().unwrap();
+        log.redo.clear();
+        log.last_update = None;
+        log.undo.push(op);
+        if log.undo.len() > HISTORY_LIMIT {
+            log.undo.remove(0);
+        }
+    }
+
+    /// Record an update, folding it into the previous one if that updated the same record
+    /// within `MERGE_WINDOW`, so a burst of edits undoes in one step.
+    fn record_update(&self, before: HomeworkRecord, after: HomeworkRecord) {
+        {
+            let mut log = self.log.lock().unwrap();
+            let recent = log.last_update.is_some_and(|at| at.elapsed() < MERGE_WINDOW);
+            if let Some(Operation::Update { after: last, .. }) = log.undo.last_mut() {
+                if recent && last.uid == after.uid {
+                    **last = after;
+                    log.redo.clear();
+                    log.last_update = Some(Instant::now());
+                    return;
+                }
+            }
+        }
+        self.record(Operation::Update { before: Box::new(before), after: Box::new(after) });
+        self.log.lock().unwrap().last_update = Some(Instant::now());
+    }
+
+    /// Revert the most recent operation. Returns it, or `None` when there is nothing to undo.
+    /// On error the operation stays on the undo stack.
+    pub fn undo(&self) -> RepoResult<Option<Operation>> {
+        let mut log = self.log.lock().unwrap();
+        log.last_update = None;
+        let Some(op) = log.undo.pop() else { return Ok(None) };
+        if let Err(e) = op.undo(self.inner.as_ref()) {
+            log.undo.push(op);
+            return Err(e);
+        }
+        log.redo.push(op.clone());
+        Ok(Some(op))
+    }
+
+    /// Re-apply the most recently undone operation.
+    pub fn redo(&self) -> RepoResult<Option<Operation>> {
+        let mut log = self.log.lock().unwrap();
+        log.last_update = None;
+        let Some(op) = log.redo.pop() else { return Ok(None) };
+        if let Err(e) = op.redo(self.inner.as_ref()) {
+            log.redo.push(op);
+            return Err(e);
+        }
+        log.undo.push(op.clone());
+        Ok(Some(op))
+    }
+
+    /// Drop operations on records that no longer exist, since they cannot be replayed.
+    fn forget(&self, uids: &[String]) {
+        let mut log = self.log.lock().unwrap();
+        log.undo.retain(|op| !uids.iter().any(|u| u == op.uid()));
+        log.redo.retain(|op| !uids.iter().any(|u| u == op.uid()));
+    }
+
+    pub fn clear(&self) {
+        *self.log.lock().unwrap() = OpLog::default();
+    }
+}
+
+impl HomeworkRepo for HistoryRepo {
+    fn list(&self) -> RepoResult<Vec<HomeworkRecord>> {
+        self.inner.list()
+    }
+
+    fn get(&self, uid: &str) -> RepoResult<Option<HomeworkRecord>> {
+        self.inner.get(uid)
+    }
+
+    fn create(&self, payload: NewHomework) -> RepoResult<HomeworkRecord> {
+        let record = self.inner.create(payload)?;
+        self.record(Operation::Create { record: Box::new(record.clone()) });
+        Ok(record)
+    }
+
+    fn update(&self, record: HomeworkRecord) -> RepoResult<HomeworkRecord> {
+        let before = self.inner.get(&record.uid)?;
+        let after = self.inner.update(record)?;
+        if let Some(before) = before {
+            self.record_update(before, after.clone());
+        }
+        Ok(after)
+    }
+
+    fn patch(&self, uid: &str, patch: Patch) -> RepoResult<HomeworkRecord> {
+        let before = self.inner.get(uid)?;
+        let after = self.inner.patch(uid, patch)?;
+        if let Some(before) = before {
+            self.record(Operation::Update { before: Box::new(before), after: Box::new(after.clone()) });
+        }
+        Ok(after)
+    }
+
+    fn delete(&self, uid: &str) -> RepoResult<()> {
+        let before = self.inner.get(uid)?;
+        self.inner.delete(uid)?;
+        if let Some(before) = before {
+            self.record(Operation::Delete { before: Box::new(before) });
+        }
+        Ok(())
+    }
+
+    fn query(&self, query: &Query) -> RepoResult<Vec<HomeworkRecord>> {
+        self.inner.query(query)
+    }
+
+    fn list_deleted(&self) -> RepoResult<Vec<HomeworkRecord>> {
+        self.inner.list_deleted()
+    }
+
+    fn restore(&self, uid: &str) -> RepoResult<HomeworkRecord> {
+        let before = self.inner.get(uid)?;
+        let after = self.inner.restore(uid)?;
+        if let Some(before) = before {
+            self.record(Operation::Update { before: Box::new(before), after: Box::new(after.clone()) });
+        }
+        Ok(after)
+    }
+
+    fn purge(&self, uid: &str) -> RepoResult<()> {
+        self.inner.purge(uid)?;
+        self.forget(&[uid.to_string()]);
+        Ok(())
+    }
+
+    fn purge_deleted_before(&self, cutoff_ts: i64) -> RepoResult<usize> {
+        let before: Vec<String> = self.inner.list_deleted()?.into_iter().map(|r| r.uid).collect();
+        let purged = self.inner.purge_deleted_before(cutoff_ts)?;
+        if purged > 0 {
+            let gone: Vec<String> = before
+                .into_iter()
+                .filter(|uid| matches!(self.inner.get(uid), Ok(None)))
+                .collect();
+            self.forget(&gone);
+        }
+        Ok(purged)
+    }
+
+    // Undo and redo go through the wrapped repository, so its events cover them too.
+    fn changes(&self) -> &ChangeBus {
+        self.inner.changes()
+    }
+
+    fn poll_external_changes(&self) -> RepoResult<bool> {
+        self.inner.poll_external_changes()
+    }
+
+    fn restore_latest_backup(&self) -> RepoResult<()> {
+        // Recorded operations refer to the replaced data.
+        self.inner.restore_latest_backup()?;
+        self.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::conformance::new_homework;
+    use crate::persistence::memory::MemoryRepo;
+
+    fn live(repo: &HistoryRepo) -> Vec<String> {
+        repo.list().unwrap().into_iter().filter(|r| !r.deleted).map(|r| r.name).collect()
+    }
+
+    #[test]
+    fn undo_and_redo_delete() {
+        let repo = HistoryRepo::new(Arc::new(MemoryRepo::new()));
+        let rec = repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+        repo.delete(&rec.uid).unwrap();
+        assert!(live(&repo).is_empty());
+
+        let op = repo.undo().unwrap().unwrap();
+        assert_eq!(op.describe(), "Deleted 'Essay'");
+        assert_eq!(live(&repo), vec!["Essay"]);
+
+        repo.redo().unwrap();
+        assert!(live(&repo).is_empty());
+    }
+
+    #[test]
+    fn multi_level_undo_of_patches_and_create() {
+        let repo = HistoryRepo::new(Arc::new(MemoryRepo::new()));
+        let rec = repo.create(new_homework("Lab", "2025-11-28 23:59", "UTC")).unwrap();
+        for p in [30, 60] {
+            repo.patch(&rec.uid, Patch { progress: Some(p), ..Default::default() }).unwrap();
+        }
+
+        repo.undo().unwrap();
+        assert_eq!(repo.get(&rec.uid).unwrap().unwrap().progress, 30);
+        repo.undo().unwrap();
+        assert_eq!(repo.get(&rec.uid).unwrap().unwrap().progress, 0);
+        repo.undo().unwrap();
+        assert!(live(&repo).is_empty());
+        assert_eq!(repo.undo().unwrap(), None);
+
+        repo.redo().unwrap();
+        repo.redo().unwrap();
+        assert_eq!(repo.get(&rec.uid).unwrap().unwrap().progress, 30);
+        assert_eq!(live(&repo), vec!["Lab"]);
+    }
+
+    #[test]
+    fn rapid_updates_to_one_record_undo_in_one_step() {
+        let repo = HistoryRepo::new(Arc::new(MemoryRepo::new()));
+        let rec = repo.create(new_homework("Lab", "2025-11-28 23:59", "UTC")).unwrap();
+        for p in [10, 20, 30, 40] {
+            let mut next = repo.get(&rec.uid).unwrap().unwrap();
+            next.progress = p;
+            repo.update(next).unwrap();
+        }
+        assert_eq!(repo.log.lock().unwrap().undo.len(), 2);
+
+        repo.undo().unwrap();
+        assert_eq!(repo.get(&rec.uid).unwrap().unwrap().progress, 0);
+        repo.redo().unwrap();
+        assert_eq!(repo.get(&rec.uid).unwrap().unwrap().progress, 40);
+        assert_eq!(live(&repo), vec!["Lab"]);
+    }
+
+    #[test]
+    fn new_mutation_clears_redo() {
+        let repo = HistoryRepo::new(Arc::new(MemoryRepo::new()));
+        let rec = repo.create(new_homework("Quiz", "2025-11-28 23:59", "UTC")).unwrap();
+        repo.patch(&rec.uid, Patch { progress: Some(50), ..Default::default() }).unwrap();
+        repo.undo().unwrap();
+        assert_eq!(repo.log.lock().unwrap().redo.len(), 1);
+        repo.patch(&rec.uid, Patch { difficulty: Some(8), ..Default::default() }).unwrap();
+        assert!(repo.log.lock().unwrap().redo.is_empty());
+    }
+}
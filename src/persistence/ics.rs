@@ -0,0 +1,498 @@
+//! iCalendar (RFC 5545) export and import of homework records.
+//! Export writes one VTODO or VEVENT per non-deleted record; import maps VTODO/VEVENT items
+//! to `NewHomework` and skips items whose UID is already known.
+
+use std::collections::HashSet;
+
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use serde::Serialize;
+
+use crate::model::datetime::Datetime;
+use crate::model::recurrence::{weekday_code, Frequency, Recurrence};
+use crate::model::status::Status;
+use crate::model::timezone::DueZone;
+use crate::persistence::repo::{HomeworkRepo, RepoResult};
+use crate::persistence::types::{HomeworkRecord, NewHomework};
+
+/// Suffix appended to record uids to form globally unique iCalendar UIDs.
+const UID_DOMAIN: &str = "@deadline-tracker";
+const PRODID: &str = "-//Yang-Yiming//DeadlineTracker//EN";
+/// Carries statuses iCalendar has no STATUS value for (blocked, waiting, submitted).
+const STATUS_PROPERTY: &str = "X-DEADLINE-STATUS";
+
+/// Calendar component used for exported deadlines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum IcsComponent {
+    /// Task with DUE and PERCENT-COMPLETE (Thunderbird, GNOME To Do, most LMSs)
+    #[default]
+    Todo,
+    /// Zero-length event at the due time (for calendars without task support)
+    Event,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ImportSummary {
+    pub imported: usize,
+    /// Items whose UID already exists in the repository
+    pub duplicates: usize,
+    /// Items without a usable due date
+    pub skipped: usize,
+}
+
+fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// Append a content line, folded at 75 octets as RFC 5545 requires.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn format_local(dt: &Datetime) -> String {
+    format!("{:04}{:02}{:02}T{:02}{:02}00", dt.year, dt.month, dt.day, dt.hour, dt.minute)
+}
+
+/// "DUE;TZID=Europe/Berlin:20251128T235900", "DUE:20251129T115900Z" or floating local time.
+fn date_property(name: &str, due: &Datetime, zone: &DueZone) -> String {
+    match zone {
+        DueZone::Named(tz) => format!("{};TZID={}:{}", name, tz.name(), format_local(due)),
+        DueZone::Offset(_) => match zone.to_utc(due) {
+            Some(utc) => format!("{}:{}Z", name, utc.format("%Y%m%dT%H%M%S")),
+            None => format!("{}:{}", name, format_local(due)),
+        },
+        DueZone::Local => format!("{}:{}", name, format_local(due)),
+    }
+}
+
+fn rrule_for(rule: &Recurrence) -> String {
+    let mut parts = Vec::new();
+    match &rule.frequency {
+        Frequency::Daily => parts.push("FREQ=DAILY".to_string()),
+        Frequency::Weekly { weekdays } => {
+            parts.push("FREQ=WEEKLY".to_string());
+            if !weekdays.is_empty() {
+                let days: Vec<&str> = weekdays.iter().map(|wd| weekday_code(*wd)).collect();
+                parts.push(format!("BYDAY={}", days.join(",")));
+            }
+        }
+        Frequency::MonthlyByDate { day } => {
+            parts.push("FREQ=MONTHLY".to_string());
+            parts.push(format!("BYMONTHDAY={}", day));
+        }
+        Frequency::MonthlyByWeekday { nth, weekday } => {
+            parts.push("FREQ=MONTHLY".to_string());
+            parts.push(format!("BYDAY={}{}", nth, weekday_code(*weekday)));
+        }
+    }
+    parts.push(format!("INTERVAL={}", rule.interval.max(1)));
+    if let Some(count) = rule.count {
+        parts.push(format!("COUNT={}", count));
+    }
+    if let Some(until) = &rule.until {
+        parts.push(format!("UNTIL={}", format_local(until)));
+    }
+    format!("RRULE:{}", parts.join(";"))
+}
+
+/// Serialize non-deleted records into a VCALENDAR document.
+pub fn export_ics(records: &[HomeworkRecord], component: IcsComponent) -> String {
+    let kind = match component {
+        IcsComponent::Todo => "VTODO",
+        IcsComponent::Event => "VEVENT",
+    };
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, &format!("PRODID:{}", PRODID));
+    for rec in records.iter().filter(|r| !r.deleted) {
+        let d = rec.to_deadline();
+        let uid = if rec.external_uid.is_empty() {
+            format!("{}{}", rec.uid, UID_DOMAIN)
+        } else {
+            rec.external_uid.clone()
+        };
+        push_line(&mut out, &format!("BEGIN:{}", kind));
+        push_line(&mut out, &format!("UID:{}", escape_text(&uid)));
+        push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+        push_line(&mut out, &format!("SUMMARY:{}", escape_text(&d.name)));
+        match component {
+            IcsComponent::Todo => {
+                push_line(&mut out, &date_property("DUE", &d.due_date, &d.due_zone));
+                push_line(&mut out, &format!("PERCENT-COMPLETE:{}", d.progress.min(100)));
+                let status = match d.status {
+                    Status::Completed => "COMPLETED",
+                    Status::Cancelled => "CANCELLED",
+                    _ => "NEEDS-ACTION",
+                };
+                push_line(&mut out, &format!("STATUS:{}", status));
+                if let Some(at) = rec.completed_at.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)) {
+                    push_line(&mut out, &format!("COMPLETED:{}", at.format("%Y%m%dT%H%M%SZ")));
+                }
+            }
+            IcsComponent::Event => {
+                push_line(&mut out, &date_property("DTSTART", &d.due_date, &d.due_zone));
+                push_line(&mut out, &date_property("DTEND", &d.due_date, &d.due_zone));
+                push_line(&mut out, &format!("X-PERCENT-COMPLETE:{}", d.progress.min(100)));
+                if d.status == Status::Cancelled {
+                    push_line(&mut out, "STATUS:CANCELLED");
+                }
+            }
+        }
+        if d.status != Status::Open {
+            push_line(&mut out, &format!("{}:{}", STATUS_PROPERTY, d.status.as_str()));
+        }
+        if !d.tags.is_empty() {
+            let tags: Vec<String> = d.tags.iter().map(|t| escape_text(t)).collect();
+            push_line(&mut out, &format!("CATEGORIES:{}", tags.join(",")));
+        }
+        push_line(&mut out, &format!("X-DEADLINE-DIFFICULTY:{}", d.difficulty));
+        if let Some(rule) = &d.recurrence {
+            push_line(&mut out, &rrule_for(rule));
+            for ex in &rule.exceptions {
+                let ex = Datetime::new(ex.year, ex.month, ex.day, d.due_date.hour, d.due_date.minute);
+                push_line(&mut out, &date_property("EXDATE", &ex, &d.due_zone));
+            }
+        }
+        push_line(&mut out, &format!("END:{}", kind));
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Export every non-deleted record of `repo`.
+pub fn export_repo_ics(repo: &dyn HomeworkRepo, component: IcsComponent) -> RepoResult<String> {
+    Ok(export_ics(&repo.list()?, component))
+}
+
+/// One parsed content line: name, parameters and raw value.
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(rest) = raw.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        if !raw.is_empty() {
+            lines.push(raw.to_string());
+        }
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    // The value starts at the first ':' outside a quoted parameter value.
+    let mut in_quotes = false;
+    let split = line.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ':' && !in_quotes
+    })?;
+    let (head, value) = (&line[..split.0], &line[split.0 + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim_matches('"').to_string()))
+        .collect();
+    Some(Property { name, params, value: value.to_string() })
+}
+
+/// Parse a DATE or DATE-TIME value into a wall-clock time and its zone.
+/// All-day dates become 23:59 on that day.
+fn parse_date_value(prop: &Property) -> Option<(Datetime, DueZone)> {
+    let value = prop.value.trim();
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        let dt = Datetime::from_naive(&date.and_hms_opt(23, 59, 0)?);
+        return Some((dt, DueZone::Local));
+    }
+    let (value, utc) = match value.strip_suffix('Z') {
+        Some(v) => (v, true),
+        None => (value, false),
+    };
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let zone = if utc {
+        DueZone::Offset(0)
+    } else {
+        prop.param("TZID").and_then(DueZone::parse).unwrap_or(DueZone::Local)
+    };
+    Some((Datetime::from_naive(&naive), zone))
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    // Split on commas that are not escaped.
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in value.chars() {
+        if escaped {
+            current.push('\\');
+            current.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == ',' {
+            items.push(unescape_text(&current));
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    items.push(unescape_text(&current));
+    items.into_iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
+
+fn component_to_new(props: &[Property]) -> Option<NewHomework> {
+    let get = |name: &str| props.iter().find(|p| p.name == name);
+    let due_prop = get("DUE").or_else(|| get("DTEND")).or_else(|| get("DTSTART"))?;
+    let (due, zone) = parse_date_value(due_prop)?;
+
+    let progress = get("PERCENT-COMPLETE")
+        .or_else(|| get("X-PERCENT-COMPLETE"))
+        .and_then(|p| p.value.trim().parse::<u8>().ok())
+        .or_else(|| get("STATUS").filter(|p| p.value.eq_ignore_ascii_case("COMPLETED")).map(|_| 100))
+        .unwrap_or(0)
+        .min(100);
+    let status = get(STATUS_PROPERTY)
+        .and_then(|p| Status::parse(&p.value))
+        .or_else(|| match get("STATUS")?.value.trim().to_ascii_uppercase().as_str() {
+            "COMPLETED" => Some(Status::Completed),
+            "CANCELLED" => Some(Status::Cancelled),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let difficulty = get("X-DEADLINE-DIFFICULTY")
+        .and_then(|p| p.value.trim().parse::<u8>().ok())
+        .unwrap_or(5);
+    let tags = props
+        .iter()
+        .filter(|p| p.name == "CATEGORIES")
+        .flat_map(|p| split_list(&p.value))
+        .collect();
+
+    let recurrence = match get("RRULE") {
+        Some(rrule) => {
+            let exdates: Vec<String> = props
+                .iter()
+                .filter(|p| p.name == "EXDATE")
+                .flat_map(|p| p.value.split(',').map(|v| v.trim().chars().take(8).collect::<String>()))
+                .collect();
+            // Drop rule parts this app does not model (WKST, BYSETPOS, ...).
+            let mut text = rrule
+                .value
+                .split(';')
+                .filter(|part| {
+                    let key = part.split('=').next().unwrap_or("").trim().to_ascii_uppercase();
+                    matches!(key.as_str(), "FREQ" | "INTERVAL" | "BYDAY" | "BYMONTHDAY" | "UNTIL" | "COUNT")
+                })
+                .collect::<Vec<_>>()
+                .join(";");
+            if !exdates.is_empty() {
+                text.push_str(&format!(";EXDATE={}", exdates.join(",")));
+            }
+            // Keep only rules this app can represent.
+            Recurrence::parse(&text).map(|r| r.to_rrule()).unwrap_or_default()
+        }
+        None => String::new(),
+    };
+
+    Some(NewHomework {
+        name: get("SUMMARY").map(|p| unescape_text(&p.value)).unwrap_or_else(|| "Untitled".to_string()),
+        due_text: due.to_string(),
+        due_zone: zone.to_string(),
+        difficulty,
+        progress,
+        tags,
+        milestones: Vec::new(),
+        recurrence,
+        external_uid: get("UID").map(|p| unescape_text(&p.value)).unwrap_or_default(),
+        status,
+        reminders: None,
+    })
+}
+
+/// Parse VTODO and VEVENT items. Items without a usable due date are counted, not returned.
+pub fn parse_ics(text: &str) -> (Vec<NewHomework>, usize) {
+    let mut items = Vec::new();
+    let mut skipped = 0;
+    let mut current: Option<Vec<Property>> = None;
+    for line in unfold(text) {
+        let Some(prop) = parse_property(&line) else { continue };
+        match (prop.name.as_str(), prop.value.trim().to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VTODO") | ("BEGIN", "VEVENT") => current = Some(Vec::new()),
+            ("END", "VTODO") | ("END", "VEVENT") => {
+                if let Some(props) = current.take() {
+                    match component_to_new(&props) {
+                        Some(item) => items.push(item),
+                        None => skipped += 1,
+                    }
+                }
+            }
+            _ => {
+                if let Some(props) = current.as_mut() {
+                    props.push(prop);
+                }
+            }
+        }
+    }
+    (items, skipped)
+}
+
+/// Create records for items in `text` whose UID is not yet known to `repo`.
+/// Items exported by this app map back to their original record by uid.
+pub fn import_ics(repo: &dyn HomeworkRepo, text: &str) -> RepoResult<ImportSummary> {
+    let existing = repo.list()?;
+    let mut known: HashSet<String> = HashSet::new();
+    for rec in &existing {
+        known.insert(format!("{}{}", rec.uid, UID_DOMAIN));
+        if !rec.external_uid.is_empty() {
+            known.insert(rec.external_uid.clone());
+        }
+    }
+
+    let (items, skipped) = parse_ics(text);
+    let mut summary = ImportSummary { skipped, ..Default::default() };
+    for item in items {
+        if !item.external_uid.is_empty() && !known.insert(item.external_uid.clone()) {
+            summary.duplicates += 1;
+            continue;
+        }
+        repo.create(item)?;
+        summary.imported += 1;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::memory::MemoryRepo;
+
+    fn sample(repo: &MemoryRepo) -> HomeworkRecord {
+        repo.create(NewHomework {
+            name: "OS lab 3, part 1".to_string(),
+            due_text: "2025-11-28 23:59".to_string(),
+            due_zone: "Europe/Berlin".to_string(),
+            difficulty: 7,
+            progress: 40,
+            tags: vec!["os".to_string(), "lab".to_string()],
+            milestones: Vec::new(),
+            recurrence: "FREQ=WEEKLY;BYDAY=FR;INTERVAL=1;EXDATE=20251205".to_string(),
+            external_uid: String::new(),
+            status: Status::Waiting,
+            reminders: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn export_writes_todo_fields() {
+        let repo = MemoryRepo::new();
+        let rec = sample(&repo);
+        let text = export_ics(std::slice::from_ref(&rec), IcsComponent::Todo);
+        assert!(text.contains(&format!("UID:{}@deadline-tracker\r\n", rec.uid)));
+        assert!(text.contains("SUMMARY:OS lab 3\\, part 1\r\n"));
+        assert!(text.contains("DUE;TZID=Europe/Berlin:20251128T235900\r\n"));
+        assert!(text.contains("CATEGORIES:os,lab\r\n"));
+        assert!(text.contains("PERCENT-COMPLETE:40\r\n"));
+        assert!(text.contains("STATUS:NEEDS-ACTION\r\n"));
+        assert!(text.contains("X-DEADLINE-STATUS:waiting\r\n"));
+        assert!(text.contains("RRULE:FREQ=WEEKLY;BYDAY=FR;INTERVAL=1\r\n"));
+        assert!(text.contains("EXDATE;TZID=Europe/Berlin:20251205T235900\r\n"));
+    }
+
+    #[test]
+    fn long_lines_are_folded() {
+        let mut out = String::new();
+        push_line(&mut out, &format!("SUMMARY:{}", "x".repeat(200)));
+        assert!(out.split("\r\n").all(|l| l.len() <= 75));
+        assert_eq!(unfold(&out).join(""), format!("SUMMARY:{}", "x".repeat(200)));
+    }
+
+    #[test]
+    fn import_round_trips_and_skips_duplicates() {
+        let source = MemoryRepo::new();
+        let rec = sample(&source);
+        let text = export_ics(&[rec], IcsComponent::Event);
+
+        let target = MemoryRepo::new();
+        let first = import_ics(&target, &text).unwrap();
+        assert_eq!(first, ImportSummary { imported: 1, duplicates: 0, skipped: 0 });
+        let again = import_ics(&target, &text).unwrap();
+        assert_eq!(again.duplicates, 1);
+
+        let imported = &target.list().unwrap()[0];
+        assert_eq!(imported.name, "OS lab 3, part 1");
+        assert_eq!(imported.due_text, "2025-11-28 23:59");
+        assert_eq!(imported.due_zone, "Europe/Berlin");
+        assert_eq!(imported.tags, vec!["os", "lab"]);
+        assert_eq!(imported.progress, 40);
+        assert_eq!(imported.status, Status::Waiting);
+        assert_eq!(imported.recurrence, "FREQ=WEEKLY;BYDAY=FR;INTERVAL=1;EXDATE=20251205");
+    }
+
+    #[test]
+    fn import_handles_utc_all_day_and_missing_due() {
+        let text = "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:a@lms\r\nSUMMARY:Quiz\r\nDUE:20251201T100000Z\r\nEND:VTODO\r\n\
+                    BEGIN:VTODO\r\nUID:b@lms\r\nSUMMARY:Essay\r\nDUE;VALUE=DATE:20251203\r\nSTATUS:COMPLETED\r\nEND:VTODO\r\n\
+                    BEGIN:VTODO\r\nUID:c@lms\r\nSUMMARY:Someday\r\nEND:VTODO\r\nEND:VCALENDAR\r\n";
+        let (items, skipped) = parse_ics(text);
+        assert_eq!(skipped, 1);
+        assert_eq!(items[0].due_text, "2025-12-01 10:00");
+        assert_eq!(items[0].due_zone, DueZone::Offset(0).to_string());
+        assert_eq!(items[0].external_uid, "a@lms");
+        assert_eq!(items[1].due_text, "2025-12-03 23:59");
+        assert_eq!(items[1].progress, 100);
+        assert_eq!(items[1].status, Status::Completed);
+        assert_eq!(items[0].status, Status::Open);
+    }
+}
@@ -1,18 +1,63 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter};
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
 
 use chrono::Utc;
 use ulid::Ulid;
 
+use crate::model::Status;
+use crate::persistence::events::{ChangeBus, RepoEvent};
+use crate::persistence::query::Query;
 use crate::persistence::repo::{HomeworkRepo, RepoError, RepoResult};
-use crate::persistence::types::{HomeworkRecord, NewHomework, Patch};
+use crate::persistence::types::{HomeworkRecord, NewHomework, Patch, CURRENT_SCHEMA_VERSION};
+
+/// File name of the JSON store inside the data directory.
+pub const DATA_FILE_NAME: &str = "deadlines.json";
+
+/// Number of rotating backups kept next to the data file (deadlines.json.1 is the newest).
+const BACKUP_COUNT: usize = 5;
+
+/// Backups rotate on the first save of a session and then at most this often, so a burst
+/// of saves (dragging a progress slider) cannot push every older backup out.
+const BACKUP_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Identifies one version of the data file. The content hash catches rewrites that keep
+/// the same mtime and length, and lets a mere `touch` pass without a reload.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: u64,
+}
+
+impl FileStamp {
+    fn of(path: &Path, contents: &[u8]) -> RepoResult<Self> {
+        let meta = fs::metadata(path).map_err(|e| RepoError::Unavailable(e.to_string()))?;
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        Ok(Self { modified: meta.modified().ok(), len: meta.len(), hash: hasher.finish() })
+    }
+}
+
+/// The file as this repo last read or wrote it.
+#[derive(Default)]
+struct Synced {
+    stamp: Option<FileStamp>,
+    records: Vec<HomeworkRecord>,
+}
 
 pub struct JsonRepo {
     file_path: PathBuf,
-    // Simple lock to serialize access to the file
-    lock: Mutex<()>,
+    // Serializes access to the file and holds its last known contents
+    state: Mutex<Synced>,
+    // When this repo last rotated the backups; `None` until its first save
+    last_backup: Mutex<Option<Instant>>,
+    changes: ChangeBus,
 }
 
 impl JsonRepo {
@@ -20,81 +65,283 @@ impl JsonRepo {
         if !dir.exists() {
             fs::create_dir_all(&dir)?;
         }
-        let file_path = dir.join("deadlines.json");
+        let file_path = dir.join(DATA_FILE_NAME);
         if !file_path.exists() {
-            let file = File::create(&file_path)?;
-            let writer = BufWriter::new(file);
-            serde_json::to_writer(writer, &Vec::<HomeworkRecord>::new())?;
+            fs::write(&file_path, serde_json::to_vec(&Vec::<HomeworkRecord>::new())?)?;
         }
-        Ok(Self {
+        let repo = Self {
             file_path,
-            lock: Mutex::new(()),
-        })
+            state: Mutex::new(Synced::default()),
+            last_backup: Mutex::new(None),
+            changes: ChangeBus::default(),
+        };
+        repo.migrate()?;
+        Ok(repo)
     }
 
-    fn load(&self) -> RepoResult<Vec<HomeworkRecord>> {
-        let file = File::open(&self.file_path).map_err(|e| RepoError::Unavailable(e.to_string()))?;
-        let reader = BufReader::new(file);
-        // If file is empty or invalid, return empty list or error. 
-        // Here we assume valid JSON array.
-        let records: Vec<HomeworkRecord> = serde_json::from_reader(reader).unwrap_or_default();
-        Ok(records)
+    fn lock(&self) -> MutexGuard<'_, Synced> {
+        self.state.lock().unwrap()
     }
 
-    fn save(&self, records: &[HomeworkRecord]) -> RepoResult<()> {
-        let file = File::create(&self.file_path).map_err(|e| RepoError::Unavailable(e.to_string()))?;
-        let writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(writer, records).map_err(|e| RepoError::Serde(e.to_string()))?;
+    /// Upgrade records from older schema versions once, so they are pinned on disk.
+    /// A corrupt file is left alone here; it is reported on the first `list` instead.
+    fn migrate(&self) -> RepoResult<()> {
+        let mut state = self.lock();
+        match self.refresh(&mut state) {
+            Err(RepoError::Corrupt(_)) => return Ok(()),
+            other => {
+                other?;
+            }
+        }
+        let mut records = state.records.clone();
+        let mut changed = false;
+        for rec in records.iter_mut() {
+            changed |= rec.migrate();
+        }
+        if changed {
+            self.save(&mut state, records)?;
+        }
         Ok(())
     }
+
+    /// The file's stamp and contents if it differs from the last version we saw.
+    /// Unchanged mtime and length are trusted without reading the file.
+    fn changed_on_disk(&self, state: &mut Synced) -> RepoResult<Option<(FileStamp, Vec<u8>)>> {
+        let io_err = |e: std::io::Error| RepoError::Unavailable(e.to_string());
+        if let Some(known) = state.stamp {
+            let meta = fs::metadata(&self.file_path).map_err(io_err)?;
+            if meta.modified().ok() == known.modified && meta.len() == known.len {
+                return Ok(None);
+            }
+        }
+        let contents = fs::read(&self.file_path).map_err(io_err)?;
+        let stamp = FileStamp::of(&self.file_path, &contents)?;
+        match state.stamp.as_mut() {
+            // Touched but not edited: remember the new mtime so we stop re-hashing.
+            Some(known) if known.hash == stamp.hash => {
+                *known = stamp;
+                Ok(None)
+            }
+            _ => Ok(Some((stamp, contents))),
+        }
+    }
+
+    /// Re-read the file if it changed. Returns whether another program changed it;
+    /// the first read after opening does not count.
+    fn refresh(&self, state: &mut Synced) -> RepoResult<bool> {
+        let Some((stamp, contents)) = self.changed_on_disk(state)? else {
+            return Ok(false);
+        };
+        let records = parse_records(&self.file_path, &contents)?;
+        let external = state.stamp.is_some();
+        *state = Synced { stamp: Some(stamp), records };
+        Ok(external)
+    }
+
+    /// `refresh`, announcing external edits to subscribers.
+    fn sync(&self, state: &mut Synced) -> RepoResult<bool> {
+        let external = self.refresh(state)?;
+        if external {
+            self.changes.emit(RepoEvent::Refreshed);
+        }
+        Ok(external)
+    }
+
+    /// Lock the state for a read, picking up external edits first.
+    fn read(&self) -> RepoResult<MutexGuard<'_, Synced>> {
+        let mut state = self.lock();
+        self.sync(&mut state)?;
+        Ok(state)
+    }
+
+    /// Lock the state for a mutation. External edits are not picked up here but merged
+    /// in `save`; the file is only read if it never loaded (e.g. it was corrupt).
+    fn write(&self) -> RepoResult<MutexGuard<'_, Synced>> {
+        let mut state = self.lock();
+        if state.stamp.is_none() {
+            self.refresh(&mut state)?;
+        }
+        Ok(state)
+    }
+
+    fn backup_path(&self, n: usize) -> PathBuf {
+        let mut name = self.file_path.as_os_str().to_owned();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    /// Shift deadlines.json.1..N-1 up by one and copy the current file to .1, unless the
+    /// backups were already rotated less than `BACKUP_INTERVAL` ago.
+    fn rotate_backups(&self) -> RepoResult<()> {
+        let mut last = self.last_backup.lock().unwrap();
+        if last.is_some_and(|at| at.elapsed() < BACKUP_INTERVAL) || !self.file_path.exists() {
+            return Ok(());
+        }
+        let io_err = |e: std::io::Error| RepoError::Unavailable(e.to_string());
+        for n in (1..BACKUP_COUNT).rev() {
+            let from = self.backup_path(n);
+            if from.exists() {
+                fs::rename(&from, self.backup_path(n + 1)).map_err(io_err)?;
+            }
+        }
+        fs::copy(&self.file_path, self.backup_path(1)).map_err(io_err)?;
+        *last = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Write to a temp file, fsync it, then rename over the data file so a crash
+    /// leaves either the old or the new contents, never a truncated file.
+    fn write_atomic(&self, records: &[HomeworkRecord]) -> RepoResult<FileStamp> {
+        let io_err = |e: std::io::Error| RepoError::Unavailable(e.to_string());
+        let mut tmp_name = self.file_path.as_os_str().to_owned();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+
+        let contents = serde_json::to_vec_pretty(records).map_err(|e| RepoError::Serde(e.to_string()))?;
+        let mut file = File::create(&tmp_path).map_err(io_err)?;
+        file.write_all(&contents).map_err(io_err)?;
+        file.sync_all().map_err(io_err)?;
+        drop(file);
+
+        fs::rename(&tmp_path, &self.file_path).map_err(io_err)?;
+        // Persist the rename itself; not supported on every platform, so best effort.
+        if let Some(dir) = self.file_path.parent() {
+            if let Ok(d) = File::open(dir) {
+                let _ = d.sync_all();
+            }
+        }
+        FileStamp::of(&self.file_path, &contents)
+    }
+
+    /// Write `records`, our edit of `state.records`. If the file changed on disk since
+    /// we last read it, both edits are merged first and `Refreshed` is emitted.
+    fn save(&self, state: &mut Synced, records: Vec<HomeworkRecord>) -> RepoResult<()> {
+        let mut external = false;
+        let records = match self.changed_on_disk(state)? {
+            Some((_, contents)) if state.stamp.is_some() => {
+                let theirs = parse_records(&self.file_path, &contents)?;
+                external = true;
+                merge(&state.records, records, theirs)
+            }
+            _ => records,
+        };
+        self.rotate_backups()?;
+        let stamp = self.write_atomic(&records)?;
+        *state = Synced { stamp: Some(stamp), records };
+        if external {
+            self.changes.emit(RepoEvent::Refreshed);
+        }
+        Ok(())
+    }
+}
+
+fn parse_records(path: &Path, contents: &[u8]) -> RepoResult<Vec<HomeworkRecord>> {
+    serde_json::from_slice(contents).map_err(|e| RepoError::Corrupt(format!("{}: {}", path.display(), e)))
+}
+
+fn read_records(path: &Path) -> RepoResult<Vec<HomeworkRecord>> {
+    let file = File::open(path).map_err(|e| RepoError::Unavailable(e.to_string()))?;
+    let reader = BufReader::new(file);
+    serde_json::from_reader(reader).map_err(|e| RepoError::Corrupt(format!("{}: {}", path.display(), e)))
+}
+
+/// Three-way merge of our records and the file's (`theirs`) against the `base` both
+/// started from. A record only one side changed takes that side's version; one both
+/// changed keeps the later `updated_at`, ours on a tie. Records new on either side are
+/// kept, and a purge wins unless the other side edited the record since.
+fn merge(base: &[HomeworkRecord], ours: Vec<HomeworkRecord>, theirs: Vec<HomeworkRecord>) -> Vec<HomeworkRecord> {
+    let base: HashMap<&str, &HomeworkRecord> = base.iter().map(|r| (r.uid.as_str(), r)).collect();
+    let mut ours_by_uid: HashMap<String, HomeworkRecord> = HashMap::new();
+    let mut our_order = Vec::new();
+    for rec in ours {
+        our_order.push(rec.uid.clone());
+        ours_by_uid.insert(rec.uid.clone(), rec);
+    }
+
+    let mut merged = Vec::new();
+    for t in theirs {
+        let b = base.get(t.uid.as_str()).copied();
+        match (b, ours_by_uid.remove(&t.uid)) {
+            (Some(b), Some(o)) if &o == b => merged.push(t),
+            (Some(b), Some(o)) if &t == b => merged.push(o),
+            (_, Some(o)) => merged.push(if t.updated_at > o.updated_at { t } else { o }),
+            // We purged it; keep it only if they edited it meanwhile.
+            (Some(b), None) => {
+                if &t != b {
+                    merged.push(t);
+                }
+            }
+            (None, None) => merged.push(t),
+        }
+    }
+    // Ours that are not in the file: new here, or purged there.
+    for uid in our_order {
+        if let Some(o) = ours_by_uid.remove(&uid) {
+            if base.get(uid.as_str()).is_none_or(|b| *b != &o) {
+                merged.push(o);
+            }
+        }
+    }
+    merged
 }
 
 impl HomeworkRepo for JsonRepo {
     fn list(&self) -> RepoResult<Vec<HomeworkRecord>> {
-        let _guard = self.lock.lock().unwrap();
-        let records = self.load()?;
+        let state = self.read()?;
         // Filter out deleted ones
-        Ok(records.into_iter().filter(|r| !r.deleted).collect())
+        Ok(state.records.iter().filter(|r| !r.deleted).cloned().collect())
     }
 
     fn get(&self, uid: &str) -> RepoResult<Option<HomeworkRecord>> {
-        let _guard = self.lock.lock().unwrap();
-        let records = self.load()?;
-        Ok(records.into_iter().find(|r| r.uid == uid))
+        let state = self.read()?;
+        Ok(state.records.iter().find(|r| r.uid == uid).cloned())
     }
 
     fn create(&self, payload: NewHomework) -> RepoResult<HomeworkRecord> {
-        let _guard = self.lock.lock().unwrap();
-        let mut records = self.load()?;
+        let mut state = self.write()?;
+        let mut records = state.records.clone();
         
         let now = Utc::now().timestamp();
-        let rec = HomeworkRecord {
+        let mut rec = HomeworkRecord {
             uid: Ulid::new().to_string(),
             name: payload.name,
             due_text: payload.due_text,
+            due_zone: payload.due_zone,
             difficulty: payload.difficulty,
             progress: payload.progress,
             tags: payload.tags,
             milestones: payload.milestones,
+            recurrence: payload.recurrence,
+            external_uid: payload.external_uid,
             deleted: false,
+            deleted_at: None,
+            status: Status::Open,
+            completed_at: None,
+            cancelled_at: None,
+            reminders: payload.reminders,
             created_at: now,
             updated_at: now,
-            schema_version: 1,
+            schema_version: CURRENT_SCHEMA_VERSION,
         };
+        rec.sync_progress_from_milestones();
+        rec.set_status(payload.status, now)?;
 
         records.push(rec.clone());
-        self.save(&records)?;
+        self.save(&mut state, records)?;
+        self.changes.emit(RepoEvent::Created(rec.clone()));
         Ok(rec)
     }
 
     fn update(&self, mut record: HomeworkRecord) -> RepoResult<HomeworkRecord> {
-        let _guard = self.lock.lock().unwrap();
-        let mut records = self.load()?;
+        let mut state = self.write()?;
+        let mut records = state.records.clone();
         
         if let Some(idx) = records.iter().position(|r| r.uid == record.uid) {
+            record.sync_progress_from_milestones();
             record.updated_at = Utc::now().timestamp();
             records[idx] = record.clone();
-            self.save(&records)?;
+            self.save(&mut state, records)?;
+            self.changes.emit(RepoEvent::Updated(record.clone()));
             Ok(record)
         } else {
             Err(RepoError::NotFound)
@@ -102,15 +349,16 @@ impl HomeworkRepo for JsonRepo {
     }
 
     fn patch(&self, uid: &str, patch: Patch) -> RepoResult<HomeworkRecord> {
-        let _guard = self.lock.lock().unwrap();
-        let mut records = self.load()?;
+        let mut state = self.write()?;
+        let mut records = state.records.clone();
         
         if let Some(idx) = records.iter().position(|r| r.uid == uid) {
             let mut current = records[idx].clone();
             let now = Utc::now().timestamp();
-            current.apply_patch(patch, now);
+            current.apply_patch(patch, now)?;
             records[idx] = current.clone();
-            self.save(&records)?;
+            self.save(&mut state, records)?;
+            self.changes.emit(RepoEvent::Updated(current.clone()));
             Ok(current)
         } else {
             Err(RepoError::NotFound)
@@ -118,16 +366,250 @@ impl HomeworkRepo for JsonRepo {
     }
 
     fn delete(&self, uid: &str) -> RepoResult<()> {
-        let _guard = self.lock.lock().unwrap();
-        let mut records = self.load()?;
+        let mut state = self.write()?;
+        let mut records = state.records.clone();
         
         if let Some(idx) = records.iter().position(|r| r.uid == uid) {
-            records[idx].deleted = true;
-            records[idx].updated_at = Utc::now().timestamp();
-            self.save(&records)?;
+            let now = Utc::now().timestamp();
+            records[idx].set_deleted(true, now);
+            records[idx].updated_at = now;
+            let deleted = records[idx].clone();
+            self.save(&mut state, records)?;
+            self.changes.emit(RepoEvent::Deleted(deleted));
             Ok(())
         } else {
             Err(RepoError::NotFound)
         }
     }
+
+    fn query(&self, query: &Query) -> RepoResult<Vec<HomeworkRecord>> {
+        let state = self.read()?;
+        Ok(query.apply(state.records.iter().cloned()))
+    }
+
+    fn list_deleted(&self) -> RepoResult<Vec<HomeworkRecord>> {
+        let state = self.read()?;
+        let mut records: Vec<HomeworkRecord> = state.records.iter().filter(|r| r.deleted).cloned().collect();
+        records.sort_by_key(|r| std::cmp::Reverse(r.deleted_time()));
+        Ok(records)
+    }
+
+    fn purge(&self, uid: &str) -> RepoResult<()> {
+        let mut state = self.write()?;
+        let mut records = state.records.clone();
+        let before = records.len();
+        records.retain(|r| r.uid != uid);
+        if records.len() == before {
+            return Err(RepoError::NotFound);
+        }
+        self.save(&mut state, records)?;
+        self.changes.emit(RepoEvent::Purged(uid.to_string()));
+        Ok(())
+    }
+
+    // One write for the whole batch, so an auto-purge does not rotate every backup away.
+    fn purge_deleted_before(&self, cutoff_ts: i64) -> RepoResult<usize> {
+        let mut state = self.write()?;
+        let (kept, expired): (Vec<HomeworkRecord>, Vec<HomeworkRecord>) = state
+            .records
+            .iter()
+            .cloned()
+            .partition(|r| r.deleted_time().is_none_or(|t| t >= cutoff_ts));
+        if !expired.is_empty() {
+            self.save(&mut state, kept)?;
+        }
+        for rec in &expired {
+            self.changes.emit(RepoEvent::Purged(rec.uid.clone()));
+        }
+        Ok(expired.len())
+    }
+
+    fn poll_external_changes(&self) -> RepoResult<bool> {
+        self.sync(&mut self.lock())
+    }
+
+    fn restore_latest_backup(&self) -> RepoResult<()> {
+        let mut state = self.lock();
+        let records = (1..=BACKUP_COUNT)
+            .find_map(|n| read_records(&self.backup_path(n)).ok())
+            .ok_or_else(|| RepoError::Unavailable("no readable backup found".to_string()))?;
+        // Keep the damaged file around for inspection instead of rotating it into the backups.
+        if self.file_path.exists() {
+            let mut corrupt_name = self.file_path.as_os_str().to_owned();
+            corrupt_name.push(format!(".corrupt-{}", Utc::now().timestamp()));
+            fs::rename(&self.file_path, PathBuf::from(corrupt_name))
+                .map_err(|e| RepoError::Unavailable(e.to_string()))?;
+        }
+        let stamp = self.write_atomic(&records)?;
+        *state = Synced { stamp: Some(stamp), records };
+        self.changes.emit(RepoEvent::Reloaded);
+        Ok(())
+    }
+
+    fn changes(&self) -> &ChangeBus {
+        &self.changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::conformance::{new_homework, TempDir};
+    use crate::persistence::memory::MemoryRepo;
+    use std::sync::Arc;
+
+    /// Rewrite the data file the way a script or sync tool would.
+    fn edit_externally(dir: &TempDir, edit: impl FnOnce(&mut Vec<HomeworkRecord>)) {
+        let path = dir.0.join(DATA_FILE_NAME);
+        let mut records = read_records(&path).unwrap();
+        edit(&mut records);
+        fs::write(&path, serde_json::to_vec_pretty(&records).unwrap()).unwrap();
+    }
+
+    fn names(repo: &JsonRepo) -> Vec<String> {
+        repo.list().unwrap().into_iter().map(|r| r.name).collect()
+    }
+
+    #[test]
+    fn external_edit_is_picked_up_and_announced() {
+        let dir = TempDir::new();
+        let repo = JsonRepo::new(dir.0.clone()).unwrap();
+        repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+        let seen: Arc<Mutex<Vec<RepoEvent>>> = Arc::default();
+        let sink = seen.clone();
+        repo.changes().subscribe(Box::new(move |e| sink.lock().unwrap().push(e.clone())));
+
+        assert!(!repo.poll_external_changes().unwrap());
+        edit_externally(&dir, |records| records[0].name = "Essay draft".to_string());
+        assert!(repo.poll_external_changes().unwrap());
+        assert_eq!(names(&repo), vec!["Essay draft"]);
+        assert_eq!(*seen.lock().unwrap(), vec![RepoEvent::Refreshed]);
+    }
+
+    #[test]
+    fn save_merges_edits_made_on_both_sides() {
+        let dir = TempDir::new();
+        let repo = JsonRepo::new(dir.0.clone()).unwrap();
+        let essay = repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+        let lab = repo.create(new_homework("Lab", "2025-11-28 23:59", "UTC")).unwrap();
+        let quiz = repo.create(new_homework("Quiz", "2025-11-28 23:59", "UTC")).unwrap();
+
+        // Elsewhere: rename the lab, drop the quiz, add a talk.
+        edit_externally(&dir, |records| {
+            records[1].name = "Lab report".to_string();
+            records[1].updated_at += 1;
+            records.retain(|r| r.uid != quiz.uid);
+            let mut talk = records[0].clone();
+            talk.uid = Ulid::new().to_string();
+            talk.name = "Talk".to_string();
+            records.push(talk);
+        });
+        // Here, without having seen that: edit the essay.
+        repo.patch(&essay.uid, Patch { progress: Some(50), ..Default::default() }).unwrap();
+
+        let on_disk = read_records(&dir.0.join(DATA_FILE_NAME)).unwrap();
+        let on_disk: Vec<_> = on_disk.iter().map(|r| (r.name.as_str(), r.progress)).collect();
+        assert_eq!(on_disk, vec![("Essay", 50), ("Lab report", 0), ("Talk", 0)]);
+        assert_eq!(repo.get(&lab.uid).unwrap().unwrap().name, "Lab report");
+    }
+
+    #[test]
+    fn merge_keeps_the_later_edit_of_a_record_changed_on_both_sides() {
+        let repo = MemoryRepo::new();
+        repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+        repo.create(new_homework("Lab", "2025-11-28 23:59", "UTC")).unwrap();
+        let base = repo.list().unwrap();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours[0].name = "Ours".to_string();
+        ours[0].updated_at = 20;
+        theirs[0].name = "Theirs".to_string();
+        theirs[0].updated_at = 30;
+        ours[1].name = "Ours again".to_string();
+        ours[1].updated_at = 30;
+        theirs[1].name = "Theirs again".to_string();
+        theirs[1].updated_at = 20;
+
+        let merged = merge(&base, ours, theirs);
+        let merged: Vec<_> = merged.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(merged, vec!["Theirs", "Ours again"]);
+    }
+
+    fn backup_names(repo: &JsonRepo, n: usize) -> Vec<String> {
+        read_records(&repo.backup_path(n)).unwrap().into_iter().map(|r| r.name).collect()
+    }
+
+    #[test]
+    fn corrupt_file_is_reported_and_never_overwritten() {
+        let dir = TempDir::new();
+        let path = dir.0.join(DATA_FILE_NAME);
+        assert!(matches!(read_records(&path), Err(RepoError::Unavailable(_))));
+        fs::write(&path, "[{ not json").unwrap();
+        assert!(matches!(read_records(&path), Err(RepoError::Corrupt(_))));
+
+        let repo = JsonRepo::new(dir.0.clone()).unwrap();
+        assert!(matches!(repo.list(), Err(RepoError::Corrupt(_))));
+        assert!(matches!(
+            repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")),
+            Err(RepoError::Corrupt(_))
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[{ not json");
+        assert!(!repo.backup_path(1).exists());
+    }
+
+    #[test]
+    fn save_replaces_the_file_through_a_temp_file() {
+        let dir = TempDir::new();
+        let repo = JsonRepo::new(dir.0.clone()).unwrap();
+        // A temp file left behind by a crash is simply replaced.
+        let tmp = dir.0.join(format!("{DATA_FILE_NAME}.tmp"));
+        fs::write(&tmp, "[{ half written").unwrap();
+        repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+
+        assert!(!tmp.exists());
+        let on_disk = read_records(&dir.0.join(DATA_FILE_NAME)).unwrap();
+        assert_eq!(on_disk, repo.list().unwrap());
+    }
+
+    #[test]
+    fn backups_rotate_once_per_session_up_to_backup_count() {
+        let dir = TempDir::new();
+        for i in 0..BACKUP_COUNT + 2 {
+            let repo = JsonRepo::new(dir.0.clone()).unwrap();
+            repo.create(new_homework(&format!("Session {i}"), "2025-11-28 23:59", "UTC")).unwrap();
+            repo.create(new_homework(&format!("Again {i}"), "2025-11-28 23:59", "UTC")).unwrap();
+        }
+        let repo = JsonRepo::new(dir.0.clone()).unwrap();
+        assert!(!repo.backup_path(BACKUP_COUNT + 1).exists());
+        // Each backup is the file as a session found it, newest first.
+        for n in 1..=BACKUP_COUNT {
+            assert_eq!(backup_names(&repo, n).len(), 2 * (BACKUP_COUNT + 2 - n));
+        }
+        assert_eq!(backup_names(&repo, 1).last().unwrap(), &format!("Again {}", BACKUP_COUNT));
+    }
+
+    #[test]
+    fn restore_uses_the_newest_readable_backup() {
+        let dir = TempDir::new();
+        let repo = JsonRepo::new(dir.0.clone()).unwrap();
+        repo.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+        drop(repo);
+        let repo = JsonRepo::new(dir.0.clone()).unwrap();
+        repo.create(new_homework("Lab", "2025-11-28 23:59", "UTC")).unwrap();
+        drop(repo);
+        let repo = JsonRepo::new(dir.0.clone()).unwrap();
+        repo.create(new_homework("Quiz", "2025-11-28 23:59", "UTC")).unwrap();
+
+        // .1 holds Essay and Lab but is damaged; .2 holds just Essay.
+        fs::write(repo.backup_path(1), "garbage").unwrap();
+        fs::write(dir.0.join(DATA_FILE_NAME), "[{ not json").unwrap();
+        repo.restore_latest_backup().unwrap();
+
+        assert_eq!(names(&repo), vec!["Essay"]);
+        let kept = fs::read_dir(&dir.0)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .any(|e| e.file_name().to_string_lossy().starts_with(&format!("{DATA_FILE_NAME}.corrupt-")));
+        assert!(kept);
+    }
 }
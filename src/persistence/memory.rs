@@ -1,83 +1,124 @@
-use std::collections::HashMap;
 use std::sync::Mutex;
 
 use chrono::Utc;
 use ulid::Ulid;
 
+use crate::model::Status;
+use crate::persistence::events::{ChangeBus, RepoEvent};
+use crate::persistence::query::Query;
 use crate::persistence::repo::{HomeworkRepo, RepoError, RepoResult};
-use crate::persistence::types::{HomeworkRecord, NewHomework, Patch};
+use crate::persistence::types::{HomeworkRecord, NewHomework, Patch, CURRENT_SCHEMA_VERSION};
 
 pub struct MemoryRepo {
-    // in-memory map keyed by uid
-    inner: Mutex<HashMap<String, HomeworkRecord>>,
+    // in-memory records in insertion order, like the JSON file
+    inner: Mutex<Vec<HomeworkRecord>>,
+    changes: ChangeBus,
 }
 
 impl MemoryRepo {
     pub fn new() -> Self {
-        Self { inner: Mutex::new(HashMap::new()) }
+        Self { inner: Mutex::new(Vec::new()), changes: ChangeBus::default() }
+    }
+}
+
+impl Default for MemoryRepo {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl HomeworkRepo for MemoryRepo {
     fn list(&self) -> RepoResult<Vec<HomeworkRecord>> {
-        let map = self.inner.lock().unwrap();
-        let mut v: Vec<_> = map.values().cloned().collect();
-        v.sort_by(|a, b| a.due_text.cmp(&b.due_text));
-        Ok(v)
+        let records = self.inner.lock().unwrap();
+        Ok(records.iter().filter(|r| !r.deleted).cloned().collect())
     }
 
     fn get(&self, uid: &str) -> RepoResult<Option<HomeworkRecord>> {
-        let map = self.inner.lock().unwrap();
-        Ok(map.get(uid).cloned())
+        let records = self.inner.lock().unwrap();
+        Ok(records.iter().find(|r| r.uid == uid).cloned())
     }
 
     fn create(&self, payload: NewHomework) -> RepoResult<HomeworkRecord> {
-        let mut map = self.inner.lock().unwrap();
+        let mut records = self.inner.lock().unwrap();
         let now = Utc::now().timestamp();
-        let uid = Ulid::new().to_string();
-        let rec = HomeworkRecord {
-            uid: uid.clone(),
+        let mut rec = HomeworkRecord {
+            uid: Ulid::new().to_string(),
             name: payload.name,
             due_text: payload.due_text,
+            due_zone: payload.due_zone,
             difficulty: payload.difficulty,
             progress: payload.progress,
             tags: payload.tags,
             milestones: payload.milestones,
+            recurrence: payload.recurrence,
+            external_uid: payload.external_uid,
             deleted: false,
+            deleted_at: None,
+            status: Status::Open,
+            completed_at: None,
+            cancelled_at: None,
+            reminders: payload.reminders,
             created_at: now,
             updated_at: now,
-            schema_version: 1,
+            schema_version: CURRENT_SCHEMA_VERSION,
         };
-        map.insert(uid.clone(), rec.clone());
+        rec.sync_progress_from_milestones();
+        rec.set_status(payload.status, now)?;
+        records.push(rec.clone());
+        self.changes.emit(RepoEvent::Created(rec.clone()));
         Ok(rec)
     }
 
     fn update(&self, record: HomeworkRecord) -> RepoResult<HomeworkRecord> {
-        let mut map = self.inner.lock().unwrap();
-        if !map.contains_key(&record.uid) {
-            return Err(RepoError::NotFound);
-        }
+        let mut records = self.inner.lock().unwrap();
+        let slot = records.iter_mut().find(|r| r.uid == record.uid).ok_or(RepoError::NotFound)?;
         let mut record = record;
+        record.sync_progress_from_milestones();
         record.updated_at = Utc::now().timestamp();
-        map.insert(record.uid.clone(), record.clone());
+        *slot = record.clone();
+        self.changes.emit(RepoEvent::Updated(record.clone()));
         Ok(record)
     }
 
     fn patch(&self, uid: &str, patch: Patch) -> RepoResult<HomeworkRecord> {
-        let mut map = self.inner.lock().unwrap();
-        let rec = map.get_mut(uid).ok_or(RepoError::NotFound)?;
-        let now = Utc::now().timestamp();
-        let mut cloned = rec.clone();
-        cloned.apply_patch(patch, now);
-        *rec = cloned.clone();
-        Ok(cloned)
+        let mut records = self.inner.lock().unwrap();
+        let rec = records.iter_mut().find(|r| r.uid == uid).ok_or(RepoError::NotFound)?;
+        rec.apply_patch(patch, Utc::now().timestamp())?;
+        self.changes.emit(RepoEvent::Updated(rec.clone()));
+        Ok(rec.clone())
     }
 
     fn delete(&self, uid: &str) -> RepoResult<()> {
-        let mut map = self.inner.lock().unwrap();
-        let rec = map.get_mut(uid).ok_or(RepoError::NotFound)?;
-        rec.deleted = true;
-        rec.updated_at = Utc::now().timestamp();
+        let mut records = self.inner.lock().unwrap();
+        let rec = records.iter_mut().find(|r| r.uid == uid).ok_or(RepoError::NotFound)?;
+        let now = Utc::now().timestamp();
+        rec.set_deleted(true, now);
+        rec.updated_at = now;
+        self.changes.emit(RepoEvent::Deleted(rec.clone()));
+        Ok(())
+    }
+
+    fn query(&self, query: &Query) -> RepoResult<Vec<HomeworkRecord>> {
+        let records = self.inner.lock().unwrap();
+        Ok(query.apply(records.iter().cloned()))
+    }
+
+    fn list_deleted(&self) -> RepoResult<Vec<HomeworkRecord>> {
+        let records = self.inner.lock().unwrap();
+        let mut v: Vec<_> = records.iter().filter(|r| r.deleted).cloned().collect();
+        v.sort_by_key(|r| std::cmp::Reverse(r.deleted_time()));
+        Ok(v)
+    }
+
+    fn purge(&self, uid: &str) -> RepoResult<()> {
+        let mut records = self.inner.lock().unwrap();
+        let idx = records.iter().position(|r| r.uid == uid).ok_or(RepoError::NotFound)?;
+        records.remove(idx);
+        self.changes.emit(RepoEvent::Purged(uid.to_string()));
         Ok(())
     }
+
+    fn changes(&self) -> &ChangeBus {
+        &self.changes
+    }
 }
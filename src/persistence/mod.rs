@@ -1,12 +1,33 @@
 //! Persistence layer for DeadlineTracker (desktop-only for now)
-//! Provides a repository abstraction with two implementations:
+//! Provides a repository abstraction with three implementations:
 //! - MemoryRepo (when DIR=None; no persistence)
-//! - SQLiteRepo (when DIR=Some(path); persisted to a SQLite file)
+//! - JsonRepo (when DIR=Some(path) and Backend::Json; persisted to deadlines.json)
+//! - SqliteRepo (when DIR=Some(path) and Backend::Sqlite; persisted to deadlines.sqlite3)
+//!
+//! `HistoryRepo` wraps any of them with an undo/redo log.
+//! `workspace` keeps one repository per named workspace; `MergedRepo` combines them.
+//! `archive` decides when completed records leave the main list and reviews them afterwards.
+//! `ics` converts records to and from iCalendar for other calendar and task apps.
 
 pub mod types;
+pub mod query;
+pub mod events;
 pub mod repo;
 pub mod memory;
 pub mod json;
+pub mod sqlite;
+pub mod ics;
+pub mod history;
+pub mod workspace;
+pub mod archive;
 
-pub use repo::{init_repo, HomeworkRepo, RepoError};
-pub use types::{HomeworkRecord, NewHomework, Patch};
+#[cfg(test)]
+pub(crate) mod conformance;
+
+pub use events::{ChangeBus, RepoEvent, SubscriptionId};
+pub use history::{HistoryRepo, Operation};
+pub use archive::ArchiveDelay;
+pub use query::{ArchiveFilter, Query, QuerySort, SortField};
+pub use repo::{init_repo, Backend, HomeworkRepo, RepoError, TrashRetention};
+pub use types::{HomeworkRecord, MilestoneRecord, NewHomework, Patch};
+pub use workspace::{MergedRepo, DEFAULT_WORKSPACE};
@@ -0,0 +1,150 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use crate::model::Status;
+use crate::persistence::types::HomeworkRecord;
+
+/// Field a query result can be ordered by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortField {
+    /// The instant the record is due, whatever its `due_zone`
+    Due,
+    Name,
+    Progress,
+    Difficulty,
+    Created,
+    Updated,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuerySort {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+impl QuerySort {
+    pub fn asc(field: SortField) -> Self {
+        Self { field, descending: false }
+    }
+
+    pub fn desc(field: SortField) -> Self {
+        Self { field, descending: true }
+    }
+}
+
+/// Whether archived records (completed at or before `cutoff`, a Unix time) are returned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArchiveFilter {
+    #[default]
+    Include,
+    Exclude { cutoff: i64 },
+    Only { cutoff: i64 },
+}
+
+impl ArchiveFilter {
+    pub fn matches(&self, rec: &HomeworkRecord) -> bool {
+        match *self {
+            ArchiveFilter::Include => true,
+            ArchiveFilter::Exclude { cutoff } => !rec.is_archived(cutoff),
+            ArchiveFilter::Only { cutoff } => rec.is_archived(cutoff),
+        }
+    }
+}
+
+/// Filter, sort and pagination evaluated by the repository.
+/// All set filters must match. `Query::default()` returns what `list` returns.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    /// Records must carry every one of these tags (case-insensitive)
+    pub tags: Vec<String>,
+    /// Inclusive lower bound on the due time, "YYYY-MM-DD HH:MM" in UTC
+    pub due_from: Option<String>,
+    /// Inclusive upper bound on the due time, "YYYY-MM-DD HH:MM" in UTC
+    pub due_to: Option<String>,
+    pub progress: Option<RangeInclusive<u8>>,
+    pub difficulty: Option<RangeInclusive<u8>>,
+    /// Records must have one of these statuses; empty = any
+    pub status: Vec<Status>,
+    pub archive: ArchiveFilter,
+    /// Case-insensitive substring of the name or of any tag
+    pub text: Option<String>,
+    /// Also return soft-deleted records
+    pub include_deleted: bool,
+    /// Sort keys in priority order; ties keep insertion order
+    pub sort: Vec<QuerySort>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+impl Query {
+    /// Search text with surrounding whitespace removed; `None` if blank.
+    pub fn text_needle(&self) -> Option<String> {
+        self.text
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_lowercase)
+    }
+
+    pub fn matches(&self, rec: &HomeworkRecord) -> bool {
+        if rec.deleted && !self.include_deleted {
+            return false;
+        }
+        if !self.tags.iter().all(|t| rec.tags.iter().any(|x| x.eq_ignore_ascii_case(t))) {
+            return false;
+        }
+        if self.due_from.is_some() || self.due_to.is_some() {
+            let due = rec.due_utc_text();
+            if self.due_from.as_ref().is_some_and(|from| due < *from)
+                || self.due_to.as_ref().is_some_and(|to| due > *to)
+            {
+                return false;
+            }
+        }
+        if self.progress.as_ref().is_some_and(|r| !r.contains(&rec.progress))
+            || self.difficulty.as_ref().is_some_and(|r| !r.contains(&rec.difficulty))
+        {
+            return false;
+        }
+        if !self.status.is_empty() && !self.status.contains(&rec.status) {
+            return false;
+        }
+        if !self.archive.matches(rec) {
+            return false;
+        }
+        if let Some(needle) = self.text_needle() {
+            return rec.name.to_lowercase().contains(&needle)
+                || rec.tags.iter().any(|t| t.to_lowercase().contains(&needle));
+        }
+        true
+    }
+
+    fn compare(&self, a: &HomeworkRecord, b: &HomeworkRecord) -> Ordering {
+        for key in &self.sort {
+            let ord = match key.field {
+                SortField::Due => a.due_utc_text().cmp(&b.due_utc_text()),
+                SortField::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                SortField::Progress => a.progress.cmp(&b.progress),
+                SortField::Difficulty => a.difficulty.cmp(&b.difficulty),
+                SortField::Created => a.created_at.cmp(&b.created_at),
+                SortField::Updated => a.updated_at.cmp(&b.updated_at),
+            };
+            let ord = if key.descending { ord.reverse() } else { ord };
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+        Ordering::Equal
+    }
+
+    /// Evaluate the query over records in insertion order (for backends without an index).
+    pub fn apply(&self, records: impl IntoIterator<Item = HomeworkRecord>) -> Vec<HomeworkRecord> {
+        let mut matched: Vec<HomeworkRecord> = records.into_iter().filter(|r| self.matches(r)).collect();
+        matched.sort_by(|a, b| self.compare(a, b));
+        matched
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
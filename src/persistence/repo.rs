@@ -2,14 +2,19 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::persistence::memory::MemoryRepo;
-use crate::persistence::json::JsonRepo;
+use crate::persistence::json::{JsonRepo, DATA_FILE_NAME};
+use crate::persistence::sqlite::{remove_database, SqliteRepo, DB_FILE_NAME};
+use crate::persistence::events::ChangeBus;
+use crate::persistence::query::Query;
 use crate::persistence::types::{HomeworkRecord, NewHomework, Patch};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
 pub type RepoResult<T> = Result<T, RepoError>;
 
 /// Unified repository trait for local-only storage.
-/// Implementations: MemoryRepo (DIR=None), JsonRepo (DIR=Some(path)).
+/// Implementations: MemoryRepo (DIR=None), JsonRepo / SqliteRepo (DIR=Some(path), by `Backend`).
 pub trait HomeworkRepo: Send + Sync {
     fn list(&self) -> RepoResult<Vec<HomeworkRecord>>;
     fn get(&self, uid: &str) -> RepoResult<Option<HomeworkRecord>>;
@@ -17,29 +22,194 @@ pub trait HomeworkRepo: Send + Sync {
     fn update(&self, record: HomeworkRecord) -> RepoResult<HomeworkRecord>;
     fn patch(&self, uid: &str, patch: Patch) -> RepoResult<HomeworkRecord>;
     fn delete(&self, uid: &str) -> RepoResult<()>; // soft delete
+
+    /// Records matching `query`, filtered, sorted and paginated by the backend.
+    fn query(&self, query: &Query) -> RepoResult<Vec<HomeworkRecord>>;
+
+    /// Soft-deleted records, most recently deleted first.
+    fn list_deleted(&self) -> RepoResult<Vec<HomeworkRecord>>;
+
+    /// Take a soft-deleted record out of the trash.
+    fn restore(&self, uid: &str) -> RepoResult<HomeworkRecord> {
+        self.patch(uid, Patch { deleted: Some(false), ..Default::default() })
+    }
+
+    /// Remove a record for good. Cannot be undone.
+    fn purge(&self, uid: &str) -> RepoResult<()>;
+
+    /// Purge every record that went to the trash before `cutoff_ts`. Returns how many were removed.
+    fn purge_deleted_before(&self, cutoff_ts: i64) -> RepoResult<usize> {
+        let expired: Vec<HomeworkRecord> = self
+            .list_deleted()?
+            .into_iter()
+            .filter(|r| r.deleted_time().is_some_and(|t| t < cutoff_ts))
+            .collect();
+        for rec in &expired {
+            self.purge(&rec.uid)?;
+        }
+        Ok(expired.len())
+    }
+
+    /// Subscribers notified after every successful mutation.
+    fn changes(&self) -> &ChangeBus;
+
+    /// Pick up edits other programs made to the storage, emitting `RepoEvent::Refreshed`
+    /// if there were any. Returns whether there were. Backends that read through on
+    /// every call have nothing to do.
+    fn poll_external_changes(&self) -> RepoResult<bool> {
+        Ok(false)
+    }
+
+    /// Replace corrupt storage with the most recent readable backup, if the backend keeps any.
+    fn restore_latest_backup(&self) -> RepoResult<()> {
+        Err(RepoError::Unavailable("this storage backend keeps no backups".to_string()))
+    }
 }
 
-#[derive(Debug, Error)]
+#[derive(Clone, Debug, Error, PartialEq)]
 pub enum RepoError {
     #[error("not found")] 
     NotFound,
+    /// Stored data exists but cannot be parsed; callers must not overwrite it.
+    #[error("corrupt data: {0}")]
+    Corrupt(String),
     #[error("serialization error: {0}")]
     Serde(String),
     #[error("unavailable: {0}")]
     Unavailable(String),
+    /// Input rejected before touching storage (e.g. a bad workspace name)
+    #[error("invalid: {0}")]
+    Invalid(String),
     #[error("unknown: {0}")]
     Unknown(String),
 }
 
-/// Initialize the repository based on data directory.
+/// How long soft-deleted records stay in the trash before they are purged automatically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrashRetention {
+    /// `None` keeps deleted records until they are purged by hand.
+    pub days: Option<u32>,
+}
+
+impl Default for TrashRetention {
+    fn default() -> Self {
+        Self { days: Some(30) }
+    }
+}
+
+impl TrashRetention {
+    /// Choices offered in the settings panel.
+    pub const PRESETS: [Option<u32>; 4] = [Some(7), Some(30), Some(90), None];
+
+    /// Records deleted before this Unix time have expired.
+    pub fn cutoff(&self, now_ts: i64) -> Option<i64> {
+        self.days.map(|d| now_ts - i64::from(d) * 24 * 60 * 60)
+    }
+
+    pub fn label(&self) -> String {
+        match self.days {
+            Some(1) => "After 1 day".to_string(),
+            Some(d) => format!("After {} days", d),
+            None => "Never".to_string(),
+        }
+    }
+}
+
+// Written to config.toml like `ArchiveDelay`: a number of days, or "never".
+impl Serialize for TrashRetention {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.days {
+            Some(d) => serializer.serialize_u32(d),
+            None => serializer.serialize_str("never"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TrashRetention {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Days(u32),
+            Word(String),
+        }
+        match Repr::deserialize(deserializer)? {
+            Repr::Days(d) => Ok(Self { days: Some(d) }),
+            Repr::Word(w) if w.trim().eq_ignore_ascii_case("never") => Ok(Self { days: None }),
+            Repr::Word(w) => Err(D::Error::custom(format!("expected a number of days or \"never\", got {w:?}"))),
+        }
+    }
+}
+
+/// Storage backend used when a data directory is given.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    #[default]
+    Json,
+    #[serde(alias = "sqlite3")]
+    Sqlite,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 2] = [Backend::Json, Backend::Sqlite];
+
+    /// Stable identifier for configuration ("json" / "sqlite").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Backend::Json => "json",
+            Backend::Sqlite => "sqlite",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Backend::Json => "JSON file",
+            Backend::Sqlite => "SQLite database",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Backend::Json),
+            "sqlite" | "sqlite3" => Some(Backend::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// Backend named by `DEADLINE_TRACKER_BACKEND` ("json" or "sqlite"), which overrides the config file.
+    pub fn env_override() -> Option<Self> {
+        std::env::var("DEADLINE_TRACKER_BACKEND")
+            .ok()
+            .and_then(|s| Backend::parse(&s))
+    }
+}
+
+/// Initialize the repository based on data directory and configured backend.
 /// - None => MemoryRepo (no persistence)
-/// - Some(path) => JsonRepo under that directory (creates file if missing)
-pub fn init_repo(data_dir: Option<PathBuf>) -> RepoResult<Arc<dyn HomeworkRepo>> {
-    match data_dir {
-        None => Ok(Arc::new(MemoryRepo::new())),
-        Some(path) => {
+/// - Some(path), Json => JsonRepo under that directory (creates file if missing)
+/// - Some(path), Sqlite => SqliteRepo under that directory; on first use, an existing
+///   deadlines.json there is imported once. If that import fails, the new database is
+///   removed again so the import is retried on the next start.
+pub fn init_repo(data_dir: Option<PathBuf>, backend: Backend) -> RepoResult<Arc<dyn HomeworkRepo>> {
+    match (data_dir, backend) {
+        (None, _) => Ok(Arc::new(MemoryRepo::new())),
+        (Some(path), Backend::Json) => {
             let repo = JsonRepo::new(path).map_err(|e| RepoError::Unavailable(e.to_string()))?;
             Ok(Arc::new(repo))
         }
+        (Some(path), Backend::Sqlite) => {
+            let first_run = !path.join(DB_FILE_NAME).exists();
+            let repo = SqliteRepo::new(path.clone()).map_err(|e| RepoError::Unavailable(e.to_string()))?;
+            let json_path = path.join(DATA_FILE_NAME);
+            if first_run && json_path.exists() {
+                if let Err(e) = repo.import_json(&json_path) {
+                    drop(repo);
+                    let _ = remove_database(&path);
+                    return Err(e);
+                }
+            }
+            Ok(Arc::new(repo))
+        }
     }
 }
@@ -0,0 +1,594 @@
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::Utc;
+use rusqlite::types::Value;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row, Transaction};
+use ulid::Ulid;
+
+use crate::model::Status;
+use crate::persistence::events::{ChangeBus, RepoEvent};
+use crate::persistence::query::{ArchiveFilter, Query, SortField};
+use crate::persistence::repo::{HomeworkRepo, RepoError, RepoResult};
+use crate::persistence::types::{due_utc_text, HomeworkRecord, NewHomework, Patch, CURRENT_SCHEMA_VERSION};
+
+pub const DB_FILE_NAME: &str = "deadlines.sqlite3";
+
+/// Delete the database in `dir` along with its WAL side files. The repo must be closed.
+pub fn remove_database(dir: &Path) -> std::io::Result<()> {
+    for suffix in ["", "-wal", "-shm"] {
+        match fs::remove_file(dir.join(format!("{DB_FILE_NAME}{suffix}"))) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Schema migrations keyed on `schema_version` (tracked in `PRAGMA user_version`).
+/// Each step upgrades the database from the previous version; they run in one transaction.
+const MIGRATIONS: &[(u16, &str)] = &[
+    (
+        1,
+        "CREATE TABLE homework (
+            uid TEXT PRIMARY KEY NOT NULL,
+            name TEXT NOT NULL,
+            due_text TEXT NOT NULL,
+            difficulty INTEGER NOT NULL,
+            progress INTEGER NOT NULL,
+            tags_json TEXT NOT NULL DEFAULT '[]',
+            milestones_json TEXT NOT NULL DEFAULT '[]',
+            deleted INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            schema_version INTEGER NOT NULL
+        );
+        CREATE INDEX idx_homework_due ON homework(due_text);
+        CREATE TABLE homework_tags (
+            uid TEXT NOT NULL REFERENCES homework(uid) ON DELETE CASCADE,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (uid, tag)
+        );
+        CREATE INDEX idx_homework_tags_tag ON homework_tags(tag);",
+    ),
+    (2, "ALTER TABLE homework ADD COLUMN due_zone TEXT NOT NULL DEFAULT '';"),
+    (3, "ALTER TABLE homework ADD COLUMN recurrence TEXT NOT NULL DEFAULT '';"),
+    (
+        5,
+        "ALTER TABLE homework ADD COLUMN external_uid TEXT NOT NULL DEFAULT '';
+        CREATE INDEX idx_homework_external_uid ON homework(external_uid);",
+    ),
+    (6, "ALTER TABLE homework ADD COLUMN deleted_at INTEGER;"),
+    (
+        7,
+        "ALTER TABLE homework ADD COLUMN status TEXT NOT NULL DEFAULT 'open';
+        ALTER TABLE homework ADD COLUMN completed_at INTEGER;
+        ALTER TABLE homework ADD COLUMN cancelled_at INTEGER;
+        CREATE INDEX idx_homework_status ON homework(status);",
+    ),
+    (8, "ALTER TABLE homework ADD COLUMN reminders_json TEXT;"),
+];
+
+const COLUMNS: &str = "uid, name, due_text, due_zone, difficulty, progress, tags_json, milestones_json, \
+                       recurrence, deleted, created_at, updated_at, schema_version, external_uid, deleted_at, \
+                       status, completed_at, cancelled_at, reminders_json";
+
+pub struct SqliteRepo {
+    conn: Mutex<Connection>,
+    changes: ChangeBus,
+}
+
+fn sql_err(e: rusqlite::Error) -> RepoError {
+    RepoError::Unavailable(e.to_string())
+}
+
+fn serde_err(e: serde_json::Error) -> RepoError {
+    RepoError::Serde(e.to_string())
+}
+
+fn row_to_record(row: &Row) -> rusqlite::Result<HomeworkRecord> {
+    let tags_json: String = row.get(6)?;
+    let milestones_json: String = row.get(7)?;
+    let status: String = row.get(15)?;
+    let reminders_json: Option<String> = row.get(18)?;
+    let to_sql_err = |e: serde_json::Error| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+    };
+    Ok(HomeworkRecord {
+        uid: row.get(0)?,
+        name: row.get(1)?,
+        due_text: row.get(2)?,
+        due_zone: row.get(3)?,
+        difficulty: row.get(4)?,
+        progress: row.get(5)?,
+        tags: serde_json::from_str(&tags_json).map_err(to_sql_err)?,
+        milestones: serde_json::from_str(&milestones_json).map_err(to_sql_err)?,
+        recurrence: row.get(8)?,
+        deleted: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+        schema_version: row.get(12)?,
+        external_uid: row.get(13)?,
+        deleted_at: row.get(14)?,
+        // Unknown values (from a newer build) read as open rather than failing the whole query.
+        status: Status::parse(&status).unwrap_or_default(),
+        completed_at: row.get(16)?,
+        cancelled_at: row.get(17)?,
+        reminders: reminders_json.as_deref().map(serde_json::from_str).transpose().map_err(to_sql_err)?,
+    })
+}
+
+/// Insert or update a full record, keeping the tag index in sync.
+/// An upsert rather than INSERT OR REPLACE, so the row keeps its rowid and `list` order.
+fn write_record(tx: &Transaction, rec: &HomeworkRecord) -> RepoResult<()> {
+    let tags_json = serde_json::to_string(&rec.tags).map_err(serde_err)?;
+    let milestones_json = serde_json::to_string(&rec.milestones).map_err(serde_err)?;
+    // NULL = the global default reminders
+    let reminders_json = rec.reminders.as_ref().map(serde_json::to_string).transpose().map_err(serde_err)?;
+    let assignments = COLUMNS
+        .split(',')
+        .map(str::trim)
+        .filter(|c| *c != "uid")
+        .map(|c| format!("{c} = excluded.{c}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    tx.execute(
+        &format!(
+            "INSERT INTO homework ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19) \
+             ON CONFLICT(uid) DO UPDATE SET {assignments}"
+        ),
+        params![
+            rec.uid,
+            rec.name,
+            rec.due_text,
+            rec.due_zone,
+            rec.difficulty,
+            rec.progress,
+            tags_json,
+            milestones_json,
+            rec.recurrence,
+            rec.deleted,
+            rec.created_at,
+            rec.updated_at,
+            rec.schema_version,
+            rec.external_uid,
+            rec.deleted_at,
+            rec.status.as_str(),
+            rec.completed_at,
+            rec.cancelled_at,
+            reminders_json,
+        ],
+    )
+    .map_err(sql_err)?;
+    tx.execute("DELETE FROM homework_tags WHERE uid = ?1", params![rec.uid])
+        .map_err(sql_err)?;
+    for tag in &rec.tags {
+        tx.execute(
+            "INSERT OR IGNORE INTO homework_tags (uid, tag) VALUES (?1, ?2)",
+            params![rec.uid, tag],
+        )
+        .map_err(sql_err)?;
+    }
+    Ok(())
+}
+
+fn read_record(conn: &Connection, uid: &str) -> RepoResult<Option<HomeworkRecord>> {
+    conn.query_row(
+        &format!("SELECT {COLUMNS} FROM homework WHERE uid = ?1"),
+        params![uid],
+        row_to_record,
+    )
+    .optional()
+    .map_err(sql_err)
+}
+
+impl SqliteRepo {
+    pub fn new(dir: PathBuf) -> anyhow::Result<Self> {
+        if !dir.exists() {
+            fs::create_dir_all(&dir)?;
+        }
+        let conn = Connection::open(dir.join(DB_FILE_NAME))?;
+        Self::from_connection(conn)
+    }
+
+    /// Open a private in-memory database.
+    #[cfg(test)]
+    pub(crate) fn in_memory() -> anyhow::Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(mut conn: Connection) -> anyhow::Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        register_functions(&conn)?;
+        Self::migrate(&mut conn)?;
+        Ok(Self { conn: Mutex::new(conn), changes: ChangeBus::default() })
+    }
+
+    fn migrate(conn: &mut Connection) -> RepoResult<()> {
+        let current: u16 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .map_err(sql_err)?;
+        if current >= CURRENT_SCHEMA_VERSION {
+            return Ok(());
+        }
+        let tx = conn.transaction().map_err(sql_err)?;
+        for (version, sql) in MIGRATIONS.iter().filter(|(v, _)| *v > current) {
+            tx.execute_batch(sql).map_err(sql_err)?;
+            tx.pragma_update(None, "user_version", version).map_err(sql_err)?;
+        }
+        tx.pragma_update(None, "user_version", CURRENT_SCHEMA_VERSION).map_err(sql_err)?;
+        // Upgrade row contents written under older schemas (e.g. pin their time zone).
+        let stale: Vec<HomeworkRecord> = {
+            let mut stmt = tx
+                .prepare(&format!("SELECT {COLUMNS} FROM homework WHERE schema_version < ?1"))
+                .map_err(sql_err)?;
+            let rows = stmt
+                .query_map(params![CURRENT_SCHEMA_VERSION], row_to_record)
+                .map_err(sql_err)?;
+            rows.collect::<rusqlite::Result<_>>().map_err(sql_err)?
+        };
+        for mut rec in stale {
+            rec.migrate();
+            write_record(&tx, &rec)?;
+        }
+        tx.commit().map_err(sql_err)
+    }
+
+    fn select(&self, sql: &str, params: impl rusqlite::Params) -> RepoResult<Vec<HomeworkRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(sql).map_err(sql_err)?;
+        let rows = stmt.query_map(params, row_to_record).map_err(sql_err)?;
+        rows.collect::<rusqlite::Result<_>>().map_err(sql_err)
+    }
+
+    /// One-shot import of an existing deadlines.json, keeping uids and timestamps.
+    /// Records whose uid already exists are skipped. Returns the number imported.
+    pub fn import_json(&self, path: &Path) -> RepoResult<usize> {
+        let file = File::open(path).map_err(|e| RepoError::Unavailable(e.to_string()))?;
+        let records: Vec<HomeworkRecord> =
+            serde_json::from_reader(BufReader::new(file)).map_err(serde_err)?;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(sql_err)?;
+        let mut imported = 0;
+        for mut rec in records {
+            if read_record(&tx, &rec.uid)?.is_some() {
+                continue;
+            }
+            rec.migrate();
+            rec.sync_progress_from_milestones();
+            write_record(&tx, &rec)?;
+            imported += 1;
+        }
+        tx.commit().map_err(sql_err)?;
+        Ok(imported)
+    }
+}
+
+/// SQL functions backed by the same Rust code the other backends use, so queries agree:
+/// `due_utc(due_text, due_zone)` is `types::due_utc_text`, and `unicode_lower(text)` folds
+/// case beyond ASCII like `str::to_lowercase` (SQLite's own `lower` and `LIKE` do not).
+fn register_functions(conn: &Connection) -> rusqlite::Result<()> {
+    conn.create_scalar_function("due_utc", 2, FunctionFlags::SQLITE_UTF8, |ctx| {
+        Ok(due_utc_text(&ctx.get::<String>(0)?, &ctx.get::<String>(1)?))
+    })?;
+    conn.create_scalar_function(
+        "unicode_lower",
+        1,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| Ok(ctx.get::<String>(0)?.to_lowercase()),
+    )
+}
+
+/// Translate a `Query` into a WHERE / ORDER BY / LIMIT clause over `homework` plus its parameters.
+fn query_sql(q: &Query) -> (String, Vec<Value>) {
+    let mut clauses: Vec<String> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+    let bind = |v: Value, values: &mut Vec<Value>| {
+        values.push(v);
+        format!("?{}", values.len())
+    };
+
+    if !q.include_deleted {
+        clauses.push("deleted = 0".to_string());
+    }
+    for tag in &q.tags {
+        let p = bind(Value::Text(tag.clone()), &mut values);
+        clauses.push(format!(
+            "EXISTS (SELECT 1 FROM homework_tags t WHERE t.uid = homework.uid AND t.tag = {p} COLLATE NOCASE)"
+        ));
+    }
+    if let Some(from) = &q.due_from {
+        clauses.push(format!("due_utc(due_text, due_zone) >= {}", bind(Value::Text(from.clone()), &mut values)));
+    }
+    if let Some(to) = &q.due_to {
+        clauses.push(format!("due_utc(due_text, due_zone) <= {}", bind(Value::Text(to.clone()), &mut values)));
+    }
+    for (column, range) in [("progress", &q.progress), ("difficulty", &q.difficulty)] {
+        if let Some(r) = range {
+            let lo = bind(Value::Integer(i64::from(*r.start())), &mut values);
+            let hi = bind(Value::Integer(i64::from(*r.end())), &mut values);
+            clauses.push(format!("{column} BETWEEN {lo} AND {hi}"));
+        }
+    }
+    if !q.status.is_empty() {
+        let placeholders: Vec<String> = q
+            .status
+            .iter()
+            .map(|s| bind(Value::Text(s.as_str().to_string()), &mut values))
+            .collect();
+        clauses.push(format!("status IN ({})", placeholders.join(", ")));
+    }
+    match q.archive {
+        ArchiveFilter::Include => {}
+        ArchiveFilter::Exclude { cutoff } | ArchiveFilter::Only { cutoff } => {
+            let p = bind(Value::Integer(cutoff), &mut values);
+            let archived = format!("(status = 'completed' AND completed_at IS NOT NULL AND completed_at <= {p})");
+            clauses.push(if matches!(q.archive, ArchiveFilter::Only { .. }) { archived } else { format!("NOT {archived}") });
+        }
+    }
+    if let Some(needle) = q.text_needle() {
+        let p = bind(Value::Text(needle), &mut values);
+        clauses.push(format!(
+            "(instr(unicode_lower(name), {p}) > 0 OR EXISTS (SELECT 1 FROM homework_tags t \
+             WHERE t.uid = homework.uid AND instr(unicode_lower(t.tag), {p}) > 0))"
+        ));
+    }
+
+    let mut sql = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", clauses.join(" AND "))
+    };
+    let mut order: Vec<String> = q
+        .sort
+        .iter()
+        .map(|key| {
+            let column = match key.field {
+                SortField::Due => "due_utc(due_text, due_zone)",
+                SortField::Name => "unicode_lower(name)",
+                SortField::Progress => "progress",
+                SortField::Difficulty => "difficulty",
+                SortField::Created => "created_at",
+                SortField::Updated => "updated_at",
+            };
+            format!("{column} {}", if key.descending { "DESC" } else { "ASC" })
+        })
+        .collect();
+    order.push("rowid".to_string());
+    sql.push_str(&format!(" ORDER BY {}", order.join(", ")));
+    // SQLite needs a LIMIT before OFFSET; -1 means unlimited.
+    let limit = q.limit.map(|l| l as i64).unwrap_or(-1);
+    sql.push_str(&format!(" LIMIT {} OFFSET {}", limit, q.offset));
+    (sql, values)
+}
+
+impl HomeworkRepo for SqliteRepo {
+    fn list(&self) -> RepoResult<Vec<HomeworkRecord>> {
+        self.select(
+            &format!("SELECT {COLUMNS} FROM homework WHERE deleted = 0 ORDER BY rowid"),
+            [],
+        )
+    }
+
+    fn get(&self, uid: &str) -> RepoResult<Option<HomeworkRecord>> {
+        let conn = self.conn.lock().unwrap();
+        read_record(&conn, uid)
+    }
+
+    fn create(&self, payload: NewHomework) -> RepoResult<HomeworkRecord> {
+        let mut conn = self.conn.lock().unwrap();
+        let now = Utc::now().timestamp();
+        let mut rec = HomeworkRecord {
+            uid: Ulid::new().to_string(),
+            name: payload.name,
+            due_text: payload.due_text,
+            due_zone: payload.due_zone,
+            difficulty: payload.difficulty,
+            progress: payload.progress,
+            tags: payload.tags,
+            milestones: payload.milestones,
+            recurrence: payload.recurrence,
+            external_uid: payload.external_uid,
+            deleted: false,
+            deleted_at: None,
+            status: Status::Open,
+            completed_at: None,
+            cancelled_at: None,
+            reminders: payload.reminders,
+            created_at: now,
+            updated_at: now,
+            schema_version: CURRENT_SCHEMA_VERSION,
+        };
+        rec.sync_progress_from_milestones();
+        rec.set_status(payload.status, now)?;
+        let tx = conn.transaction().map_err(sql_err)?;
+        write_record(&tx, &rec)?;
+        tx.commit().map_err(sql_err)?;
+        self.changes.emit(RepoEvent::Created(rec.clone()));
+        Ok(rec)
+    }
+
+    fn update(&self, mut record: HomeworkRecord) -> RepoResult<HomeworkRecord> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(sql_err)?;
+        if read_record(&tx, &record.uid)?.is_none() {
+            return Err(RepoError::NotFound);
+        }
+        record.sync_progress_from_milestones();
+        record.updated_at = Utc::now().timestamp();
+        write_record(&tx, &record)?;
+        tx.commit().map_err(sql_err)?;
+        self.changes.emit(RepoEvent::Updated(record.clone()));
+        Ok(record)
+    }
+
+    fn patch(&self, uid: &str, patch: Patch) -> RepoResult<HomeworkRecord> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(sql_err)?;
+        let mut current = read_record(&tx, uid)?.ok_or(RepoError::NotFound)?;
+        current.apply_patch(patch, Utc::now().timestamp())?;
+        write_record(&tx, &current)?;
+        tx.commit().map_err(sql_err)?;
+        self.changes.emit(RepoEvent::Updated(current.clone()));
+        Ok(current)
+    }
+
+    fn delete(&self, uid: &str) -> RepoResult<()> {
+        let conn = self.conn.lock().unwrap();
+        let changed = conn
+            .execute(
+                "UPDATE homework SET deleted = 1, deleted_at = COALESCE(deleted_at, ?2), updated_at = ?2 \
+                 WHERE uid = ?1",
+                params![uid, Utc::now().timestamp()],
+            )
+            .map_err(sql_err)?;
+        if changed == 0 {
+            return Err(RepoError::NotFound);
+        }
+        if let Some(rec) = read_record(&conn, uid)? {
+            self.changes.emit(RepoEvent::Deleted(rec));
+        }
+        Ok(())
+    }
+
+    fn query(&self, q: &Query) -> RepoResult<Vec<HomeworkRecord>> {
+        let (clause, values) = query_sql(q);
+        self.select(&format!("SELECT {COLUMNS} FROM homework{clause}"), params_from_iter(values))
+    }
+
+    fn list_deleted(&self) -> RepoResult<Vec<HomeworkRecord>> {
+        self.select(
+            &format!(
+                "SELECT {COLUMNS} FROM homework WHERE deleted = 1 \
+                 ORDER BY COALESCE(deleted_at, updated_at) DESC"
+            ),
+            [],
+        )
+    }
+
+    fn purge(&self, uid: &str) -> RepoResult<()> {
+        let conn = self.conn.lock().unwrap();
+        // homework_tags rows go with it through ON DELETE CASCADE.
+        let changed = conn
+            .execute("DELETE FROM homework WHERE uid = ?1", params![uid])
+            .map_err(sql_err)?;
+        if changed == 0 {
+            return Err(RepoError::NotFound);
+        }
+        self.changes.emit(RepoEvent::Purged(uid.to_string()));
+        Ok(())
+    }
+
+    fn purge_deleted_before(&self, cutoff_ts: i64) -> RepoResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "DELETE FROM homework WHERE deleted = 1 AND COALESCE(deleted_at, updated_at) < ?1 \
+                 RETURNING uid",
+            )
+            .map_err(sql_err)?;
+        let purged: Vec<String> = stmt
+            .query_map(params![cutoff_ts], |row| row.get(0))
+            .map_err(sql_err)?
+            .collect::<rusqlite::Result<_>>()
+            .map_err(sql_err)?;
+        for uid in &purged {
+            self.changes.emit(RepoEvent::Purged(uid.clone()));
+        }
+        Ok(purged.len())
+    }
+
+    fn changes(&self) -> &ChangeBus {
+        &self.changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::timezone::DueZone;
+    use crate::persistence::conformance::{new_homework, TempDir};
+    use crate::persistence::json::{JsonRepo, DATA_FILE_NAME};
+    use crate::persistence::repo::{init_repo, Backend};
+
+    fn tag_rows(repo: &SqliteRepo) -> Vec<(String, String)> {
+        let conn = repo.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT uid, tag FROM homework_tags ORDER BY tag").unwrap();
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+        rows.collect::<rusqlite::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn version_1_database_is_migrated_to_current() {
+        let dir = TempDir::new();
+        {
+            let conn = Connection::open(dir.0.join(DB_FILE_NAME)).unwrap();
+            conn.execute_batch(MIGRATIONS[0].1).unwrap();
+            conn.pragma_update(None, "user_version", 1).unwrap();
+            conn.execute(
+                "INSERT INTO homework (uid, name, due_text, difficulty, progress, tags_json, deleted, \
+                 created_at, updated_at, schema_version) \
+                 VALUES ('old', 'Essay', '2025-11-28 23:59', 5, 100, '[\"course\"]', 0, 10, 20, 1)",
+                [],
+            )
+            .unwrap();
+        }
+
+        let repo = SqliteRepo::new(dir.0.clone()).unwrap();
+        let rec = repo.get("old").unwrap().unwrap();
+        assert_eq!(rec.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(rec.due_zone, DueZone::local().to_string());
+        // Full progress used to mean done.
+        assert_eq!((rec.status, rec.completed_at), (Status::Completed, Some(20)));
+        assert_eq!(rec.reminders, None);
+        let version: u16 = repo.conn.lock().unwrap().pragma_query_value(None, "user_version", |r| r.get(0)).unwrap();
+        assert_eq!(version, CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn first_start_imports_json_with_its_tags() {
+        let dir = TempDir::new();
+        let json = JsonRepo::new(dir.0.clone()).unwrap();
+        let essay = json.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+        let mut lab = new_homework("Lab", "2025-12-01 09:00", "UTC");
+        lab.tags = vec!["os".to_string(), "course".to_string()];
+        let lab = json.create(lab).unwrap();
+        drop(json);
+
+        init_repo(Some(dir.0.clone()), Backend::Sqlite).unwrap();
+        let repo = SqliteRepo::new(dir.0.clone()).unwrap();
+        assert_eq!(repo.get(&essay.uid).unwrap(), Some(essay.clone()));
+        assert_eq!(repo.get(&lab.uid).unwrap(), Some(lab.clone()));
+        assert_eq!(
+            tag_rows(&repo),
+            vec![
+                (essay.uid.clone(), "course".to_string()),
+                (lab.uid.clone(), "course".to_string()),
+                (lab.uid.clone(), "os".to_string()),
+            ]
+        );
+        // Importing again skips what is already there.
+        assert_eq!(repo.import_json(&dir.0.join(DATA_FILE_NAME)).unwrap(), 0);
+    }
+
+    #[test]
+    fn failed_import_is_retried_on_the_next_start() {
+        let dir = TempDir::new();
+        let json_path = dir.0.join(DATA_FILE_NAME);
+        fs::write(&json_path, "[{ not json").unwrap();
+        assert!(init_repo(Some(dir.0.clone()), Backend::Sqlite).is_err());
+        assert!(!dir.0.join(DB_FILE_NAME).exists());
+
+        fs::remove_file(&json_path).unwrap();
+        let json = JsonRepo::new(dir.0.clone()).unwrap();
+        let essay = json.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+        drop(json);
+
+        let repo = init_repo(Some(dir.0.clone()), Backend::Sqlite).unwrap();
+        assert_eq!(repo.list().unwrap(), vec![essay]);
+    }
+}
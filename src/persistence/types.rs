@@ -1,5 +1,96 @@
 use serde::{Deserialize, Serialize};
 
+use crate::model::datetime::Datetime;
+use crate::model::milestone::{progress_from_milestones, Milestone};
+use crate::model::recurrence::Recurrence;
+use crate::model::reminder::Lead;
+use crate::model::status::Status;
+use crate::model::timezone::DueZone;
+use crate::model::Deadline;
+use crate::persistence::repo::{RepoError, RepoResult};
+
+/// Schema version written by this build. Older records are upgraded by `HomeworkRecord::migrate`.
+/// - 1: naive `due_text` interpreted in the viewer's local zone
+/// - 2: adds `due_zone`
+/// - 3: adds `recurrence`
+/// - 4: milestones become `MilestoneRecord` objects instead of `(u8, String)` pairs
+/// - 5: adds `external_uid`
+/// - 6: adds `deleted_at`
+/// - 7: adds `status`, `completed_at` and `cancelled_at`
+/// - 8: adds `reminders`
+pub const CURRENT_SCHEMA_VERSION: u16 = 8;
+
+/// `due_text` in `due_zone` converted to UTC, in the same "YYYY-MM-DD HH:MM" form, so due
+/// dates in different zones compare correctly as text. Unparseable dates are returned as stored.
+pub fn due_utc_text(due_text: &str, due_zone: &str) -> String {
+    let Some(due) = Datetime::from_string(due_text) else { return due_text.to_string() };
+    let zone = DueZone::parse(due_zone).unwrap_or(DueZone::Local);
+    due.convert(&zone, &DueZone::Offset(0)).to_string()
+}
+
+/// Persisted checklist item of a homework record.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(from = "MilestoneRepr")]
+pub struct MilestoneRecord {
+    pub title: String,
+    pub weight: u8,
+    pub done: bool,
+    /// Own due date "YYYY-MM-DD HH:MM" in the record's `due_zone`; empty = none
+    #[serde(default)]
+    pub due_text: String,
+}
+
+/// Accepts both the current object form and the schema-v1..3 `[weight, title]` pair.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MilestoneRepr {
+    Current {
+        title: String,
+        weight: u8,
+        #[serde(default)]
+        done: bool,
+        #[serde(default)]
+        due_text: String,
+    },
+    Legacy((u8, String)),
+}
+
+impl From<MilestoneRepr> for MilestoneRecord {
+    fn from(repr: MilestoneRepr) -> Self {
+        match repr {
+            MilestoneRepr::Current { title, weight, done, due_text } => Self { title, weight, done, due_text },
+            MilestoneRepr::Legacy((weight, title)) => Self {
+                title,
+                weight: weight.max(1),
+                done: false,
+                due_text: String::new(),
+            },
+        }
+    }
+}
+
+impl From<&Milestone> for MilestoneRecord {
+    fn from(m: &Milestone) -> Self {
+        Self {
+            title: m.title.clone(),
+            weight: m.weight.max(1),
+            done: m.done,
+            due_text: m.due.map(|d| d.to_string()).unwrap_or_default(),
+        }
+    }
+}
+
+impl MilestoneRecord {
+    pub fn to_milestone(&self) -> Milestone {
+        Milestone {
+            title: self.title.clone(),
+            weight: self.weight.max(1),
+            done: self.done,
+            due: Datetime::from_string(&self.due_text),
+        }
+    }
+}
+
 /// Minimal persisted record for a homework/deadline item.
 /// Notes:
 /// - `due_text`: formatted as "YYYY-MM-DD HH:MM" to align with existing Datetime.to_string()
@@ -10,11 +101,34 @@ pub struct HomeworkRecord {
     pub name: String,
     /// Due in text form "YYYY-MM-DD HH:MM"
     pub due_text: String,
+    /// Zone `due_text` is expressed in: IANA name, "+HH:MM" offset, "UTC" or "AoE" (see `DueZone`)
+    #[serde(default)]
+    pub due_zone: String,
     pub difficulty: u8,
     pub progress: u8,
     pub tags: Vec<String>,
-    pub milestones: Vec<(u8, String)>,
+    pub milestones: Vec<MilestoneRecord>,
+    /// RRULE-style recurrence, e.g. "FREQ=WEEKLY;BYDAY=MO" (see `Recurrence`); empty = one-off
+    #[serde(default)]
+    pub recurrence: String,
+    /// UID of the calendar item this record was imported from (empty if created here)
+    #[serde(default)]
+    pub external_uid: String,
     pub deleted: bool,
+    /// Unix time the record was moved to the trash; `None` while it is live
+    #[serde(default)]
+    pub deleted_at: Option<i64>,
+    #[serde(default)]
+    pub status: Status,
+    /// Unix time the record was last completed; `None` unless `status` is `Completed`
+    #[serde(default)]
+    pub completed_at: Option<i64>,
+    /// Unix time the record was cancelled; `None` unless `status` is `Cancelled`
+    #[serde(default)]
+    pub cancelled_at: Option<i64>,
+    /// Reminder leads such as ["1d", "2h"]; `None` = the global defaults, empty = no reminders
+    #[serde(default)]
+    pub reminders: Option<Vec<Lead>>,
     pub created_at: i64,
     pub updated_at: i64,
     pub schema_version: u16,
@@ -26,10 +140,20 @@ pub struct NewHomework {
     pub name: String,
     /// "YYYY-MM-DD HH:MM"
     pub due_text: String,
+    #[serde(default)]
+    pub due_zone: String,
     pub difficulty: u8,
     pub progress: u8,
     pub tags: Vec<String>,
-    pub milestones: Vec<(u8, String)>,
+    pub milestones: Vec<MilestoneRecord>,
+    #[serde(default)]
+    pub recurrence: String,
+    #[serde(default)]
+    pub external_uid: String,
+    #[serde(default)]
+    pub status: Status,
+    #[serde(default)]
+    pub reminders: Option<Vec<Lead>>,
 }
 
 /// Partial update. `None` means unchanged.
@@ -37,22 +161,183 @@ pub struct NewHomework {
 pub struct Patch {
     pub name: Option<String>,
     pub due_text: Option<String>,
+    pub due_zone: Option<String>,
     pub difficulty: Option<u8>,
     pub progress: Option<u8>,
     pub tags: Option<Vec<String>>,
-    pub milestones: Option<Vec<(u8, String)>>,
+    pub milestones: Option<Vec<MilestoneRecord>>,
+    pub recurrence: Option<String>,
+    pub status: Option<Status>,
+    /// `Some(None)` goes back to the global default reminders
+    pub reminders: Option<Option<Vec<Lead>>>,
     pub deleted: Option<bool>,
 }
 
 impl HomeworkRecord {
-    pub fn apply_patch(&mut self, patch: Patch, now_ts: i64) {
+    /// Apply `patch`, leaving the record untouched if its status change is not allowed.
+    pub fn apply_patch(&mut self, patch: Patch, now_ts: i64) -> RepoResult<()> {
+        if let Some(v) = patch.status { self.check_status(v)?; }
         if let Some(v) = patch.name { self.name = v; }
         if let Some(v) = patch.due_text { self.due_text = v; }
+        if let Some(v) = patch.due_zone { self.due_zone = v; }
         if let Some(v) = patch.difficulty { self.difficulty = v; }
         if let Some(v) = patch.progress { self.progress = v; }
         if let Some(v) = patch.tags { self.tags = v; }
         if let Some(v) = patch.milestones { self.milestones = v; }
-        if let Some(v) = patch.deleted { self.deleted = v; }
+        if let Some(v) = patch.recurrence { self.recurrence = v; }
+        if let Some(v) = patch.reminders { self.reminders = v; }
+        if let Some(v) = patch.status { self.set_status(v, now_ts)?; }
+        if let Some(v) = patch.deleted { self.set_deleted(v, now_ts); }
+        self.sync_progress_from_milestones();
         self.updated_at = now_ts;
+        Ok(())
+    }
+
+    fn check_status(&self, next: Status) -> RepoResult<()> {
+        if self.status.can_become(next) {
+            Ok(())
+        } else {
+            Err(RepoError::Invalid(format!(
+                "cannot change status from {} to {}",
+                self.status.as_str(),
+                next.as_str()
+            )))
+        }
+    }
+
+    /// The due time in UTC; see [`due_utc_text`].
+    pub fn due_utc_text(&self) -> String {
+        due_utc_text(&self.due_text, &self.due_zone)
+    }
+
+    /// Re-derive `progress` from completed milestone weights, if there are milestones, so
+    /// sorting and filtering in the repo see the same value as the app.
+    pub fn sync_progress_from_milestones(&mut self) {
+        let milestones: Vec<Milestone> = self.milestones.iter().map(MilestoneRecord::to_milestone).collect();
+        if let Some(p) = progress_from_milestones(&milestones) {
+            self.progress = p;
+        }
+    }
+
+    /// Move the record to `next`, keeping `completed_at` / `cancelled_at` in step.
+    /// Fails if the current status cannot become `next`.
+    pub fn set_status(&mut self, next: Status, now_ts: i64) -> RepoResult<()> {
+        self.check_status(next)?;
+        if next != self.status {
+            self.completed_at = (next == Status::Completed).then_some(now_ts);
+            self.cancelled_at = (next == Status::Cancelled).then_some(now_ts);
+            self.status = next;
+        }
+        Ok(())
+    }
+
+    /// Move the record into (or out of) the trash, keeping `deleted_at` in step.
+    pub fn set_deleted(&mut self, deleted: bool, now_ts: i64) {
+        if deleted && !self.deleted {
+            self.deleted_at = Some(now_ts);
+        } else if !deleted {
+            self.deleted_at = None;
+        }
+        self.deleted = deleted;
+    }
+
+    /// When the record went to the trash; records deleted before schema v6 use `updated_at`.
+    pub fn deleted_time(&self) -> Option<i64> {
+        self.deleted.then(|| self.deleted_at.unwrap_or(self.updated_at))
+    }
+
+    /// Completed at or before `cutoff_ts`, so shown in the archive rather than the main list.
+    pub fn is_archived(&self, cutoff_ts: i64) -> bool {
+        self.status == Status::Completed && self.completed_at.is_some_and(|t| t <= cutoff_ts)
+    }
+
+    /// Upgrade a record written by an older schema in place. Returns true if anything changed.
+    pub fn migrate(&mut self) -> bool {
+        if self.schema_version >= CURRENT_SCHEMA_VERSION {
+            return false;
+        }
+        if self.schema_version < 2 && self.due_zone.is_empty() {
+            // v1 due dates meant "local time wherever the app runs"; pin them to the
+            // zone of the machine doing the migration so they stop drifting.
+            self.due_zone = DueZone::local().to_string();
+        }
+        if self.schema_version < 6 && self.deleted && self.deleted_at.is_none() {
+            self.deleted_at = Some(self.updated_at);
+        }
+        if self.schema_version < 7 && self.progress >= 100 && self.status == Status::Open {
+            // Completion used to be implied by full progress.
+            self.status = Status::Completed;
+            self.completed_at = Some(self.updated_at);
+        }
+        self.schema_version = CURRENT_SCHEMA_VERSION;
+        true
+    }
+
+    /// Build the in-memory model. Unparseable due dates fall back to now; urgency is left at 0.
+    pub fn to_deadline(&self) -> Deadline {
+        let mut d = Deadline {
+            id: self.uid.clone(),
+            name: self.name.clone(),
+            due_date: Datetime::from_string(&self.due_text).unwrap_or_else(Datetime::now),
+            due_zone: DueZone::parse(&self.due_zone).unwrap_or(DueZone::Local),
+            difficulty: self.difficulty,
+            progress: self.progress,
+            milestones: self.milestones.iter().map(MilestoneRecord::to_milestone).collect(),
+            urgency: 0.0,
+            tags: self.tags.clone(),
+            recurrence: Recurrence::parse(&self.recurrence),
+            status: self.status,
+            reminders: self.reminders.clone(),
+            occurrence: 0,
+        };
+        d.sync_progress_from_milestones();
+        d
+    }
+
+    /// Copy the user-editable fields of `d` onto this record. Timestamps are left to the repo,
+    /// apart from the status ones, which are stamped with `now_ts` when the status changes.
+    pub fn apply_deadline(&mut self, d: &Deadline, now_ts: i64) -> RepoResult<()> {
+        self.check_status(d.status)?;
+        self.name = d.name.clone();
+        self.due_text = d.due_date.to_string();
+        self.due_zone = d.due_zone.to_string();
+        self.difficulty = d.difficulty;
+        self.progress = d.progress;
+        self.tags = d.tags.clone();
+        self.milestones = d.milestones.iter().map(MilestoneRecord::from).collect();
+        self.recurrence = d.recurrence.as_ref().map(Recurrence::to_rrule).unwrap_or_default();
+        self.reminders = d.reminders.clone();
+        self.set_status(d.status, now_ts)
+    }
+}
+
+impl From<&Deadline> for NewHomework {
+    fn from(d: &Deadline) -> Self {
+        Self {
+            name: d.name.clone(),
+            due_text: d.due_date.to_string(),
+            due_zone: d.due_zone.to_string(),
+            difficulty: d.difficulty,
+            progress: d.progress,
+            tags: d.tags.clone(),
+            milestones: d.milestones.iter().map(MilestoneRecord::from).collect(),
+            recurrence: d.recurrence.as_ref().map(Recurrence::to_rrule).unwrap_or_default(),
+            external_uid: String::new(),
+            status: d.status,
+            reminders: d.reminders.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_weight_title_pairs_still_load() {
+        let json = r#"[[3, "Outline"], [0, "Draft"], {"title": "Proofread", "weight": 2, "done": true}]"#;
+        let milestones: Vec<MilestoneRecord> = serde_json::from_str(json).unwrap();
+        let summary: Vec<_> = milestones.iter().map(|m| (m.title.as_str(), m.weight, m.done, m.due_text.as_str())).collect();
+        assert_eq!(summary, vec![("Outline", 3, false, ""), ("Draft", 1, false, ""), ("Proofread", 2, true, "")]);
     }
 }
@@ -0,0 +1,320 @@
+//! Named workspaces: separate repositories for separate parts of life, each in its own folder.
+//! `MergedRepo` presents several of them as one for the "All workspaces" view.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::persistence::events::{ChangeBus, RepoEvent, SubscriptionId};
+use crate::persistence::query::Query;
+use crate::persistence::repo::{init_repo, Backend, HomeworkRepo, RepoError, RepoResult};
+use crate::persistence::types::{HomeworkRecord, NewHomework, Patch};
+
+pub const DEFAULT_WORKSPACE: &str = "Default";
+const WORKSPACES_DIR: &str = "workspaces";
+
+/// The default workspace keeps its data directly in the data directory, where earlier
+/// versions kept it; the others live under `workspaces/<name>`.
+pub fn workspace_dir(data_dir: &Path, name: &str) -> PathBuf {
+    if name == DEFAULT_WORKSPACE {
+        data_dir.to_path_buf()
+    } else {
+        data_dir.join(WORKSPACES_DIR).join(name)
+    }
+}
+
+/// Names of existing workspaces, the default first and the rest alphabetically.
+pub fn list_workspaces(data_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(data_dir.join(WORKSPACES_DIR))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| validate_name(name).is_ok())
+        .collect();
+    names.sort_by_key(|n| n.to_lowercase());
+    names.insert(0, DEFAULT_WORKSPACE.to_string());
+    names
+}
+
+/// Check a workspace name, returning it trimmed. Names become folder names, so only
+/// letters, digits, spaces, `-` and `_` are allowed.
+pub fn validate_name(name: &str) -> RepoResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(RepoError::Invalid("workspace name is empty".to_string()));
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_')) {
+        return Err(RepoError::Invalid(format!(
+            "workspace name {name:?} may only contain letters, digits, spaces, '-' and '_'"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Create an empty workspace and return its (trimmed) name.
+pub fn create_workspace(data_dir: &Path, name: &str) -> RepoResult<String> {
+    let name = validate_name(name)?;
+    if list_workspaces(data_dir).iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+        return Err(RepoError::Invalid(format!("workspace {name:?} already exists")));
+    }
+    fs::create_dir_all(workspace_dir(data_dir, &name)).map_err(|e| RepoError::Unavailable(e.to_string()))?;
+    Ok(name)
+}
+
+pub fn open_workspace(data_dir: &Path, name: &str, backend: Backend) -> RepoResult<Arc<dyn HomeworkRepo>> {
+    init_repo(Some(workspace_dir(data_dir, &validate_name(name)?)), backend)
+}
+
+/// Every workspace merged, with `primary` first so it receives new records.
+pub fn open_all_workspaces(data_dir: &Path, primary: &str, backend: Backend) -> RepoResult<MergedRepo> {
+    let mut names = list_workspaces(data_dir);
+    if let Some(i) = names.iter().position(|n| n == primary) {
+        let name = names.remove(i);
+        names.insert(0, name);
+    }
+    let members = names
+        .into_iter()
+        .map(|name| open_workspace(data_dir, &name, backend).map(|repo| (name, repo)))
+        .collect::<RepoResult<Vec<_>>>()?;
+    Ok(MergedRepo::new(members))
+}
+
+/// Several workspaces as one repository. Reads combine all of them; changes go to the
+/// workspace holding the record, and new records to the first one.
+pub struct MergedRepo {
+    members: Vec<(String, Arc<dyn HomeworkRepo>)>,
+    // uid -> index into `members`, learned from reads and events
+    owners: Arc<Mutex<HashMap<String, usize>>>,
+    changes: Arc<ChangeBus>,
+    subscriptions: Vec<SubscriptionId>,
+}
+
+impl MergedRepo {
+    pub fn new(members: Vec<(String, Arc<dyn HomeworkRepo>)>) -> Self {
+        assert!(!members.is_empty(), "MergedRepo needs at least one workspace");
+        let owners: Arc<Mutex<HashMap<String, usize>>> = Arc::default();
+        let changes: Arc<ChangeBus> = Arc::default();
+        // Forward each member's events, noting which workspace new records landed in.
+        let subscriptions = members
+            .iter()
+            .enumerate()
+            .map(|(i, (_, repo))| {
+                let owners = owners.clone();
+                let changes = changes.clone();
+                repo.changes().subscribe(Box::new(move |event| {
+                    match event {
+                        RepoEvent::Created(rec) => {
+                            owners.lock().unwrap().insert(rec.uid.clone(), i);
+                        }
+                        RepoEvent::Purged(uid) => {
+                            owners.lock().unwrap().remove(uid);
+                        }
+                        _ => {}
+                    }
+                    changes.emit(event.clone());
+                }))
+            })
+            .collect();
+        Self { members, owners, changes, subscriptions }
+    }
+
+    /// Name of the workspace holding `uid`.
+    pub fn workspace_of(&self, uid: &str) -> Option<&str> {
+        let i = self.owner(uid).ok().flatten()?;
+        Some(&self.members[i].0)
+    }
+
+    fn remember(&self, i: usize, records: &[HomeworkRecord]) {
+        let mut owners = self.owners.lock().unwrap();
+        for rec in records {
+            owners.insert(rec.uid.clone(), i);
+        }
+    }
+
+    fn owner(&self, uid: &str) -> RepoResult<Option<usize>> {
+        if let Some(&i) = self.owners.lock().unwrap().get(uid) {
+            return Ok(Some(i));
+        }
+        for (i, (_, repo)) in self.members.iter().enumerate() {
+            if let Some(rec) = repo.get(uid)? {
+                self.remember(i, std::slice::from_ref(&rec));
+                return Ok(Some(i));
+            }
+        }
+        Ok(None)
+    }
+
+    fn member_for(&self, uid: &str) -> RepoResult<&Arc<dyn HomeworkRepo>> {
+        let i = self.owner(uid)?.ok_or(RepoError::NotFound)?;
+        Ok(&self.members[i].1)
+    }
+
+    /// Run `read` on every member and concatenate the results in workspace order.
+    fn gather(&self, read: impl Fn(&dyn HomeworkRepo) -> RepoResult<Vec<HomeworkRecord>>) -> RepoResult<Vec<HomeworkRecord>> {
+        let mut all = Vec::new();
+        for (i, (_, repo)) in self.members.iter().enumerate() {
+            let records = read(repo.as_ref())?;
+            self.remember(i, &records);
+            all.extend(records);
+        }
+        Ok(all)
+    }
+}
+
+impl Drop for MergedRepo {
+    fn drop(&mut self) {
+        for ((_, repo), id) in self.members.iter().zip(&self.subscriptions) {
+            repo.changes().unsubscribe(*id);
+        }
+    }
+}
+
+impl HomeworkRepo for MergedRepo {
+    fn list(&self) -> RepoResult<Vec<HomeworkRecord>> {
+        self.gather(|repo| repo.list())
+    }
+
+    fn get(&self, uid: &str) -> RepoResult<Option<HomeworkRecord>> {
+        match self.owner(uid)? {
+            Some(i) => self.members[i].1.get(uid),
+            None => Ok(None),
+        }
+    }
+
+    fn create(&self, payload: NewHomework) -> RepoResult<HomeworkRecord> {
+        self.members[0].1.create(payload)
+    }
+
+    fn update(&self, record: HomeworkRecord) -> RepoResult<HomeworkRecord> {
+        self.member_for(&record.uid)?.update(record)
+    }
+
+    fn patch(&self, uid: &str, patch: Patch) -> RepoResult<HomeworkRecord> {
+        self.member_for(uid)?.patch(uid, patch)
+    }
+
+    fn delete(&self, uid: &str) -> RepoResult<()> {
+        self.member_for(uid)?.delete(uid)
+    }
+
+    // Each workspace filters; sorting and paging have to see all of them at once.
+    fn query(&self, query: &Query) -> RepoResult<Vec<HomeworkRecord>> {
+        let unpaged = Query { limit: None, offset: 0, ..query.clone() };
+        Ok(query.apply(self.gather(|repo| repo.query(&unpaged))?))
+    }
+
+    fn list_deleted(&self) -> RepoResult<Vec<HomeworkRecord>> {
+        let mut records = self.gather(|repo| repo.list_deleted())?;
+        records.sort_by_key(|r| std::cmp::Reverse(r.deleted_time()));
+        Ok(records)
+    }
+
+    fn purge(&self, uid: &str) -> RepoResult<()> {
+        self.member_for(uid)?.purge(uid)
+    }
+
+    fn purge_deleted_before(&self, cutoff_ts: i64) -> RepoResult<usize> {
+        let mut purged = 0;
+        for (_, repo) in &self.members {
+            purged += repo.purge_deleted_before(cutoff_ts)?;
+        }
+        Ok(purged)
+    }
+
+    fn changes(&self) -> &ChangeBus {
+        &self.changes
+    }
+
+    fn poll_external_changes(&self) -> RepoResult<bool> {
+        let mut any = false;
+        for (_, repo) in &self.members {
+            any |= repo.poll_external_changes()?;
+        }
+        Ok(any)
+    }
+
+    // Only the workspaces that no longer load are restored; the others keep their data.
+    fn restore_latest_backup(&self) -> RepoResult<()> {
+        let mut restored = false;
+        for (_, repo) in &self.members {
+            if matches!(repo.list(), Err(RepoError::Corrupt(_))) {
+                repo.restore_latest_backup()?;
+                restored = true;
+            }
+        }
+        if !restored {
+            return Err(RepoError::Unavailable("no workspace needs restoring".to_string()));
+        }
+        self.owners.lock().unwrap().clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::conformance::{new_homework, TempDir};
+    use crate::persistence::json::{JsonRepo, DATA_FILE_NAME};
+    use crate::persistence::memory::MemoryRepo;
+
+    #[test]
+    fn workspaces_are_folders_under_the_data_dir() {
+        let dir = TempDir::new();
+        assert_eq!(list_workspaces(&dir.0), vec![DEFAULT_WORKSPACE]);
+        assert_eq!(create_workspace(&dir.0, "  Job hunt ").unwrap(), "Job hunt");
+        create_workspace(&dir.0, "errands").unwrap();
+        assert_eq!(list_workspaces(&dir.0), vec![DEFAULT_WORKSPACE, "errands", "Job hunt"]);
+        assert!(matches!(create_workspace(&dir.0, "job hunt"), Err(RepoError::Invalid(_))));
+        assert!(matches!(create_workspace(&dir.0, "../escape"), Err(RepoError::Invalid(_))));
+        assert!(matches!(create_workspace(&dir.0, " "), Err(RepoError::Invalid(_))));
+        assert_eq!(workspace_dir(&dir.0, DEFAULT_WORKSPACE), dir.0);
+    }
+
+    #[test]
+    fn merged_repo_routes_changes_to_the_owning_workspace() {
+        let courses: Arc<dyn HomeworkRepo> = Arc::new(MemoryRepo::new());
+        let job: Arc<dyn HomeworkRepo> = Arc::new(MemoryRepo::new());
+        let interview = job.create(new_homework("Interview", "2025-11-28 23:59", "UTC")).unwrap();
+        let merged = MergedRepo::new(vec![
+            ("Courses".to_string(), courses.clone()),
+            ("Job".to_string(), job.clone()),
+        ]);
+
+        let essay = merged.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+        assert_eq!(courses.list().unwrap().len(), 1);
+        let names: Vec<_> = merged.list().unwrap().into_iter().map(|r| r.name).collect();
+        assert_eq!(names, vec!["Essay", "Interview"]);
+
+        merged.patch(&interview.uid, Patch { progress: Some(40), ..Default::default() }).unwrap();
+        assert_eq!(job.get(&interview.uid).unwrap().unwrap().progress, 40);
+        assert_eq!(merged.workspace_of(&interview.uid), Some("Job"));
+        assert_eq!(merged.workspace_of(&essay.uid), Some("Courses"));
+        assert_eq!(merged.workspace_of("missing"), None);
+    }
+
+    #[test]
+    fn restore_repairs_only_the_corrupt_workspace() {
+        let dir = TempDir::new();
+        let courses_dir = dir.0.join("courses");
+        let job_dir = dir.0.join("job");
+        let courses: Arc<dyn HomeworkRepo> = Arc::new(JsonRepo::new(courses_dir.clone()).unwrap());
+        let job: Arc<dyn HomeworkRepo> = Arc::new(JsonRepo::new(job_dir.clone()).unwrap());
+        courses.create(new_homework("Essay", "2025-11-28 23:59", "UTC")).unwrap();
+        job.create(new_homework("Interview", "2025-11-28 23:59", "UTC")).unwrap();
+        let merged = MergedRepo::new(vec![("Courses".to_string(), courses), ("Job".to_string(), job)]);
+        assert!(matches!(merged.restore_latest_backup(), Err(RepoError::Unavailable(_))));
+
+        fs::write(job_dir.join(DATA_FILE_NAME), "[{ not json").unwrap();
+        assert!(matches!(merged.list(), Err(RepoError::Corrupt(_))));
+        merged.restore_latest_backup().unwrap();
+
+        // The job workspace is back to its backup from before the interview was added.
+        let names: Vec<_> = merged.list().unwrap().into_iter().map(|r| r.name).collect();
+        assert_eq!(names, vec!["Essay"]);
+        let mut untouched = fs::read_dir(&courses_dir).unwrap().filter_map(|e| e.ok());
+        assert!(untouched.all(|e| !e.file_name().to_string_lossy().contains(".corrupt-")));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::model::agenda::{agenda, local_date, relative_day_label};
+use crate::model::countdown::DueState;
+use crate::model::Deadline;
+use crate::views::Now;
+use dioxus::prelude::*;
+
+/// Choices for how many days ahead the agenda reaches.
+const AGENDA_SPANS: [u32; 3] = [7, 14, 30];
+
+/// Overdue deadlines, then the coming days that have something due, each under a relative
+/// heading ("Today", "Tomorrow", "Friday", "In 9 days").
+#[component]
+pub fn AgendaView(deadlines: Vec<Deadline>, on_edit_deadline: EventHandler<Deadline>) -> Element {
+    let clock = use_context::<Signal<Now>>();
+    let now = clock().0;
+    let today = now.to_naive().map(|n| n.date()).unwrap_or_default();
+    let mut span = use_signal(|| 14u32);
+
+    let (overdue, days) = agenda(&deadlines, today, span());
+
+    // One agenda line: due time, name and what is left.
+    let row = move |d: Deadline, with_date: bool| {
+        let due = d.local_due();
+        let when = if with_date {
+            let day = local_date(&d).unwrap_or(today);
+            format!("{} {:02}:{:02}", relative_day_label(day, today), due.hour, due.minute)
+        } else {
+            format!("{:02}:{:02}", due.hour, due.minute)
+        };
+        let state = DueState::of(&d, &now);
+        let closed = d.status.is_closed();
+        rsx! {
+            div {
+                key: "{d.id}-{due.to_string()}",
+                class: if closed { "agenda-item closed" } else { "agenda-item" },
+                title: d.original_due_text(),
+                onclick: move |_| on_edit_deadline.call(d.clone()),
+                span { class: "agenda-time", "{when}" }
+                span { class: "font-bold truncate", if d.recurrence.is_some() { "↻ {d.name}" } else { "{d.name}" } }
+                if closed {
+                    span { class: "badge badge-green", "{d.status.label()}" }
+                } else {
+                    span {
+                        class: if state.is_overdue() { "badge badge-red" } else { "badge badge-gray" },
+                        {state.label()}
+                    }
+                }
+            }
+        }
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-4",
+
+            div {
+                class: "flex justify-between items-center p-2",
+                h3 { class: "text-lg font-bold text-gray-800", "Agenda" }
+                select {
+                    class: "form-input",
+                    style: "width: auto;",
+                    title: "Days ahead",
+                    onchange: move |e| {
+                        if let Ok(days) = e.value().parse::<u32>() {
+                            span.set(days);
+                        }
+                    },
+                    for days in AGENDA_SPANS {
+                        option { value: "{days}", selected: span() == days, "Next {days} days" }
+                    }
+                }
+            }
+
+            if !overdue.is_empty() {
+                div {
+                    class: "agenda-day",
+                    h4 { class: "agenda-day-header overdue", "Overdue" }
+                    for d in overdue {
+                        {row(d, true)}
+                    }
+                }
+            }
+
+            if days.is_empty() {
+                p { class: "text-gray-500", "Nothing due in the next {span} days." }
+            }
+            for day in days {
+                div {
+                    key: "{day.date}",
+                    class: "agenda-day",
+                    h4 {
+                        class: if day.date == today { "agenda-day-header today" } else { "agenda-day-header" },
+                        "{day.label}"
+                        span { class: "text-sm text-gray-500", " · {day.date.format(\"%a, %b %-d\")}" }
+                    }
+                    for d in day.deadlines {
+                        {row(d, false)}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use crate::model::timezone::DueZone;
+use crate::model::Status;
+use crate::persistence::archive::{archive_entries, describe_lateness, month_label, months, tags, ArchiveSummary};
+use crate::persistence::{ArchiveDelay, HomeworkRecord, HomeworkRepo, Patch, Query, RepoError};
+use crate::views::use_repo_events;
+use crate::Route;
+use chrono::Utc;
+use dioxus::prelude::*;
+use std::sync::Arc;
+
+/// Completed deadlines past the archive delay, browsable by month and tag, with how early or
+/// late each one was finished.
+#[component]
+pub fn Archive() -> Element {
+    let repo = use_context::<Arc<dyn HomeworkRepo>>();
+    let archive_delay = use_context::<Signal<ArchiveDelay>>();
+    let mut records = use_signal(Vec::<HomeworkRecord>::new);
+    let mut error = use_signal(|| Option::<RepoError>::None);
+    let mut revision = use_signal(|| 0u32);
+    // `None` shows every month / tag
+    let mut month = use_signal(|| Option::<String>::None);
+    let mut tag = use_signal(|| Option::<String>::None);
+
+    use_repo_events(move |_| revision += 1);
+
+    use_effect({
+        let repo = repo.clone();
+        move || {
+            let _ = revision(); // Subscribe
+            let query = Query { archive: archive_delay().only(Utc::now().timestamp()), ..Default::default() };
+            match repo.query(&query) {
+                Ok(r) => {
+                    records.set(r);
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    });
+
+    let reopen = {
+        let repo = repo.clone();
+        move |uid: String| {
+            let patch = Patch { status: Some(Status::Open), ..Default::default() };
+            if let Err(e) = repo.patch(&uid, patch) {
+                error.set(Some(e));
+            }
+        }
+    };
+
+    let entries = archive_entries(records(), &DueZone::Local);
+    let all_months = months(&entries);
+    let all_tags = tags(&entries);
+    let shown: Vec<_> = entries
+        .into_iter()
+        .filter(|e| month().is_none_or(|m| e.month_key() == m))
+        .filter(|e| tag().is_none_or(|t| e.record.tags.contains(&t)))
+        .collect();
+    let summary = ArchiveSummary::of(&shown);
+    let typical = summary.median_lateness_minutes.map(describe_lateness).unwrap_or_default();
+    let delay_note = match archive_delay().days {
+        Some(0) => "Deadlines move here as soon as they are completed.".to_string(),
+        Some(1) => "Deadlines move here 1 day after they are completed.".to_string(),
+        Some(days) => format!("Deadlines move here {days} days after they are completed."),
+        None => "Archiving is off; completed deadlines stay in the main list.".to_string(),
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-6",
+
+            div {
+                class: "flex justify-between items-center",
+                div { class: "flex items-center gap-2",
+                    Link { class: "btn btn-ghost p-2", to: Route::Home {}, "← Back" }
+                    h2 { class: "text-2xl font-bold", "Archive" }
+                }
+                div { class: "flex items-center gap-2",
+                    select {
+                        class: "form-input",
+                        title: "Month",
+                        onchange: move |e| month.set(Some(e.value()).filter(|v| !v.is_empty())),
+                        option { value: "", selected: month().is_none(), "All months" }
+                        for key in all_months {
+                            option {
+                                value: "{key}",
+                                selected: month().as_deref() == Some(key.as_str()),
+                                {month_label(&key)}
+                            }
+                        }
+                    }
+                    select {
+                        class: "form-input",
+                        title: "Tag",
+                        onchange: move |e| tag.set(Some(e.value()).filter(|v| !v.is_empty())),
+                        option { value: "", selected: tag().is_none(), "All tags" }
+                        for t in all_tags {
+                            option { value: "{t}", selected: tag().as_deref() == Some(t.as_str()), "{t}" }
+                        }
+                    }
+                }
+            }
+
+            p { class: "text-sm text-gray-500", "{delay_note}" }
+
+            if let Some(err) = error() {
+                div { class: "alert alert-error", "Could not load the archive: {err}" }
+            }
+
+            if summary.count == 0 {
+                p { class: "text-gray-500", "Nothing archived yet." }
+            } else {
+                div {
+                    class: "grid-list",
+                    style: "grid-template-columns: repeat(3, 1fr); gap: 1rem;",
+                    div {
+                        class: "bg-gray-50 p-3 rounded-lg border border-gray-100",
+                        div { class: "text-xs text-gray-500 uppercase font-semibold", "Completed" }
+                        div { class: "text-2xl font-bold", "{summary.count}" }
+                    }
+                    div {
+                        class: "bg-gray-50 p-3 rounded-lg border border-gray-100",
+                        div { class: "text-xs text-gray-500 uppercase font-semibold", "On time" }
+                        div { class: "text-2xl font-bold", "{summary.on_time}/{summary.count}" }
+                    }
+                    div {
+                        class: "bg-gray-50 p-3 rounded-lg border border-gray-100",
+                        div { class: "text-xs text-gray-500 uppercase font-semibold", "Typically finished" }
+                        div { class: "text-2xl font-bold", "{typical}" }
+                    }
+                }
+            }
+
+            for key in months(&shown) {
+                div {
+                    key: "{key}",
+                    class: "flex flex-col gap-2",
+                    h3 { class: "text-xl font-bold", {month_label(&key)} }
+                    div {
+                        class: "grid-list",
+                        for entry in shown.iter().filter(|e| e.month_key() == key).cloned() {
+                            div {
+                                key: "{entry.record.uid}",
+                                class: "card flex justify-between items-center gap-4",
+                                div {
+                                    class: "flex flex-col gap-1",
+                                    span { class: "font-bold", "{entry.record.name}" }
+                                    span {
+                                        class: "text-sm text-gray-500",
+                                        "Due {entry.planned.to_string()} · Done {entry.completed.to_string()}"
+                                    }
+                                    if !entry.record.tags.is_empty() {
+                                        div {
+                                            class: "flex gap-2",
+                                            style: "flex-wrap: wrap;",
+                                            for t in entry.record.tags.iter() {
+                                                span { class: "badge badge-gray", "{t}" }
+                                            }
+                                        }
+                                    }
+                                }
+                                div {
+                                    class: "flex items-center gap-2",
+                                    span {
+                                        class: if entry.is_on_time() { "badge badge-green" } else { "badge badge-red" },
+                                        {describe_lateness(entry.lateness_minutes)}
+                                    }
+                                    button {
+                                        class: "btn btn-secondary",
+                                        title: "Move back to the main list",
+                                        onclick: {
+                                            let uid = entry.record.uid.clone();
+                                            let mut reopen = reopen.clone();
+                                            move |_| reopen(uid.clone())
+                                        },
+                                        "Reopen"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
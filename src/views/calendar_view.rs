@@ -1,4 +1,7 @@
-use crate::model::{datetime::Datetime, Deadline};
+use crate::config::WeekStart;
+use crate::model::workload::{daily_load, heat_level, WorkloadSettings};
+use crate::model::{datetime::Datetime, milestone::Milestone, timezone::DueZone, Deadline};
+use crate::views::{use_drag_state, Reschedule, WEEK_MINUTES};
 use dioxus::prelude::*;
 use chrono::{Datelike, NaiveDate};
 use std::collections::HashMap;
@@ -7,8 +10,13 @@ use std::collections::HashMap;
 pub fn CalendarView(
     deadlines: Vec<Deadline>,
     on_select_date: EventHandler<Datetime>,
-    on_edit_deadline: EventHandler<Deadline>
+    on_edit_deadline: EventHandler<Deadline>,
+    on_reschedule: EventHandler<Reschedule>,
 ) -> Element {
+    let week_start = use_context::<Signal<WeekStart>>();
+    let workload = use_context::<Signal<WorkloadSettings>>();
+    // Chips are dragged onto another day, or moved with Alt+arrows (a row is a week)
+    let mut drag = use_drag_state::<NaiveDate>();
     let now = chrono::Local::now();
     let mut current_date = use_signal(|| NaiveDate::from_ymd_opt(now.year(), now.month(), 1).unwrap());
 
@@ -24,12 +32,40 @@ pub fn CalendarView(
         NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap().signed_duration_since(first_day).num_days()
     };
 
-    let start_weekday = first_day.weekday().num_days_from_sunday(); // 0 = Sunday
+    let start_weekday = week_start().column(first_day.weekday()); // blank cells before the 1st
+
+    // Remaining work due each day, shading the cells like a heatmap
+    let capacity = workload().daily_capacity();
+    let loads = daily_load(&deadlines, first_day, first_day + chrono::Duration::days(days_in_month - 1));
     
-    // Group deadlines by date
+    // Expand recurring deadlines into their occurrences for this month
+    let month_start = Datetime::new(year as u16, month as u8, 1, 0, 0);
+    let month_end = Datetime::new(year as u16, month as u8, days_in_month as u8, 23, 59);
+    let deadlines: Vec<Deadline> = deadlines
+        .into_iter()
+        .flat_map(|d| {
+            let projected = d.projected_occurrences(&month_start, &month_end);
+            std::iter::once(d).chain(projected)
+        })
+        .collect();
+
+    // Milestone due dates become secondary markers, also in the viewer's zone
+    let mut milestones_by_date: HashMap<(u16, u8, u8), Vec<(Deadline, Milestone)>> = HashMap::new();
+    for d in deadlines.iter().filter(|d| !d.is_projected()) {
+        for m in &d.milestones {
+            if let Some(due) = m.due {
+                let local = due.convert(&d.due_zone, &DueZone::Local);
+                let key = (local.year, local.month, local.day);
+                milestones_by_date.entry(key).or_default().push((d.clone(), m.clone()));
+            }
+        }
+    }
+
+    // Group deadlines by date in the viewer's zone
     let mut deadlines_by_date: HashMap<(u16, u8, u8), Vec<Deadline>> = HashMap::new();
     for d in deadlines {
-        let key = (d.due_date.year, d.due_date.month, d.due_date.day);
+        let local_due = d.local_due();
+        let key = (local_due.year, local_due.month, local_due.day);
         deadlines_by_date.entry(key).or_default().push(d);
     }
 
@@ -98,7 +134,9 @@ pub fn CalendarView(
             // Days Header
             div {
                 class: "grid grid-cols-7 gap-1 text-center text-sm font-bold text-gray-500 mb-2",
-                div { "Sun" } div { "Mon" } div { "Tue" } div { "Wed" } div { "Thu" } div { "Fri" } div { "Sat" }
+                for day in week_start().days() {
+                    div { "{day}" }
+                }
             }
 
             // Calendar Grid
@@ -114,22 +152,38 @@ pub fn CalendarView(
                     (1..=days_in_month).map(|day| {
                         let date_key = (year as u16, month as u8, day as u8);
                         let day_deadlines = deadlines_by_date.get(&date_key).cloned().unwrap_or_default();
+                        let day_milestones = milestones_by_date.get(&date_key).cloned().unwrap_or_default();
                         let is_today = {
                             let n = chrono::Local::now();
                             n.year() == year && n.month() == month && n.day() == day as u32
                         };
+                        let date = NaiveDate::from_ymd_opt(year, month, day as u32).unwrap_or(first_day);
+                        let drop_class = if drag.is_over(&date) { " drop-target" } else { "" };
+                        let hours = loads.get(day as usize - 1).map_or(0.0, |l| l.hours);
+                        let heat = heat_level(hours, capacity);
 
                         rsx! {
                             div {
                                 class: if is_today { 
-                                    "min-h-[75px] p-2 border-2 border-primary-200 bg-primary-50/30 rounded-lg cursor-pointer hover:border-primary-300 transition-colors relative group" 
+                                    "min-h-[75px] p-2 border-2 border-primary-200 bg-primary-50/30 rounded-lg cursor-pointer hover:border-primary-300 transition-colors relative group heat-{heat}{drop_class}" 
                                 } else { 
-                                    "min-h-[75px] p-2 border border-gray-100 hover:border-gray-300 rounded-lg cursor-pointer transition-colors relative group" 
+                                    "min-h-[75px] p-2 border border-gray-100 hover:border-gray-300 rounded-lg cursor-pointer transition-colors relative group heat-{heat}{drop_class}" 
                                 },
+                                title: if hours > 0.0 { "{hours:.1} h of work due" } else { "" },
                                 onclick: move |_| {
                                     let dt = Datetime::new(year as u16, month as u8, day as u8, 12, 0);
                                     on_select_date.call(dt);
                                 },
+                                ondragover: move |evt| {
+                                    evt.prevent_default();
+                                    drag.enter(date);
+                                },
+                                ondrop: move |evt| {
+                                    evt.prevent_default();
+                                    if let Some(moved) = drag.drop_on(date, None) {
+                                        on_reschedule.call(moved);
+                                    }
+                                },
                                 
                                 div { 
                                     class: if is_today { "font-bold text-primary-600 mb-1" } else { "font-medium text-gray-700 mb-1" },
@@ -146,12 +200,46 @@ pub fn CalendarView(
                                     class: "flex flex-col gap-1 overflow-y-auto max-h-[80px] scrollbar-hide",
                                     for d in day_deadlines {
                                         div {
+                                            key: "{d.id}-{d.occurrence}",
+                                            title: d.original_due_text(),
                                             class: "text-xs p-1.5 rounded bg-white border border-gray-200 shadow-sm text-gray-700 truncate hover:bg-primary-50 hover:text-primary-700 hover:border-primary-200 transition-colors",
+                                            tabindex: "0",
+                                            draggable: if d.is_projected() { "false" } else { "true" },
+                                            ondragstart: {
+                                                let d = d.clone();
+                                                move |_| drag.start(&d)
+                                            },
+                                            ondragend: move |_| drag.end(),
+                                            onmounted: {
+                                                let id = d.id.clone();
+                                                move |evt| drag.refocus(evt, &id)
+                                            },
+                                            onkeydown: {
+                                                let d = d.clone();
+                                                move |evt: KeyboardEvent| {
+                                                    if evt.key() == Key::Enter {
+                                                        on_edit_deadline.call(d.clone());
+                                                    } else if let Some(moved) = drag.nudge(&evt, &d, WEEK_MINUTES) {
+                                                        on_reschedule.call(moved);
+                                                    }
+                                                }
+                                            },
+                                            onclick: move |evt| {
+                                                evt.stop_propagation();
+                                                on_edit_deadline.call(d.clone());
+                                            },
+                                            if d.recurrence.is_some() { "↻ {d.name}" } else { "{d.name}" }
+                                        }
+                                    }
+                                    for (d, m) in day_milestones {
+                                        div {
+                                            class: if m.done { "milestone-marker done" } else { "milestone-marker" },
+                                            title: "{d.name}: {m.title}",
                                             onclick: move |evt| {
                                                 evt.stop_propagation();
                                                 on_edit_deadline.call(d.clone());
                                             },
-                                            "{d.name}"
+                                            "◆ {m.title}"
                                         }
                                     }
                                 }
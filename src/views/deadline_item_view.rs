@@ -1,9 +1,12 @@
-use crate::model::datetime::Datetime;
-use crate::model::Deadline;
+use crate::model::countdown::DueState;
+use crate::model::{Deadline, Status};
+use crate::persistence::MergedRepo;
+use crate::views::Now;
 use dioxus::prelude::*;
+use std::sync::Arc;
 
 // --- Continuous color utilities ---
-// We map urgency (0..∞) to a continuous gradient through Blue -> Yellow -> Orange -> Red
+// We map the normalized urgency (0..1) to a continuous gradient through Blue -> Yellow -> Orange -> Red
 // using linear RGB interpolation between the stop colors.
 
 fn lerp(a: f32, b: f32, t: f32) -> f32 {
@@ -23,23 +26,23 @@ fn rgb_to_hex(rgb: (u8, u8, u8)) -> String {
     format!("#{:02x}{:02x}{:02x}", rgb.0, rgb.1, rgb.2)
 }
 
-/// Continuous color from urgency using piecewise-linear stops.
-/// Stops: 0 -> Blue(#3b82f6), 1 -> Yellow(#eab308), 5 -> Orange(#f97316), 10+ -> Red(#ef4444)
+/// Continuous color from normalized urgency using piecewise-linear stops.
+/// Stops: 0 -> Blue(#3b82f6), 0.1 -> Yellow(#eab308), 0.5 -> Orange(#f97316), 1 -> Red(#ef4444)
 fn color_from_urgency_rgb(urgency: f32) -> (u8, u8, u8) {
-    let u = urgency.max(0.0);
+    let u = urgency.clamp(0.0, 1.0);
     let blue = (0x3b, 0x82, 0xf6);
     let yellow = (0xea, 0xb3, 0x08);
     let orange = (0xf9, 0x73, 0x16);
     let red = (0xef, 0x44, 0x44);
 
-    if u <= 1.0 {
-        let t = u / 1.0;
+    if u <= 0.1 {
+        let t = u / 0.1;
         lerp_rgb(blue, yellow, t)
-    } else if u <= 5.0 {
-        let t = (u - 1.0) / 4.0;
+    } else if u <= 0.5 {
+        let t = (u - 0.1) / 0.4;
         lerp_rgb(yellow, orange, t)
-    } else if u <= 10.0 {
-        let t = (u - 5.0) / 5.0;
+    } else if u < 1.0 {
+        let t = (u - 0.5) / 0.5;
         lerp_rgb(orange, red, t)
     } else {
         red
@@ -55,40 +58,80 @@ fn color_from_urgency_hex(urgency: f32) -> String {
 fn card_tint_from_urgency(urgency: f32) -> String {
     let (r, g, b) = color_from_urgency_rgb(urgency);
     // Choose alpha based on urgency; min 0.03, max 0.16 (subtle tint)
-    let mut a = urgency / 20.0; // 0..∞ => 0..∞; but we'll clamp
+    let mut a = urgency / 2.0; // 0..1 => 0..0.5; but we'll clamp
     if a < 0.03 { a = 0.03 }
     if a > 0.16 { a = 0.16 }
     format!("rgba({}, {}, {}, {:.3})", r, g, b, a)
 }
 
+/// Badge colour per status: red when stuck, amber when out of your hands, green when done.
+fn status_badge_class(status: Status) -> &'static str {
+    match status {
+        Status::Open => "badge badge-gray",
+        Status::Blocked => "badge badge-red",
+        Status::Waiting | Status::Submitted => "badge badge-amber",
+        Status::Completed => "badge badge-green",
+        Status::Cancelled => "badge badge-gray",
+    }
+}
+
 #[component]
 pub fn DeadlineItemView(mut deadline: Deadline, mut on_update: EventHandler<Deadline>, mut on_edit: EventHandler<Deadline>, mut on_delete: EventHandler<Deadline>) -> Element {
     // Local, draggable progress state (0-100). If you want to persist upward, we can add a callback later.
     let mut progress = use_signal(|| deadline.progress as f32);
+    // Only labelled in the "All workspaces" view
+    let workspace = use_context::<Option<Arc<MergedRepo>>>()
+        .and_then(|merged| merged.workspace_of(&deadline.id).map(str::to_string));
     
     let bar_color = color_from_urgency_hex(deadline.urgency);
     let card_tint = card_tint_from_urgency(deadline.urgency);
-    let (r, g, b) = color_from_urgency_rgb(deadline.urgency);
-    let border_color = format!("rgba({}, {}, {}, {:.3})", r, g, b, 0.12);
-    let due_date_str = deadline.due_date.to_string();
-    // Calculate remaining days for the due badge
-    let now = Datetime::now();
-    let diff = deadline.due_date.time_diff(&now);
-    let due_badge = if diff.is_negative {
-        format!("Overdue {}d", diff.days)
-    } else if diff.days == 0 {
-        "Due today".to_string()
+    // Render in the viewer's zone; the original zone is shown on hover.
+    let local_due = deadline.local_due();
+    let due_date_str = local_due.to_string();
+    let original_due = deadline.original_due_text();
+    // Time left, redrawn on every clock tick; counts down to the minute in the final day.
+    let clock = use_context::<Signal<Now>>();
+    let due_state = DueState::of(&deadline, &clock().0);
+    let overdue = due_state.is_overdue() && !deadline.status.is_closed();
+    let due_badge_class = match due_state {
+        DueState::Overdue(_) => "badge badge-red",
+        DueState::Countdown { .. } => "badge badge-amber",
+        DueState::Days(_) => "badge badge-blue",
+    };
+    let card_class = if deadline.status.is_closed() {
+        "card card-closed flex flex-col gap-4"
+    } else if overdue {
+        "card card-overdue flex flex-col gap-4"
     } else {
-        format!("Due in {}d", diff.days)
+        "card flex flex-col gap-4"
+    };
+    let border_color = if overdue {
+        "var(--red-800)".to_string()
+    } else {
+        let (r, g, b) = color_from_urgency_rgb(deadline.urgency);
+        format!("rgba({}, {}, {}, {:.3})", r, g, b, 0.12)
     };
     let progress_width = move || format!("{}%", progress().clamp(0.0, 100.0));
     let edit_clone = deadline.clone();
     let update_clone = deadline.clone();
     let delete_clone = deadline.clone();
+    let projected = deadline.is_projected();
+    let status = deadline.status;
+    let recurrence_text = deadline.recurrence.as_ref().map(|r| r.describe());
+    // With milestones, progress is derived from the checklist instead of the slider.
+    let has_milestones = !deadline.milestones.is_empty();
+    let milestones_done = deadline.milestones.iter().filter(|m| m.done).count();
+    let milestones_total = deadline.milestones.len();
+    let milestones_title = deadline
+        .milestones
+        .iter()
+        .map(|m| format!("{} {}", if m.done { "☑" } else { "☐" }, m.title))
+        .collect::<Vec<_>>()
+        .join("\n");
 
     rsx! {
         div {
-            class: "card flex flex-col gap-4",
+            class: card_class,
             style: "background-color: {card_tint}; border-color: {border_color};",
             
             // Header
@@ -98,7 +141,7 @@ pub fn DeadlineItemView(mut deadline: Deadline, mut on_update: EventHandler<Dead
                 div {
                     class: "flex flex-col min-w-0",
                     h3 { class: "text-xl font-bold truncate", "{deadline.name}" }
-                    span { class: "text-sm text-gray-500 truncate", "{due_date_str}" }
+                    span { class: "text-sm text-gray-500 truncate", title: "{original_due}", "{due_date_str}" }
                 }
                 div { class: "flex items-center gap-2 flex-shrink-0",
                     // Edit button
@@ -123,7 +166,7 @@ pub fn DeadlineItemView(mut deadline: Deadline, mut on_update: EventHandler<Dead
                     // Delete button (grouped with Edit)
                     button {
                         class: "btn-icon delete",
-                        title: "Delete",
+                        title: if projected { "Skip this occurrence" } else { "Delete" },
                         onclick: move |_| on_delete.call(delete_clone.clone()),
                         svg {
                             width: "20",
@@ -147,14 +190,40 @@ pub fn DeadlineItemView(mut deadline: Deadline, mut on_update: EventHandler<Dead
             div {
                 class: "flex gap-2",
                 style: "flex-wrap: wrap;",
-                span {
-                    class: if diff.is_negative { "badge badge-red" } else { "badge badge-blue" },
-                    "{due_badge}"
+                if status != Status::Open {
+                    span { class: status_badge_class(status), "{status.label()}" }
+                }
+                if !status.is_closed() {
+                    span {
+                        class: due_badge_class,
+                        {due_state.label()}
+                    }
                 }
                 span {
                     class: "badge badge-gray",
                     "Difficulty: {deadline.difficulty}"
                 }
+                if let Some(name) = workspace {
+                    span {
+                        class: "badge badge-blue",
+                        title: "Workspace",
+                        "▣ {name}"
+                    }
+                }
+                if has_milestones {
+                    span {
+                        class: "badge badge-gray",
+                        title: "{milestones_title}",
+                        "☑ {milestones_done}/{milestones_total}"
+                    }
+                }
+                if let Some(text) = recurrence_text {
+                    span {
+                        class: "badge badge-gray",
+                        title: "{text}",
+                        if projected { "↻ Upcoming" } else { "↻ Repeats" }
+                    }
+                }
             }
 
             // Progress Bar
@@ -182,14 +251,21 @@ pub fn DeadlineItemView(mut deadline: Deadline, mut on_update: EventHandler<Dead
                         max: "100",
                         step: "1",
                         value: "{progress}",
+                        // Projected occurrences cannot be worked on until the series reaches them,
+                        // and milestone checklists drive progress themselves.
+                        disabled: projected || has_milestones,
+                        // Follow the drag locally; save once the slider is released.
                         oninput: move |evt| {
                             let val = evt.value().parse::<f32>().unwrap_or(progress());
-                           let clamped = val.clamp(0.0, 100.0);
-                           progress.set(clamped);
-                           let mut d = update_clone.clone();
-                           d.progress = clamped.round() as u8;
-                           d.update_urgency();
-                           on_update.call(d);
+                            progress.set(val.clamp(0.0, 100.0));
+                        },
+                        onchange: move |evt| {
+                            let val = evt.value().parse::<f32>().unwrap_or(progress());
+                            let clamped = val.clamp(0.0, 100.0);
+                            progress.set(clamped);
+                            let mut d = update_clone.clone();
+                            d.progress = clamped.round() as u8;
+                            on_update.call(d);
                         },
                         class: "range-input",
                     }
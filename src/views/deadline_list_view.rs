@@ -1,6 +1,14 @@
-use crate::model::Deadline;
-use crate::views::DeadlineItemView;
+use crate::model::datetime::Datetime;
+use crate::model::urgency::UrgencyStrategy;
+use crate::model::{Deadline, Status};
+use crate::persistence::{ArchiveDelay, HomeworkRecord, HomeworkRepo, Query, QuerySort, RepoError, RepoEvent, SortField};
+use crate::views::{use_repo_events, DeadlineItemView, Now};
+use chrono::{Duration, Utc};
 use dioxus::prelude::*;
+use std::sync::Arc;
+
+/// How far ahead projected occurrences of recurring deadlines are listed.
+const RECURRENCE_LIST_HORIZON_DAYS: i64 = 14;
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 enum SortType {
@@ -9,51 +17,131 @@ enum SortType {
     Progress,
 }
 
-fn sorted_deadlines(input: &[Deadline], sort: SortType) -> Vec<Deadline> {
-    let mut v: Vec<Deadline> = input.iter().map(|d| (*d).clone()).collect();
-    match sort {
-        SortType::DueDate => {
-            v.sort_by(|a, b| a.due_date.cmp(&b.due_date));
+impl SortType {
+    /// Storage-level ordering; urgency depends on the clock and model, so it is sorted here.
+    fn query_sort(self) -> Vec<QuerySort> {
+        match self {
+            SortType::DueDate => vec![QuerySort::asc(SortField::Due)],
+            SortType::Urgency => Vec::new(),
+            // Lowest progress first
+            SortType::Progress => vec![QuerySort::asc(SortField::Progress)],
+        }
+    }
+}
+
+/// Which statuses the list shows.
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum StatusFilter {
+    All,
+    /// Everything that is neither completed nor cancelled
+    Active,
+    Only(Status),
+}
+
+impl StatusFilter {
+    fn statuses(self) -> Vec<Status> {
+        match self {
+            StatusFilter::All => Vec::new(),
+            StatusFilter::Active => Status::ALL.into_iter().filter(|s| !s.is_closed()).collect(),
+            StatusFilter::Only(s) => vec![s],
         }
-        SortType::Urgency => {
-            // Highest urgency first
-            v.sort_by(|a, b| {
-                b.urgency
-                    .partial_cmp(&a.urgency)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
+    }
+
+    /// Option value in the filter select.
+    fn as_str(self) -> &'static str {
+        match self {
+            StatusFilter::All => "all",
+            StatusFilter::Active => "active",
+            StatusFilter::Only(s) => s.as_str(),
         }
-        SortType::Progress => {
-            // Lowest progress first
-            v.sort_by(|a, b| a.progress.cmp(&b.progress));
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "active" => StatusFilter::Active,
+            other => Status::parse(other).map(StatusFilter::Only).unwrap_or(StatusFilter::All),
         }
     }
-    v
 }
 
+/// Order cards after projected occurrences have been merged in.
+fn sort_cards(v: &mut [Deadline], sort: SortType) {
+    match sort {
+        // By the instant each is due, not the wall-clock time in its own zone
+        SortType::DueDate => v.sort_by_cached_key(|d| d.local_due().to_total_minutes()),
+        // Highest urgency first; completed and cancelled ones are not ranked and go last
+        SortType::Urgency => v.sort_by(|a, b| {
+            a.status.is_closed().cmp(&b.status.is_closed()).then(b.urgency.total_cmp(&a.urgency))
+        }),
+        SortType::Progress => v.sort_by_key(|d| d.progress),
+    }
+}
+
+/// Searchable, sortable list of deadlines. The repository runs the query when the search or
+/// filters change; individual changes are then applied to the loaded records with the same
+/// query, and only a wholesale replacement of the storage re-reads it. Clock ticks only
+/// re-score and re-sort the cards already loaded. Query errors go to `on_error`.
 #[component]
-pub fn DeadlineListView(deadlines: Vec<Deadline>, mut on_update: EventHandler<Deadline>, mut on_edit: EventHandler<Deadline>, mut on_delete: EventHandler<Deadline>) -> Element {
+pub fn DeadlineListView(on_error: EventHandler<RepoError>, mut on_update: EventHandler<Deadline>, mut on_edit: EventHandler<Deadline>, mut on_delete: EventHandler<Deadline>) -> Element {
+    let repo = use_context::<Arc<dyn HomeworkRepo>>();
     let mut sort = use_signal(|| SortType::Urgency);
     let mut search = use_signal(|| String::new());
+    let mut status_filter = use_signal(|| StatusFilter::All);
+    let urgency_strategy = use_context::<Signal<UrgencyStrategy>>();
+    let archive_delay = use_context::<Signal<ArchiveDelay>>();
+    let clock = use_context::<Signal<Now>>();
+
+    let query = use_memo(move || Query {
+        text: Some(search()),
+        status: status_filter().statuses(),
+        archive: archive_delay().exclude(Utc::now().timestamp()),
+        sort: sort().query_sort(),
+        ..Default::default()
+    });
+    let mut records = use_signal(Vec::<HomeworkRecord>::new);
+    let load = use_callback(move |_: ()| match repo.query(&query.peek()) {
+        Ok(found) => records.set(found),
+        Err(e) => on_error.call(e),
+    });
+    // Re-queried when the search or the filters change, not on clock ticks.
+    use_effect(move || {
+        let _ = query(); // Subscribe
+        load.call(());
+    });
+    use_repo_events(move |event| match event {
+        RepoEvent::Created(rec) | RepoEvent::Updated(rec) => records.with_mut(|all| {
+            match all.iter().position(|r| r.uid == rec.uid) {
+                Some(i) => all[i] = rec,
+                None => all.push(rec),
+            }
+            *all = query.peek().apply(std::mem::take(all));
+        }),
+        RepoEvent::Deleted(rec) => records.with_mut(|all| all.retain(|r| r.uid != rec.uid)),
+        RepoEvent::Purged(uid) => records.with_mut(|all| all.retain(|r| r.uid != uid)),
+        RepoEvent::Reloaded | RepoEvent::Refreshed => load.call(()),
+    });
 
-    let query = search().trim().to_lowercase();
-    let filtered: Vec<Deadline> = deadlines
+    // Show upcoming occurrences of recurring deadlines as their own cards.
+    let statuses = status_filter().statuses();
+    let now = clock().0;
+    let to = now.to_naive().map(|n| Datetime::from_naive(&(n + Duration::days(RECURRENCE_LIST_HORIZON_DAYS)))).unwrap_or(now);
+    let strategy = urgency_strategy();
+    let mut sorted: Vec<Deadline> = records
+        .read()
         .iter()
-        .filter(|deadline| {
-            if query.is_empty() {
-                return true;
+        .flat_map(|r| {
+            let mut d = r.to_deadline();
+            d.update_urgency(&strategy);
+            let mut projected = d.projected_occurrences(&now, &to);
+            for p in projected.iter_mut() {
+                p.update_urgency(&strategy);
             }
-            // Match query against name or any tag (case-insensitive).
-            deadline.name.to_lowercase().contains(&query)
-                || deadline
-                    .tags
-                    .iter()
-                    .any(|tag| tag.to_lowercase().contains(&query))
+            std::iter::once(d).chain(projected)
         })
-        .cloned()
         .collect();
-
-    let sorted = sorted_deadlines(&filtered, sort());
+    // Projected occurrences start out open whatever the stored deadline's status is.
+    sorted.retain(|d| statuses.is_empty() || statuses.contains(&d.status));
+    sort_cards(&mut sorted, sort());
 
     rsx! {
         div {
@@ -81,6 +169,27 @@ pub fn DeadlineListView(deadlines: Vec<Deadline>, mut on_update: EventHandler<De
                     "Progress"
                 }
 
+                select {
+                    class: "form-input",
+                    title: "Status",
+                    style: "width: auto;",
+                    onchange: move |e| status_filter.set(StatusFilter::parse(&e.value())),
+                    for filter in [StatusFilter::All, StatusFilter::Active] {
+                        option {
+                            value: filter.as_str(),
+                            selected: status_filter() == filter,
+                            if filter == StatusFilter::All { "All statuses" } else { "Active" }
+                        }
+                    }
+                    for status in Status::ALL {
+                        option {
+                            value: status.as_str(),
+                            selected: status_filter() == StatusFilter::Only(status),
+                            "{status.label()}"
+                        }
+                    }
+                }
+
                 div {
                     class: "sort-search",
                     input {
@@ -98,10 +207,11 @@ pub fn DeadlineListView(deadlines: Vec<Deadline>, mut on_update: EventHandler<De
                 class: "flex flex-col gap-4",
                 { sorted.into_iter().map(|d| {
                     let deadline_clone = d.clone();
-                    let id = deadline_clone.id.clone();
+                    // Key on the due date too so a series that moved on remounts fresh.
+                    let key = format!("{}-{}", deadline_clone.id, deadline_clone.due_date.to_string());
                     rsx! {
                         DeadlineItemView {
-                            key: "{id}",
+                            key: "{key}",
                             deadline: deadline_clone,
                             on_update: move |d| on_update.call(d),
                             on_edit: move |d| on_edit.call(d),
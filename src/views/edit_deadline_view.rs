@@ -1,6 +1,20 @@
-use crate::model::{datetime::Datetime, Deadline};
+use crate::model::milestone::Milestone;
+use crate::model::recurrence::{Frequency, Recurrence};
+use crate::model::reminder::{Lead, ReminderSettings};
+use crate::model::{datetime::Datetime, timezone::DueZone, Deadline, Status};
+use chrono::{Datelike, Weekday};
 use dioxus::prelude::*;
 
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
 fn parse_due_date(s: &str) -> Option<Datetime> {
     // Expect "YYYY-MM-DD HH:MM"
     let s = s.trim();
@@ -16,7 +30,102 @@ fn parse_due_date(s: &str) -> Option<Datetime> {
     if time_parts.len() != 2 { return None; }
     let hour = time_parts[0].parse::<u8>().ok()?;
     let minute = time_parts[1].parse::<u8>().ok()?;
-    Some(Datetime::new(year, month, day, hour, minute))
+    let dt = Datetime::new(year, month, day, hour, minute);
+    if dt.is_valid() { Some(dt) } else { None }
+}
+
+/// Editable form state of one milestone; `due` is kept as typed until save.
+#[derive(Clone, Debug, PartialEq)]
+struct MilestoneDraft {
+    title: String,
+    weight: u8,
+    done: bool,
+    due: String,
+}
+
+impl From<&Milestone> for MilestoneDraft {
+    fn from(m: &Milestone) -> Self {
+        Self {
+            title: m.title.clone(),
+            weight: m.weight,
+            done: m.done,
+            due: m.due.map(|d| d.to_string()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Drafts with an empty title are dropped. `None` if any due date is invalid.
+fn parse_milestone_drafts(drafts: &[MilestoneDraft]) -> Option<Vec<Milestone>> {
+    drafts
+        .iter()
+        .filter(|m| !m.title.trim().is_empty())
+        .map(|m| {
+            let due = if m.due.trim().is_empty() { None } else { Some(parse_due_date(&m.due)?) };
+            Some(Milestone {
+                title: m.title.trim().to_string(),
+                weight: m.weight.max(1),
+                done: m.done,
+                due,
+            })
+        })
+        .collect()
+}
+
+/// "YYYY-MM-DD" => end of that day.
+fn parse_day(s: &str) -> Option<Datetime> {
+    parse_due_date(&format!("{} 23:59", s.trim()))
+}
+
+fn format_day(dt: &Datetime) -> String {
+    format!("{:04}-{:02}-{:02}", dt.year, dt.month, dt.day)
+}
+
+fn repeat_kind(rule: Option<&Recurrence>) -> &'static str {
+    match rule.map(|r| &r.frequency) {
+        None => "none",
+        Some(Frequency::Daily) => "daily",
+        Some(Frequency::Weekly { .. }) => "weekly",
+        Some(Frequency::MonthlyByDate { .. }) => "monthly_date",
+        Some(Frequency::MonthlyByWeekday { .. }) => "monthly_weekday",
+    }
+}
+
+/// Build the recurrence from the form fields. Monthly rules take their day (or nth weekday)
+/// from the due date. The outer `None` means some field is invalid.
+fn parse_recurrence_form(
+    kind: &str,
+    interval: u32,
+    weekdays: &[Weekday],
+    until: &str,
+    count: &str,
+    exceptions: &str,
+    due: &Datetime,
+) -> Option<Option<Recurrence>> {
+    let frequency = match kind {
+        "daily" => Frequency::Daily,
+        "weekly" => Frequency::Weekly { weekdays: weekdays.to_vec() },
+        "monthly_date" => Frequency::MonthlyByDate { day: due.day },
+        "monthly_weekday" => {
+            let weekday = due.to_naive()?.weekday();
+            let nth = (due.day as i8 - 1) / 7 + 1;
+            // A 5th weekday only exists in some months; treat it as "the last one".
+            let nth = if nth == 5 { -1 } else { nth };
+            Frequency::MonthlyByWeekday { nth, weekday }
+        }
+        _ => return Some(None),
+    };
+    let mut rule = Recurrence::new(frequency);
+    rule.interval = interval.max(1);
+    if !until.trim().is_empty() {
+        rule.until = Some(parse_day(until)?);
+    }
+    if !count.trim().is_empty() {
+        rule.count = Some(count.trim().parse::<u32>().ok().filter(|c| *c > 0)?);
+    }
+    for day in exceptions.split(',').filter(|s| !s.trim().is_empty()) {
+        rule.exceptions.push(parse_day(day)?);
+    }
+    Some(Some(rule))
 }
 
 #[component]
@@ -28,9 +137,37 @@ pub fn EditDeadlineView(
     // Local temporary state for editing
     let mut name = use_signal(|| deadline.name.clone());
     let mut due = use_signal(|| deadline.due_date.to_string());
+    let mut zone = use_signal(|| deadline.due_zone.to_string());
+    let mut zone_error = use_signal(|| Option::<String>::None);
     let mut difficulty = use_signal(|| deadline.difficulty);
+    let mut status = use_signal(|| deadline.status);
+    // Only the current status and the ones it may move to are offered.
+    let status_choices: Vec<Status> = std::iter::once(deadline.status).chain(deadline.status.next().iter().copied()).collect();
     // progress removed from edit UI per user request
     let mut tags = use_signal(|| deadline.tags.join(", "));
+    let mut milestones = use_signal(|| deadline.milestones.iter().map(MilestoneDraft::from).collect::<Vec<_>>());
+    let reminder_settings = use_context::<Signal<ReminderSettings>>();
+    let mut default_reminders = use_signal(|| deadline.reminders.is_none());
+    // Blank = no reminders for this deadline
+    let mut reminders = use_signal(|| {
+        deadline.reminders.as_deref().unwrap_or(&reminder_settings.peek().leads)
+            .iter().map(Lead::to_string).collect::<Vec<_>>().join(", ")
+    });
+    let rule = deadline.recurrence.as_ref();
+    let mut repeat = use_signal(|| repeat_kind(rule).to_string());
+    let mut interval = use_signal(|| rule.map(|r| r.interval).unwrap_or(1));
+    let mut weekdays = use_signal(|| match rule.map(|r| &r.frequency) {
+        Some(Frequency::Weekly { weekdays }) => weekdays.clone(),
+        _ => Vec::new(),
+    });
+    let mut until = use_signal(|| rule.and_then(|r| r.until.as_ref()).map(format_day).unwrap_or_default());
+    let mut count = use_signal(|| rule.and_then(|r| r.count).map(|c| c.to_string()).unwrap_or_default());
+    let mut exceptions = use_signal(|| {
+        rule.map(|r| r.exceptions.iter().map(format_day).collect::<Vec<_>>().join(", "))
+            .unwrap_or_default()
+    });
+
+    let default_leads = Lead::format_list(&reminder_settings().leads);
 
     rsx! {
         div {
@@ -67,6 +204,22 @@ pub fn EditDeadlineView(
                     oninput: move |e| due.set(e.value().clone()),
                 }
             }
+            div {
+                class: "form-group",
+                label { class: "form-label", "Time zone (e.g. Europe/Berlin, +08:00, AoE; blank = local)" }
+                input {
+                    r#type: "text",
+                    class: "form-input",
+                    value: "{zone}",
+                    oninput: move |e| {
+                        zone.set(e.value().clone());
+                        zone_error.set(None);
+                    },
+                }
+                if let Some(err) = zone_error() {
+                    div { class: "alert alert-error text-sm", "{err}" }
+                }
+            }
             div {
                 class: "form-group",
                 label { class: "form-label", "Difficulty (1-10)" }
@@ -81,6 +234,19 @@ pub fn EditDeadlineView(
                     },
                 }
             }
+            div {
+                class: "form-group",
+                label { class: "form-label", "Status" }
+                select {
+                    class: "form-input",
+                    onchange: move |e| {
+                        if let Some(s) = Status::parse(&e.value()) { status.set(s); }
+                    },
+                    for s in status_choices {
+                        option { value: s.as_str(), selected: status() == s, "{s.label()}" }
+                    }
+                }
+            }
             // Progress control intentionally removed from the edit panel
             div {
                 class: "form-group",
@@ -92,6 +258,188 @@ pub fn EditDeadlineView(
                     oninput: move |e| tags.set(e.value().clone()),
                 }
             }
+            div {
+                class: "form-group",
+                label { class: "form-label", "Remind me before (e.g. 1w, 1d, 2h, 30m)" }
+                label {
+                    class: "flex items-center gap-2 text-sm",
+                    input {
+                        r#type: "checkbox",
+                        checked: default_reminders(),
+                        onchange: move |e| default_reminders.set(e.checked()),
+                    }
+                    "Use the default reminders ({default_leads})"
+                }
+                if !default_reminders() {
+                    input {
+                        r#type: "text",
+                        class: "form-input",
+                        placeholder: "No reminders",
+                        value: "{reminders}",
+                        oninput: move |e| reminders.set(e.value().clone()),
+                    }
+                }
+            }
+            div {
+                class: "form-group",
+                div {
+                    class: "flex justify-between items-center",
+                    label { class: "form-label", "Milestones (progress follows completed weight)" }
+                    button {
+                        class: "btn btn-secondary",
+                        onclick: move |_| milestones.with_mut(|v| v.push(MilestoneDraft {
+                            title: String::new(),
+                            weight: 1,
+                            done: false,
+                            due: String::new(),
+                        })),
+                        "+ Add"
+                    }
+                }
+                for (idx, m) in milestones().into_iter().enumerate() {
+                    div {
+                        key: "{idx}",
+                        class: "milestone-row",
+                        input {
+                            r#type: "checkbox",
+                            title: "Done",
+                            checked: m.done,
+                            onchange: move |e| milestones.with_mut(|v| v[idx].done = e.checked()),
+                        }
+                        input {
+                            r#type: "text",
+                            class: "form-input",
+                            placeholder: "Title",
+                            value: "{m.title}",
+                            oninput: move |e| milestones.with_mut(|v| v[idx].title = e.value()),
+                        }
+                        input {
+                            r#type: "number",
+                            class: "form-input milestone-weight",
+                            title: "Weight",
+                            min: "1",
+                            max: "100",
+                            value: "{m.weight}",
+                            oninput: move |e| {
+                                if let Ok(w) = e.value().parse::<u8>() {
+                                    milestones.with_mut(|v| v[idx].weight = w.max(1));
+                                }
+                            },
+                        }
+                        input {
+                            r#type: "text",
+                            class: "form-input milestone-due",
+                            placeholder: "Due (optional)",
+                            value: "{m.due}",
+                            oninput: move |e| milestones.with_mut(|v| v[idx].due = e.value()),
+                        }
+                        button {
+                            class: "btn-icon",
+                            title: "Move up",
+                            disabled: idx == 0,
+                            onclick: move |_| milestones.with_mut(|v| if idx > 0 { v.swap(idx, idx - 1) }),
+                            "↑"
+                        }
+                        button {
+                            class: "btn-icon",
+                            title: "Move down",
+                            onclick: move |_| milestones.with_mut(|v| if idx + 1 < v.len() { v.swap(idx, idx + 1) }),
+                            "↓"
+                        }
+                        button {
+                            class: "btn-icon delete",
+                            title: "Remove",
+                            onclick: move |_| milestones.with_mut(|v| { v.remove(idx); }),
+                            "✕"
+                        }
+                    }
+                }
+            }
+            div {
+                class: "form-group",
+                label { class: "form-label", "Repeat" }
+                select {
+                    class: "form-input",
+                    value: "{repeat}",
+                    onchange: move |e| repeat.set(e.value()),
+                    option { value: "none", selected: repeat() == "none", "Does not repeat" }
+                    option { value: "daily", selected: repeat() == "daily", "Daily" }
+                    option { value: "weekly", selected: repeat() == "weekly", "Weekly" }
+                    option { value: "monthly_date", selected: repeat() == "monthly_date", "Monthly on this date" }
+                    option { value: "monthly_weekday", selected: repeat() == "monthly_weekday", "Monthly on this weekday" }
+                }
+            }
+            if repeat() != "none" {
+                div {
+                    class: "form-group",
+                    label { class: "form-label", "Every N (days / weeks / months)" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        class: "form-input",
+                        value: "{interval}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse::<u32>() { interval.set(v.max(1)); }
+                        },
+                    }
+                }
+                if repeat() == "weekly" {
+                    div {
+                        class: "flex gap-2",
+                        style: "flex-wrap: wrap;",
+                        for wd in WEEKDAYS {
+                            label {
+                                class: "text-sm",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: weekdays().contains(&wd),
+                                    onchange: move |_| {
+                                        let mut days = weekdays();
+                                        if let Some(idx) = days.iter().position(|d| *d == wd) {
+                                            days.remove(idx);
+                                        } else {
+                                            days.push(wd);
+                                        }
+                                        weekdays.set(days);
+                                    },
+                                }
+                                " {wd}"
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "form-group",
+                    label { class: "form-label", "Ends on (YYYY-MM-DD, optional)" }
+                    input {
+                        r#type: "text",
+                        class: "form-input",
+                        value: "{until}",
+                        oninput: move |e| until.set(e.value().clone()),
+                    }
+                }
+                div {
+                    class: "form-group",
+                    label { class: "form-label", "Occurrences left (optional)" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        class: "form-input",
+                        value: "{count}",
+                        oninput: move |e| count.set(e.value().clone()),
+                    }
+                }
+                div {
+                    class: "form-group",
+                    label { class: "form-label", "Skip dates (YYYY-MM-DD, comma separated)" }
+                    input {
+                        r#type: "text",
+                        class: "form-input",
+                        value: "{exceptions}",
+                        oninput: move |e| exceptions.set(e.value().clone()),
+                    }
+                }
+            }
             div {
                 class: "flex justify-end gap-3",
                 style: "margin-top: 1rem;",
@@ -104,14 +452,35 @@ pub fn EditDeadlineView(
                     class: "btn btn-primary",
                     onclick: move |_| {
                         // Build and validate
-                        if let Some(parsed) = parse_due_date(&due()) {
+                        let Some(parsed) = parse_due_date(&due()) else { return };
+                        let parsed_zone = DueZone::parse(&zone());
+                        if parsed_zone.is_none() {
+                            zone_error.set(Some(format!("Unknown time zone \"{}\"", zone().trim())));
+                        }
+                        let recurrence = parse_recurrence_form(
+                            &repeat(), interval(), &weekdays(), &until(), &count(), &exceptions(), &parsed,
+                        );
+                        let parsed_milestones = parse_milestone_drafts(&milestones());
+                        let parsed_reminders = if default_reminders() {
+                            Some(None)
+                        } else {
+                            Lead::parse_list(&reminders()).ok().map(Some)
+                        };
+                        if let (Some(parsed_zone), Some(recurrence), Some(parsed_milestones), Some(parsed_reminders)) =
+                            (parsed_zone, recurrence, parsed_milestones, parsed_reminders)
+                        {
                             let mut new = deadline.clone();
                             new.name = name();
                             new.due_date = parsed;
+                            new.due_zone = parsed_zone;
                             new.difficulty = difficulty();
-                            // Keep existing progress value; do not modify from this view
+                            new.status = status();
+                            // Keep existing progress value unless milestones drive it
                             new.tags = tags().split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
-                            new.update_urgency();
+                            new.recurrence = recurrence;
+                            new.milestones = parsed_milestones;
+                            new.reminders = parsed_reminders;
+                            new.sync_progress_from_milestones();
                             on_save.call(new);
                         }
                     },
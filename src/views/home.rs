@@ -1,73 +1,256 @@
-use crate::model::{datetime, Deadline};
-use crate::persistence::{HomeworkRepo, NewHomework};
-use crate::views::{DeadlineListView, EditDeadlineView, CalendarView};
+use crate::model::{datetime, urgency::UrgencyStrategy, Deadline, Status};
+use crate::persistence::{ArchiveDelay, HistoryRepo, HomeworkRecord, HomeworkRepo, NewHomework, Patch, Query, RepoError, RepoEvent};
+use crate::config::HomeView;
+use crate::views::{use_live_clock, use_repo_events, Reschedule, AgendaView, DeadlineListView, EditDeadlineView, CalendarView, WeekView, SettingsView, IcsView, QuickAddBar, WorkloadPanel, WorkspaceSwitcher};
+use chrono::Utc;
 use dioxus::prelude::*;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// How long the undo toast stays on screen.
+const TOAST_DURATION: Duration = Duration::from_secs(6);
+
+/// Transient message, optionally offering to undo (or redo) the last change.
+#[derive(Clone, PartialEq)]
+struct Toast {
+    message: String,
+    action: Option<ToastAction>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ToastAction {
+    Undo,
+    Redo,
+}
+
+/// Projected occurrences of a series are edited through the stored deadline they came from.
+fn stored_deadline(all: &[Deadline], d: Deadline) -> Deadline {
+    if d.is_projected() {
+        all.iter().find(|x| x.id == d.id).cloned().unwrap_or(d)
+    } else {
+        d
+    }
+}
+
+/// Write the editable fields of `d` back to its record. A status change the record's current
+/// status does not allow is reported instead of saved.
+fn save_deadline(repo: &dyn HomeworkRepo, d: &Deadline) -> Result<(), RepoError> {
+    if let Some(mut rec) = repo.get(&d.id)? {
+        rec.apply_deadline(d, Utc::now().timestamp())?;
+        repo.update(rec)?;
+    }
+    Ok(())
+}
 
 /// The Home page component that will be rendered when the current route is `[Route::Home]`
 #[component]
 pub fn Home() -> Element {
     let repo = use_context::<Arc<dyn HomeworkRepo>>();
+    let history = use_context::<Arc<HistoryRepo>>();
     let mut deadlines_state = use_signal(Vec::<Deadline>::new);
     let mut selected = use_signal(|| Option::<Deadline>::None);
-    let mut is_calendar_view = use_signal(|| false);
+    let mut home_view = use_context::<Signal<HomeView>>();
+    let mut show_settings = use_signal(|| false);
+    let mut show_ics = use_signal(|| false);
+    let urgency_strategy = use_context::<Signal<UrgencyStrategy>>();
+    let archive_delay = use_context::<Signal<ArchiveDelay>>();
     
-    // Signal to trigger reload
-    let mut reload_trigger = use_signal(|| 0);
+    // Last error from loading the list, shown as a banner
+    let mut load_error = use_signal(|| Option::<RepoError>::None);
+    let mut toast = use_signal(|| Option::<Toast>::None);
+    let mut toast_generation = use_signal(|| 0u32);
 
-    use_effect({
+    // Newer toasts replace older ones; only the latest one's timer may hide it.
+    let show_toast = use_callback(move |t: Toast| {
+        toast.set(Some(t));
+        let generation = toast_generation() + 1;
+        toast_generation.set(generation);
+        spawn(async move {
+            tokio::time::sleep(TOAST_DURATION).await;
+            if toast_generation() == generation {
+                toast.set(None);
+            }
+        });
+    });
+
+    let undo = use_callback({
+        let history = history.clone();
+        move |_: ()| match history.undo() {
+            Ok(Some(op)) => {
+                show_toast.call(Toast { message: format!("Undone: {}", op.describe()), action: Some(ToastAction::Redo) });
+            }
+            Ok(None) => {}
+            Err(e) => load_error.set(Some(e)),
+        }
+    });
+
+    let redo = use_callback({
+        let history = history.clone();
+        move |_: ()| match history.redo() {
+            Ok(Some(op)) => {
+                show_toast.call(Toast { message: op.describe(), action: Some(ToastAction::Undo) });
+            }
+            Ok(None) => {}
+            Err(e) => load_error.set(Some(e)),
+        }
+    });
+
+    // Dragging (or Alt+arrowing) a chip in the calendar changes only its due date.
+    let reschedule = use_callback({
         let repo = repo.clone();
-        move || {
-            let _ = reload_trigger(); // Subscribe
-            let repo = repo.clone();
-            spawn(async move {
-                if let Ok(records) = repo.list() {
-                    let deadlines: Vec<Deadline> = records.into_iter().map(|r| {
-                        let due_date = datetime::Datetime::from_string(&r.due_text).unwrap_or_else(datetime::Datetime::now);
-                        let mut d = Deadline {
-                            id: r.uid,
-                            name: r.name,
-                            due_date,
-                            difficulty: r.difficulty,
-                            progress: r.progress,
-                            milestones: r.milestones,
-                            urgency: 0.0,
-                            tags: r.tags,
-                        };
-                        d.update_urgency();
-                        d
-                    }).collect();
-                    deadlines_state.set(deadlines);
+        move |(mut d, due): Reschedule| {
+            let patch = Patch { due_text: Some(due.to_string()), ..Default::default() };
+            match repo.patch(&d.id, patch) {
+                Ok(_) => {
+                    d.due_date = due;
+                    let message = format!("Moved '{}' to {}", d.name, d.original_due_text());
+                    show_toast.call(Toast { message, action: Some(ToastAction::Undo) });
+                }
+                Err(e) => show_toast.call(Toast { message: e.to_string(), action: None }),
+            }
+        }
+    });
+
+    let scored = move |r: &HomeworkRecord| {
+        let mut d = r.to_deadline();
+        d.update_urgency(&*urgency_strategy.peek());
+        d
+    };
+
+    // Archived deadlines only appear on the archive page.
+    let archive_filter = move || archive_delay.peek().exclude(Utc::now().timestamp());
+
+    // Full read, on startup and when the storage was replaced wholesale.
+    let load_all = use_callback({
+        let repo = repo.clone();
+        move |_: ()| match repo.query(&Query { archive: archive_filter(), ..Default::default() }) {
+            Ok(records) => {
+                deadlines_state.set(records.iter().map(scored).collect());
+                load_error.set(None);
+            }
+            Err(e) => load_error.set(Some(e)),
+        }
+    });
+    // Runs on startup and again when the archive delay changes.
+    use_effect(move || {
+        let _ = archive_delay(); // Subscribe
+        load_all.call(());
+    });
+
+    // Apply individual changes in place instead of re-listing.
+    use_repo_events(move |event| match event {
+        RepoEvent::Created(rec) | RepoEvent::Updated(rec) => deadlines_state.with_mut(|all| {
+            let pos = all.iter().position(|d| d.id == rec.uid);
+            let hidden = rec.deleted || !archive_filter().matches(&rec);
+            match (pos, hidden) {
+                (Some(i), true) => {
+                    all.remove(i);
                 }
-            });
+                (Some(i), false) => all[i] = scored(&rec),
+                (None, false) => all.push(scored(&rec)),
+                (None, true) => {}
+            }
+        }),
+        RepoEvent::Deleted(rec) => deadlines_state.with_mut(|all| all.retain(|d| d.id != rec.uid)),
+        RepoEvent::Purged(uid) => deadlines_state.with_mut(|all| all.retain(|d| d.id != uid)),
+        RepoEvent::Reloaded => load_all.call(()),
+        RepoEvent::Refreshed => {
+            load_all.call(());
+            show_toast.call(Toast { message: "Reloaded changes made outside the app".to_string(), action: None });
+        }
+    });
+
+    // Ticks as due times approach; cards read it from context to redraw their countdowns.
+    let now = use_live_clock(deadlines_state);
+
+    // Re-score when the urgency model changes and as time passes.
+    use_effect(move || {
+        let _ = now(); // Subscribe
+        let strategy = urgency_strategy();
+        for d in deadlines_state.write().iter_mut() {
+            d.update_urgency(&strategy);
         }
     });
 
     rsx! {
         div {
             class: "layout-grid",
+            tabindex: "0",
+            // Ctrl+Z / Ctrl+Shift+Z (Cmd on macOS); left to text fields while a form is open.
+            onkeydown: move |evt: KeyboardEvent| {
+                let mods = evt.modifiers();
+                if evt.code() != Code::KeyZ || !(mods.ctrl() || mods.meta()) {
+                    return;
+                }
+                if selected().is_some() || show_ics() {
+                    return;
+                }
+                evt.prevent_default();
+                if mods.shift() { redo.call(()) } else { undo.call(()) }
+            },
             
-            // Left Column: Deadline List or Calendar
+            // Left Column: list, month, week or agenda view
             div {
                 class: "flex flex-col gap-6",
                 
                 // Header
                 div {
                     class: "flex justify-between items-center",
-                    h2 { class: "text-2xl font-bold", "Your Deadlines" }
+                    div { class: "flex items-center gap-3",
+                        h2 { class: "text-2xl font-bold", "Your Deadlines" }
+                        WorkspaceSwitcher {}
+                    }
                     div { class: "flex items-center gap-2",
+                        div { class: "view-switcher",
+                            for view in HomeView::ALL {
+                                button {
+                                    class: if home_view() == view { "btn btn-primary" } else { "btn btn-ghost p-2" },
+                                    title: "Show {view.as_str()} view",
+                                    onclick: move |_| {
+                                        home_view.set(view);
+                                        selected.set(None);
+                                    },
+                                    "{view.label()}"
+                                }
+                            }
+                        }
+                        button {
+                            class: if show_settings() { "btn btn-primary" } else { "btn btn-ghost p-2" },
+                            title: "Settings",
+                            onclick: move |_| {
+                                show_settings.set(!show_settings());
+                                show_ics.set(false);
+                                selected.set(None);
+                            },
+                            "⚙"
+                        }
                         button {
-                            class: if is_calendar_view() { "btn btn-primary" } else { "btn btn-ghost p-2" },
-                            title: if is_calendar_view() { "Switch to List" } else { "Switch to Calendar" },
+                            class: if show_ics() { "btn btn-primary" } else { "btn btn-ghost p-2" },
+                            title: "Import / Export iCalendar",
                             onclick: move |_| {
-                                is_calendar_view.set(!is_calendar_view());
+                                show_ics.set(!show_ics());
+                                show_settings.set(false);
                                 selected.set(None);
                             },
-                            if is_calendar_view() { "📝 List" } else { "📅 Calendar" }
+                            "⇅ .ics"
+                        }
+                        Link {
+                            class: "btn btn-ghost p-2",
+                            title: "Archive",
+                            to: crate::Route::Archive {},
+                            "🗄"
+                        }
+                        Link {
+                            class: "btn btn-ghost p-2",
+                            title: "Trash",
+                            to: crate::Route::Trash {},
+                            "🗑"
                         }
                         button {
                             class: "btn btn-primary",
                             onclick: move |_| {
+                                show_settings.set(false);
+                                show_ics.set(false);
                                 let new_deadline = Deadline::new("".to_string(), "".to_string(), datetime::Datetime::now(), 5);
                                 selected.set(Some(new_deadline));
                             },
@@ -76,7 +259,35 @@ pub fn Home() -> Element {
                     }
                 }
 
-                if is_calendar_view() {
+                QuickAddBar {
+                    on_error: move |message: String| show_toast.call(Toast { message, action: None }),
+                }
+
+                if let Some(err) = load_error() {
+                    div {
+                        class: "alert alert-error flex justify-between items-center gap-4",
+                        span { "Could not load your deadlines: {err}" }
+                        if matches!(err, RepoError::Corrupt(_)) {
+                            button {
+                                class: "btn btn-primary",
+                                onclick: {
+                                    let repo = repo.clone();
+                                    move |_| {
+                                        let repo = repo.clone();
+                                        spawn(async move {
+                                            if let Err(e) = repo.restore_latest_backup() {
+                                                load_error.set(Some(e));
+                                            }
+                                        });
+                                    }
+                                },
+                                "Restore latest backup"
+                            }
+                        }
+                    }
+                }
+
+                if home_view() == HomeView::Month {
                     CalendarView {
                         deadlines: deadlines_state().clone(),
                         on_select_date: move |dt: datetime::Datetime| {
@@ -84,32 +295,56 @@ pub fn Home() -> Element {
                             selected.set(Some(new_deadline));
                         },
                         on_edit_deadline: move |d: Deadline| {
-                            selected.set(Some(d));
+                            selected.set(Some(stored_deadline(&deadlines_state(), d)));
+                        },
+                        on_reschedule: move |moved: Reschedule| reschedule.call(moved),
+                    }
+                } else if home_view() == HomeView::Week {
+                    WeekView {
+                        deadlines: deadlines_state().clone(),
+                        on_select_date: move |dt: datetime::Datetime| {
+                            let new_deadline = Deadline::new("".to_string(), "".to_string(), dt, 5);
+                            selected.set(Some(new_deadline));
+                        },
+                        on_edit_deadline: move |d: Deadline| {
+                            selected.set(Some(stored_deadline(&deadlines_state(), d)));
+                        },
+                        on_reschedule: move |moved: Reschedule| reschedule.call(moved),
+                    }
+                } else if home_view() == HomeView::Agenda {
+                    AgendaView {
+                        deadlines: deadlines_state().clone(),
+                        on_edit_deadline: move |d: Deadline| {
+                            selected.set(Some(stored_deadline(&deadlines_state(), d)));
                         }
                     }
                 } else {
                     DeadlineListView { 
-                        deadlines: deadlines_state().clone(), 
+                        on_error: move |e| load_error.set(Some(e)),
                         on_update: {
                             let repo = repo.clone();
-                            move |d: Deadline| {
+                            move |mut d: Deadline| {
+                                if d.is_projected() {
+                                    return;
+                                }
+                                // Sent once the progress slider is released, never mid-drag. Letting go
+                                // at 100% finishes the deadline like "done" does: a series moves on to its
+                                // next occurrence, anything else completes. Lower values only reopen.
+                                if d.progress == 100 && d.status != Status::Completed && d.status.can_become(Status::Completed) {
+                                    d.mark_done();
+                                } else {
+                                    d.sync_status_with_progress();
+                                }
                                 let repo = repo.clone();
                                 spawn(async move {
-                                    if let Ok(Some(mut rec)) = repo.get(&d.id) {
-                                        rec.name = d.name;
-                                        rec.due_text = d.due_date.to_string();
-                                        rec.difficulty = d.difficulty;
-                                        rec.progress = d.progress;
-                                        rec.tags = d.tags;
-                                        rec.milestones = d.milestones;
-                                        let _ = repo.update(rec);
-                                        reload_trigger.with_mut(|x| *x += 1);
+                                    if let Err(e) = save_deadline(repo.as_ref(), &d) {
+                                        show_toast.call(Toast { message: e.to_string(), action: None });
                                     }
                                 });
                             }
                         }, 
                         on_edit: move |d: Deadline| {
-                            selected.set(Some(d));
+                            selected.set(Some(stored_deadline(&deadlines_state(), d)));
                         }
                         ,
                         on_delete: {
@@ -117,20 +352,57 @@ pub fn Home() -> Element {
                             move |d: Deadline| {
                                 let repo = repo.clone();
                                 spawn(async move {
-                                    let _ = repo.delete(&d.id);
+                                    if d.is_projected() {
+                                        // Deleting a projected occurrence skips just that date.
+                                        if let Ok(Some(mut rec)) = repo.get(&d.id) {
+                                            let mut series = rec.to_deadline();
+                                            if let Some(rule) = series.recurrence.as_mut() {
+                                                rule.exceptions.push(d.due_date);
+                                            }
+                                            if rec.apply_deadline(&series, Utc::now().timestamp()).is_ok() {
+                                                let _ = repo.update(rec);
+                                            }
+                                        }
+                                    } else {
+                                        let _ = repo.delete(&d.id);
+                                    }
+                                    show_toast.call(Toast { message: format!("Deleted '{}'", d.name), action: Some(ToastAction::Undo) });
                                 });
-                                reload_trigger.with_mut(|x| *x += 1);
                             }
                         }
                     }
                 }
             }
             
+            if let Some(t) = toast() {
+                div {
+                    class: "toast",
+                    span { "{t.message}" }
+                    if let Some(action) = t.action {
+                        button {
+                            class: "btn btn-ghost",
+                            onclick: move |_| {
+                                toast.set(None);
+                                match action {
+                                    ToastAction::Undo => undo.call(()),
+                                    ToastAction::Redo => redo.call(()),
+                                }
+                            },
+                            if action == ToastAction::Undo { "Undo" } else { "Redo" }
+                        }
+                    }
+                }
+            }
+
             // Right Column: Edit Panel or Stats
             div {
                 class: "card",
                 style: "position: sticky; top: 1rem;",
-                if let Some(sel) = selected().clone() {
+                if show_settings() && selected().is_none() {
+                    SettingsView { on_close: move |_| show_settings.set(false) }
+                } else if show_ics() && selected().is_none() {
+                    IcsView { on_close: move |_| show_ics.set(false) }
+                } else if let Some(sel) = selected().clone() {
                     EditDeadlineView { 
                         key: "{sel.id}", 
                         deadline: sel.clone(), 
@@ -139,29 +411,15 @@ pub fn Home() -> Element {
                             move |d: Deadline| {
                                 let repo = repo.clone();
                                 spawn(async move {
-                                    if d.id.is_empty() {
-                                        let payload = NewHomework {
-                                            name: d.name,
-                                            due_text: d.due_date.to_string(),
-                                            difficulty: d.difficulty,
-                                            progress: d.progress,
-                                            tags: d.tags,
-                                            milestones: d.milestones,
-                                        };
-                                        let _ = repo.create(payload);
+                                    let saved = if d.id.is_empty() {
+                                        repo.create(NewHomework::from(&d)).map(|_| ())
                                     } else {
-                                        if let Ok(Some(mut rec)) = repo.get(&d.id) {
-                                            rec.name = d.name;
-                                            rec.due_text = d.due_date.to_string();
-                                            rec.difficulty = d.difficulty;
-                                            rec.progress = d.progress;
-                                            rec.tags = d.tags;
-                                            rec.milestones = d.milestones;
-                                            let _ = repo.update(rec);
-                                        }
+                                        save_deadline(repo.as_ref(), &d)
+                                    };
+                                    match saved {
+                                        Ok(()) => selected.set(None),
+                                        Err(e) => show_toast.call(Toast { message: e.to_string(), action: None }),
                                     }
-                                    reload_trigger.with_mut(|x| *x += 1);
-                                    selected.set(None);
                                 });
                             }
                         }, 
@@ -172,10 +430,18 @@ pub fn Home() -> Element {
                     {
                         let all = deadlines_state();
                         let total = all.len();
-                        let completed = all.iter().filter(|d| d.progress == 100).count();
-                        let in_progress = all.iter().filter(|d| d.progress > 0 && d.progress < 100).count();
-                        let not_started = all.iter().filter(|d| d.progress == 0).count();
-                        let avg_progress = if total > 0 { all.iter().map(|d| d.progress as f32).sum::<f32>() / total as f32 } else { 0.0 };
+                        let completed = all.iter().filter(|d| d.status == Status::Completed).count();
+                        let cancelled = all.iter().filter(|d| d.status == Status::Cancelled).count();
+                        let active: Vec<&Deadline> = all.iter().filter(|d| !d.status.is_closed()).collect();
+                        let in_progress = active.iter().filter(|d| d.progress > 0).count();
+                        let not_started = active.iter().filter(|d| d.progress == 0).count();
+                        // Cancelled work will never be finished, so it does not count against progress.
+                        let counted = total - cancelled;
+                        let avg_progress = if counted > 0 {
+                            all.iter().filter(|d| d.status != Status::Cancelled).map(|d| d.progress as f32).sum::<f32>() / counted as f32
+                        } else {
+                            0.0
+                        };
                         
                         rsx! {
                             div {
@@ -223,6 +489,18 @@ pub fn Home() -> Element {
                                         div { class: "text-xs text-gray-500 uppercase font-semibold", "Not Started" }
                                         div { class: "text-2xl font-bold text-gray-600", "{not_started}" }
                                     }
+                                    div {
+                                        class: "bg-gray-50 p-3 rounded-lg border border-gray-100",
+                                        div { class: "text-xs text-gray-500 uppercase font-semibold", "Cancelled" }
+                                        div { class: "text-2xl font-bold text-gray-600", "{cancelled}" }
+                                    }
+                                }
+
+                                WorkloadPanel {
+                                    deadlines: all.clone(),
+                                    on_edit_deadline: move |d: Deadline| {
+                                        selected.set(Some(stored_deadline(&deadlines_state(), d)));
+                                    }
                                 }
                             }
                         }
@@ -0,0 +1,112 @@
+use crate::persistence::ics::{export_repo_ics, import_ics, IcsComponent};
+use crate::persistence::HomeworkRepo;
+use dioxus::prelude::*;
+use std::sync::Arc;
+
+/// Side panel for exchanging deadlines with other calendar apps as iCalendar (.ics) files.
+#[component]
+pub fn IcsView(on_close: EventHandler<()>) -> Element {
+    let repo = use_context::<Arc<dyn HomeworkRepo>>();
+    let mut as_events = use_signal(|| false);
+    let mut export_path = use_signal(|| {
+        std::env::current_dir()
+            .map(|dir| dir.join("deadlines.ics").display().to_string())
+            .unwrap_or_else(|_| "deadlines.ics".to_string())
+    });
+    let mut message = use_signal(|| Option::<String>::None);
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-4",
+
+            div {
+                class: "flex justify-between items-center",
+                style: "border-bottom: 1px solid var(--gray-200); padding-bottom: 0.5rem; margin-bottom: 0.5rem;",
+                h3 { class: "text-xl font-bold", "Import / Export" }
+                button {
+                    class: "btn-icon",
+                    onclick: move |_| on_close.call(()),
+                    "✕"
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Export as" }
+                select {
+                    class: "form-input",
+                    onchange: move |e| as_events.set(e.value() == "event"),
+                    option { value: "todo", selected: !as_events(), "Tasks (VTODO)" }
+                    option { value: "event", selected: as_events(), "Events (VEVENT)" }
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Export file" }
+                input {
+                    class: "form-input",
+                    value: "{export_path}",
+                    oninput: move |e| export_path.set(e.value()),
+                }
+            }
+
+            button {
+                class: "btn btn-primary",
+                onclick: {
+                    let repo = repo.clone();
+                    move |_| {
+                        let component = if as_events() { IcsComponent::Event } else { IcsComponent::Todo };
+                        let path = export_path();
+                        let result = export_repo_ics(repo.as_ref(), component)
+                            .map_err(|e| e.to_string())
+                            .and_then(|text| std::fs::write(&path, text).map_err(|e| e.to_string()));
+                        message.set(Some(match result {
+                            Ok(()) => format!("Exported to {path}"),
+                            Err(e) => format!("Export failed: {e}"),
+                        }));
+                    }
+                },
+                "Export .ics"
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Import from .ics" }
+                input {
+                    class: "form-input",
+                    r#type: "file",
+                    accept: ".ics",
+                    onchange: {
+                        let repo = repo.clone();
+                        move |evt: FormEvent| {
+                            let repo = repo.clone();
+                            async move {
+                                let Some(engine) = evt.files() else { return };
+                                for name in engine.files() {
+                                    let Some(text) = engine.read_file_to_string(&name).await else {
+                                        message.set(Some(format!("Could not read {name}")));
+                                        continue;
+                                    };
+                                    match import_ics(repo.as_ref(), &text) {
+                                        Ok(summary) => {
+                                            message.set(Some(format!(
+                                                "Imported {} item(s); skipped {} already present and {} without a due date",
+                                                summary.imported, summary.duplicates, summary.skipped
+                                            )));
+                                        }
+                                        Err(e) => message.set(Some(format!("Import failed: {e}"))),
+                                    }
+                                }
+                            }
+                        }
+                    },
+                }
+            }
+
+            if let Some(msg) = message() {
+                p { class: "text-sm text-gray-500", "{msg}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::model::countdown::minutes_until_refresh;
+use crate::model::datetime::Datetime;
+use crate::model::Deadline;
+use chrono::{Local, Timelike};
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// The viewer's wall-clock time as of the last tick. Views showing time left read it from
+/// context instead of calling `Datetime::now()`, so they redraw when it advances.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Now(pub Datetime);
+
+/// Provide `Signal<Now>` to the component's children and advance it whenever the cards of
+/// `deadlines` next change: every minute while one is within a day of its due time, less often
+/// otherwise. Ticks land on minute boundaries and never touch storage.
+pub fn use_live_clock(deadlines: Signal<Vec<Deadline>>) -> Signal<Now> {
+    let mut now = use_context_provider(|| Signal::new(Now(Datetime::now())));
+
+    // Re-planned after every tick and whenever the deadlines change.
+    let _ = use_resource(move || async move {
+        let minutes = minutes_until_refresh(&deadlines.read(), &now().0);
+        let local = Local::now();
+        let into_minute = Duration::from_secs(local.second().into()) + Duration::from_nanos(local.nanosecond().into());
+        let wait = Duration::from_secs(minutes.max(1) as u64 * 60).saturating_sub(into_minute);
+        tokio::time::sleep(wait).await;
+        now.set(Now(Datetime::now()));
+    });
+
+    now
+}
@@ -29,3 +29,39 @@ pub use deadline_list_view::DeadlineListView;
 
 mod calendar_view;
 pub use calendar_view::CalendarView;
+
+mod week_view;
+pub use week_view::WeekView;
+
+mod agenda_view;
+pub use agenda_view::AgendaView;
+
+mod workload_panel;
+pub use workload_panel::WorkloadPanel;
+
+mod settings_view;
+pub use settings_view::SettingsView;
+
+mod ics_view;
+pub use ics_view::IcsView;
+
+mod trash;
+pub use trash::Trash;
+
+mod archive;
+pub use archive::Archive;
+
+mod quick_add_bar;
+pub use quick_add_bar::QuickAddBar;
+
+mod reschedule;
+pub use reschedule::{use_drag_state, Reschedule, WEEK_MINUTES};
+
+mod live_clock;
+pub use live_clock::{use_live_clock, Now};
+
+mod repo_events;
+pub use repo_events::use_repo_events;
+
+mod workspace_switcher;
+pub use workspace_switcher::WorkspaceSwitcher;
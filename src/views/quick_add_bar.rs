@@ -0,0 +1,78 @@
+use crate::model::quick_add::QuickAdd;
+use crate::persistence::{HomeworkRepo, NewHomework};
+use chrono::Local;
+use dioxus::prelude::*;
+use std::sync::Arc;
+
+/// One-line input that turns text like "OS lab 3 next fri 23:59 #os !8" into a deadline,
+/// previewing what it understood before anything is created.
+#[component]
+pub fn QuickAddBar(on_error: EventHandler<String>) -> Element {
+    let repo = use_context::<Arc<dyn HomeworkRepo>>();
+    let mut text = use_signal(String::new);
+
+    let parsed = QuickAdd::parse(&text(), Local::now().naive_local());
+    let ready = parsed.to_deadline().is_some();
+
+    let add = use_callback(move |_: ()| {
+        let Some(d) = QuickAdd::parse(&text(), Local::now().naive_local()).to_deadline() else {
+            return;
+        };
+        match repo.create(NewHomework::from(&d)) {
+            Ok(_) => text.set(String::new()),
+            Err(e) => on_error.call(e.to_string()),
+        }
+    });
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-1",
+            div {
+                class: "flex items-center gap-2",
+                input {
+                    class: "form-input",
+                    style: "flex: 1;",
+                    placeholder: "Quick add: OS lab 3 next fri 23:59 #os !8",
+                    value: "{text}",
+                    oninput: move |e| text.set(e.value()),
+                    onkeydown: move |e: KeyboardEvent| match e.key() {
+                        Key::Enter => add.call(()),
+                        Key::Escape => text.set(String::new()),
+                        _ => {}
+                    },
+                }
+                button {
+                    class: "btn btn-primary",
+                    disabled: !ready,
+                    onclick: move |_| add.call(()),
+                    "Add"
+                }
+            }
+            if !text().trim().is_empty() {
+                div {
+                    class: "flex items-center gap-2 text-sm text-gray-500",
+                    style: "flex-wrap: wrap;",
+                    if parsed.name.is_empty() {
+                        span { "Add a name" }
+                    } else {
+                        span { class: "font-bold", "{parsed.name}" }
+                    }
+                    if let Some(due) = parsed.due {
+                        span {
+                            "Due {due.to_string()}"
+                            if let Some(zone) = &parsed.zone { " ({zone.label()})" }
+                        }
+                    } else {
+                        span { "No due date yet: try \"tomorrow\", \"fri 17:00\" or \"in 3 days\"" }
+                    }
+                    for t in parsed.tags.iter() {
+                        span { class: "badge badge-gray", "{t}" }
+                    }
+                    if let Some(d) = parsed.difficulty {
+                        span { class: "badge badge-amber", "Difficulty {d}" }
+                    }
+                }
+            }
+        }
+    }
+}
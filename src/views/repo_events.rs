@@ -0,0 +1,27 @@
+use crate::persistence::{HomeworkRepo, RepoEvent};
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use std::sync::Arc;
+
+/// Call `on_event` for every change to the repository in context, on the UI thread.
+/// The subscription lives as long as the calling component.
+pub fn use_repo_events(on_event: impl FnMut(RepoEvent) + 'static) {
+    let repo = use_context::<Arc<dyn HomeworkRepo>>();
+    let handler = use_callback(on_event);
+    let events = use_coroutine(move |mut rx: UnboundedReceiver<RepoEvent>| async move {
+        while let Some(event) = rx.next().await {
+            handler.call(event);
+        }
+    });
+    // Repo listeners may run on any thread, so they only forward into the coroutine.
+    let subscription = use_hook({
+        let repo = repo.clone();
+        move || {
+            let tx = events.tx();
+            repo.changes().subscribe(Box::new(move |event| {
+                let _ = tx.unbounded_send(event.clone());
+            }))
+        }
+    });
+    use_drop(move || repo.changes().unsubscribe(subscription));
+}
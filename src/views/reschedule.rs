@@ -0,0 +1,97 @@
+use crate::model::reschedule::{due_moved_to, due_shifted};
+use crate::model::{datetime::Datetime, Deadline};
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+
+const DAY_MINUTES: i64 = 24 * 60;
+/// Minutes in a week, for moving month-view chips up or down a row.
+pub const WEEK_MINUTES: i64 = 7 * DAY_MINUTES;
+
+/// Payload of `on_reschedule`: the deadline as it was and its new due date in its own zone.
+pub type Reschedule = (Deadline, Datetime);
+
+/// Drag-and-drop state shared by the chips and drop targets of one calendar view.
+#[derive(Clone, Copy)]
+pub struct DragState<T: 'static> {
+    /// The chip being dragged
+    pub dragging: Signal<Option<Deadline>>,
+    /// The cell it is over, highlighted as a drop target
+    pub over: Signal<Option<T>>,
+    /// Deadline last moved from the keyboard, focused again once it lands in its new cell
+    pub moved: Signal<Option<String>>,
+}
+
+pub fn use_drag_state<T: PartialEq + 'static>() -> DragState<T> {
+    DragState {
+        dragging: use_signal(|| None),
+        over: use_signal(|| None),
+        moved: use_signal(|| None),
+    }
+}
+
+impl<T: PartialEq + 'static> DragState<T> {
+    pub fn is_over(&self, target: &T) -> bool {
+        self.over.read().as_ref() == Some(target)
+    }
+
+    pub fn start(&mut self, d: &Deadline) {
+        self.dragging.set(Some(d.clone()));
+    }
+
+    pub fn enter(&mut self, target: T) {
+        if !self.is_over(&target) {
+            self.over.set(Some(target));
+        }
+    }
+
+    pub fn end(&mut self) {
+        self.dragging.set(None);
+        self.over.set(None);
+    }
+
+    /// Drop the dragged deadline on local `day` (and `hour`, in views laid out by hour).
+    /// `None` if nothing was being dragged or it was dropped where it already was.
+    pub fn drop_on(&mut self, day: NaiveDate, hour: Option<u8>) -> Option<Reschedule> {
+        let d = self.dragging.take()?;
+        self.over.set(None);
+        due_moved_to(&d, day, hour).map(|due| (d, due))
+    }
+
+    /// Move `d` with Alt+arrow keys (see [`nudge_minutes`]), keeping it focused in its new cell.
+    /// Projected occurrences move with their series, not on their own.
+    pub fn nudge(&mut self, evt: &KeyboardEvent, d: &Deadline, vertical: i64) -> Option<Reschedule> {
+        if d.is_projected() {
+            return None;
+        }
+        let minutes = nudge_minutes(evt, vertical)?;
+        evt.prevent_default();
+        let due = due_shifted(d, minutes)?;
+        self.moved.set(Some(d.id.clone()));
+        Some((d.clone(), due))
+    }
+
+    /// Focus a freshly mounted chip if it is the one just moved from the keyboard.
+    pub fn refocus(&mut self, evt: MountedEvent, id: &str) {
+        if self.moved.peek().as_deref() == Some(id) {
+            self.moved.set(None);
+            spawn(async move {
+                let _ = evt.set_focus(true).await;
+            });
+        }
+    }
+}
+
+/// How far an Alt+arrow key press moves a focused chip, in minutes: a day for ←/→ and
+/// `vertical` for ↑/↓. `None` for any other key.
+pub fn nudge_minutes(evt: &KeyboardEvent, vertical: i64) -> Option<i64> {
+    if !evt.modifiers().alt() {
+        return None;
+    }
+    match evt.key() {
+        Key::ArrowLeft => Some(-DAY_MINUTES),
+        Key::ArrowRight => Some(DAY_MINUTES),
+        Key::ArrowUp => Some(-vertical),
+        Key::ArrowDown => Some(vertical),
+        _ => None,
+    }
+}
@@ -0,0 +1,236 @@
+use crate::config::{Theme, WeekStart};
+use crate::model::reminder::{Lead, ReminderSettings};
+use crate::model::urgency::UrgencyStrategy;
+use crate::model::workload::WorkloadSettings;
+use crate::persistence::{ArchiveDelay, Backend, TrashRetention};
+use crate::Startup;
+use dioxus::prelude::*;
+
+/// Side panel with user preferences. Values live in context signals provided by `App`,
+/// which saves them to config.toml.
+#[component]
+pub fn SettingsView(on_close: EventHandler<()>) -> Element {
+    let mut urgency_strategy = use_context::<Signal<UrgencyStrategy>>();
+    let mut retention = use_context::<Signal<TrashRetention>>();
+    let mut archive_delay = use_context::<Signal<ArchiveDelay>>();
+    let mut week_start = use_context::<Signal<WeekStart>>();
+    let mut theme = use_context::<Signal<Theme>>();
+    let mut backend = use_context::<Signal<Backend>>();
+    let mut reminders = use_context::<Signal<ReminderSettings>>();
+    let mut workload = use_context::<Signal<WorkloadSettings>>();
+    // Default leads as typed; saved once they parse.
+    let mut leads_text = use_signal(|| Lead::format_list(&reminders.peek().leads));
+    let mut leads_error = use_signal(|| Option::<String>::None);
+    let startup = use_context::<Startup>();
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-4",
+
+            div {
+                class: "flex justify-between items-center",
+                style: "border-bottom: 1px solid var(--gray-200); padding-bottom: 0.5rem; margin-bottom: 0.5rem;",
+                h3 { class: "text-xl font-bold", "Settings" }
+                button {
+                    class: "btn-icon",
+                    onclick: move |_| on_close.call(()),
+                    "✕"
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Urgency model" }
+                select {
+                    class: "form-input",
+                    value: "{urgency_strategy().as_str()}",
+                    onchange: move |e| {
+                        if let Some(s) = UrgencyStrategy::parse(&e.value()) {
+                            urgency_strategy.set(s);
+                        }
+                    },
+                    for s in UrgencyStrategy::ALL {
+                        option {
+                            value: s.as_str(),
+                            selected: s == urgency_strategy(),
+                            {s.label()}
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Week starts on" }
+                select {
+                    class: "form-input",
+                    onchange: move |e| {
+                        if let Some(w) = WeekStart::parse(&e.value()) {
+                            week_start.set(w);
+                        }
+                    },
+                    for w in WeekStart::ALL {
+                        option {
+                            value: w.as_str(),
+                            selected: w == week_start(),
+                            {w.label()}
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Theme" }
+                select {
+                    class: "form-input",
+                    onchange: move |e| {
+                        if let Some(t) = Theme::parse(&e.value()) {
+                            theme.set(t);
+                        }
+                    },
+                    for t in Theme::ALL {
+                        option {
+                            value: t.as_str(),
+                            selected: t == theme(),
+                            {t.label()}
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Empty trash" }
+                select {
+                    class: "form-input",
+                    onchange: move |e| {
+                        let days = e.value().parse::<u32>().ok();
+                        retention.set(TrashRetention { days });
+                    },
+                    for days in TrashRetention::PRESETS {
+                        option {
+                            value: days.map(|d| d.to_string()).unwrap_or_else(|| "never".to_string()),
+                            selected: days == retention().days,
+                            {TrashRetention { days }.label()}
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Archive completed deadlines" }
+                select {
+                    class: "form-input",
+                    onchange: move |e| {
+                        let days = e.value().parse::<u32>().ok();
+                        archive_delay.set(ArchiveDelay { days });
+                    },
+                    for days in ArchiveDelay::PRESETS {
+                        option {
+                            value: days.map(|d| d.to_string()).unwrap_or_else(|| "never".to_string()),
+                            selected: days == archive_delay().days,
+                            {ArchiveDelay { days }.label()}
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Reminders" }
+                label {
+                    class: "flex items-center gap-2 text-sm",
+                    input {
+                        r#type: "checkbox",
+                        checked: reminders().enabled,
+                        onchange: move |e| reminders.with_mut(|r| r.enabled = e.checked()),
+                    }
+                    "Show desktop notifications"
+                }
+                input {
+                    r#type: "text",
+                    class: "form-input",
+                    title: "Default reminders, e.g. 1w, 1d, 2h",
+                    value: "{leads_text}",
+                    oninput: move |e| {
+                        let text = e.value();
+                        // "none" is what an empty list is shown as.
+                        let parsed = if text.trim().eq_ignore_ascii_case("none") { Ok(Vec::new()) } else { Lead::parse_list(&text) };
+                        match parsed {
+                            Ok(leads) => {
+                                reminders.with_mut(|r| r.leads = leads);
+                                leads_error.set(None);
+                            }
+                            Err(e) => leads_error.set(Some(e)),
+                        }
+                        leads_text.set(text);
+                    },
+                }
+                if let Some(err) = leads_error() {
+                    div { class: "alert alert-error text-sm", "{err}" }
+                }
+                select {
+                    class: "form-input",
+                    title: "Snooze for",
+                    onchange: move |e| {
+                        if let Ok(m) = e.value().parse::<u32>() {
+                            reminders.with_mut(|r| r.snooze_minutes = m);
+                        }
+                    },
+                    for minutes in [10u32, 30, 60, 180, 24 * 60] {
+                        option {
+                            value: "{minutes}",
+                            selected: reminders().snooze_minutes == minutes,
+                            "Snooze for "
+                            {Lead { minutes }.to_string()}
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Daily capacity" }
+                select {
+                    class: "form-input",
+                    title: "Hours of work a day can take before it is flagged as over capacity",
+                    onchange: move |e| {
+                        if let Ok(hours) = e.value().parse::<u32>() {
+                            workload.with_mut(|w| w.daily_capacity_hours = hours);
+                        }
+                    },
+                    for hours in WorkloadSettings::PRESETS {
+                        option {
+                            value: "{hours}",
+                            selected: workload().daily_capacity_hours == hours,
+                            "{hours} hours a day"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "form-group",
+                label { class: "form-label", "Storage" }
+                select {
+                    class: "form-input",
+                    onchange: move |e| {
+                        if let Some(b) = Backend::parse(&e.value()) {
+                            backend.set(b);
+                        }
+                    },
+                    for b in Backend::ALL {
+                        option {
+                            value: b.as_str(),
+                            selected: b == backend(),
+                            {b.label()}
+                        }
+                    }
+                }
+                p { class: "text-sm text-gray-500", "Takes effect after a restart. Data folder: {startup.data_dir.display()}" }
+            }
+        }
+    }
+}
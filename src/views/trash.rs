@@ -0,0 +1,145 @@
+use crate::persistence::{HomeworkRecord, HomeworkRepo, RepoError, TrashRetention};
+use crate::views::use_repo_events;
+use crate::Route;
+use chrono::{Local, TimeZone, Utc};
+use dioxus::prelude::*;
+use std::sync::Arc;
+
+fn format_timestamp(ts: i64) -> String {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Soft-deleted deadlines with actions to restore or permanently purge them.
+#[component]
+pub fn Trash() -> Element {
+    let repo = use_context::<Arc<dyn HomeworkRepo>>();
+    let retention = use_context::<Signal<TrashRetention>>();
+    let mut records = use_signal(Vec::<HomeworkRecord>::new);
+    let mut error = use_signal(|| Option::<RepoError>::None);
+    let mut revision = use_signal(|| 0u32);
+    let mut confirm_empty = use_signal(|| false);
+
+    use_repo_events(move |_| revision += 1);
+
+    use_effect({
+        let repo = repo.clone();
+        move || {
+            let _ = revision(); // Subscribe
+            match repo.list_deleted() {
+                Ok(r) => {
+                    records.set(r);
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    });
+
+    let run = {
+        let repo = repo.clone();
+        move |action: &dyn Fn(&dyn HomeworkRepo) -> Result<(), RepoError>| {
+            if let Err(e) = action(repo.as_ref()) {
+                error.set(Some(e));
+            }
+        }
+    };
+
+    let now = Utc::now().timestamp();
+    let retention_note = match retention().days {
+        Some(days) => format!("Deleted deadlines are removed for good after {days} days."),
+        None => "Deleted deadlines are kept until you remove them.".to_string(),
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-6",
+
+            div {
+                class: "flex justify-between items-center",
+                div { class: "flex items-center gap-2",
+                    Link { class: "btn btn-ghost p-2", to: Route::Home {}, "← Back" }
+                    h2 { class: "text-2xl font-bold", "Trash" }
+                }
+                if !records().is_empty() {
+                    button {
+                        class: if confirm_empty() { "btn btn-danger" } else { "btn btn-secondary" },
+                        onclick: {
+                            let mut run = run.clone();
+                            move |_| {
+                                if !confirm_empty() {
+                                    confirm_empty.set(true);
+                                    return;
+                                }
+                                confirm_empty.set(false);
+                                run(&|repo| repo.purge_deleted_before(i64::MAX).map(|_| ()));
+                            }
+                        },
+                        if confirm_empty() { "Really delete all forever?" } else { "Empty trash" }
+                    }
+                }
+            }
+
+            p { class: "text-sm text-gray-500", "{retention_note}" }
+
+            if let Some(err) = error() {
+                div { class: "alert alert-error", "Could not update the trash: {err}" }
+            }
+
+            if records().is_empty() {
+                p { class: "text-gray-500", "The trash is empty." }
+            }
+
+            div {
+                class: "grid-list",
+                for rec in records() {
+                    div {
+                        key: "{rec.uid}",
+                        class: "card flex justify-between items-center gap-4",
+                        div {
+                            class: "flex flex-col gap-1",
+                            span { class: "font-bold", "{rec.name}" }
+                            span { class: "text-sm text-gray-500", "Due {rec.due_text}" }
+                            if let Some(ts) = rec.deleted_time() {
+                                span {
+                                    class: "text-xs text-gray-500",
+                                    {format!("Deleted {}", format_timestamp(ts))}
+                                    if let Some(cutoff) = retention().cutoff(now) {
+                                        {
+                                            let days_left = ((ts - cutoff).max(0) + 86_399) / 86_400;
+                                            rsx! { " · purged in {days_left} days" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex items-center gap-2",
+                            button {
+                                class: "btn btn-primary",
+                                onclick: {
+                                    let uid = rec.uid.clone();
+                                    let mut run = run.clone();
+                                    move |_| run(&|repo| repo.restore(&uid).map(|_| ()))
+                                },
+                                "Restore"
+                            }
+                            button {
+                                class: "btn btn-secondary",
+                                onclick: {
+                                    let uid = rec.uid.clone();
+                                    let mut run = run.clone();
+                                    move |_| run(&|repo| repo.purge(&uid))
+                                },
+                                "Delete forever"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
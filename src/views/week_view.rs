@@ -0,0 +1,136 @@
+use crate::config::WeekStart;
+use crate::model::agenda::{local_date, occurrences_between};
+use crate::model::{datetime::Datetime, Deadline};
+use crate::views::{use_drag_state, Now, Reschedule};
+use chrono::{Datelike, Duration, NaiveDate};
+use dioxus::prelude::*;
+
+/// One week as seven day columns by 24 hour rows, with each deadline in the row of its due
+/// hour (in the viewer's zone). Clicking an empty slot starts a new deadline due then; chips
+/// are dragged to another slot, or moved a day or an hour at a time with Alt+arrows.
+#[component]
+pub fn WeekView(
+    deadlines: Vec<Deadline>,
+    on_select_date: EventHandler<Datetime>,
+    on_edit_deadline: EventHandler<Deadline>,
+    on_reschedule: EventHandler<Reschedule>,
+) -> Element {
+    let week_start = use_context::<Signal<WeekStart>>();
+    let clock = use_context::<Signal<Now>>();
+    let today = clock().0.to_naive().map(|n| n.date()).unwrap_or_default();
+    // Offset in weeks from the current one
+    let mut offset = use_signal(|| 0i64);
+    let mut drag = use_drag_state::<(NaiveDate, u8)>();
+
+    let first = week_start().week_of(today) + Duration::weeks(offset());
+    let days: Vec<NaiveDate> = (0..7).map(|i| first + Duration::days(i)).collect();
+    let last = days[6];
+    let items = occurrences_between(&deadlines, first, last);
+    let title = if first.year() == last.year() {
+        format!("{} – {}", first.format("%b %-d"), last.format("%b %-d, %Y"))
+    } else {
+        format!("{} – {}", first.format("%b %-d, %Y"), last.format("%b %-d, %Y"))
+    };
+    // Deadlines due in the slot (day column, hour) with their "HH:MM", earliest first
+    let slot = |day: NaiveDate, hour: u8| -> Vec<(String, Deadline)> {
+        items
+            .iter()
+            .filter(|d| local_date(d) == Some(day) && d.local_due().hour == hour)
+            .map(|d| {
+                let due = d.local_due();
+                (format!("{:02}:{:02}", due.hour, due.minute), d.clone())
+            })
+            .collect()
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-4",
+
+            div {
+                class: "flex justify-between items-center p-2",
+                button { class: "btn btn-ghost p-2", title: "Previous week", onclick: move |_| offset -= 1, "‹" }
+                div { class: "flex items-center gap-2",
+                    h3 { class: "text-lg font-bold text-gray-800", "{title}" }
+                    if offset() != 0 {
+                        button { class: "btn btn-secondary", onclick: move |_| offset.set(0), "This week" }
+                    }
+                }
+                button { class: "btn btn-ghost p-2", title: "Next week", onclick: move |_| offset += 1, "›" }
+            }
+
+            div {
+                class: "week-grid",
+                div { class: "week-corner" }
+                for day in days.iter().copied() {
+                    div {
+                        class: if day == today { "week-day-header today" } else { "week-day-header" },
+                        div { class: "text-xs text-gray-500", "{day.format(\"%a\")}" }
+                        div { class: "font-bold", "{day.format(\"%-d\")}" }
+                    }
+                }
+                for hour in 0..24u8 {
+                    div { class: "week-hour", "{hour:02}:00" }
+                    for day in days.iter().copied() {
+                        div {
+                            class: match (day == today, drag.is_over(&(day, hour))) {
+                                (_, true) => "week-slot drop-target",
+                                (true, false) => "week-slot today",
+                                (false, false) => "week-slot",
+                            },
+                            onclick: move |_| {
+                                if let Some(at) = day.and_hms_opt(hour.into(), 0, 0) {
+                                    on_select_date.call(Datetime::from_naive(&at));
+                                }
+                            },
+                            ondragover: move |evt| {
+                                evt.prevent_default();
+                                drag.enter((day, hour));
+                            },
+                            ondrop: move |evt| {
+                                evt.prevent_default();
+                                if let Some(moved) = drag.drop_on(day, Some(hour)) {
+                                    on_reschedule.call(moved);
+                                }
+                            },
+                            for (time, d) in slot(day, hour) {
+                                div {
+                                    key: "{d.id}-{d.occurrence}",
+                                    class: if d.status.is_closed() { "week-item closed" } else { "week-item" },
+                                    title: d.original_due_text(),
+                                    tabindex: "0",
+                                    draggable: if d.is_projected() { "false" } else { "true" },
+                                    ondragstart: {
+                                        let d = d.clone();
+                                        move |_| drag.start(&d)
+                                    },
+                                    ondragend: move |_| drag.end(),
+                                    onmounted: {
+                                        let id = d.id.clone();
+                                        move |evt| drag.refocus(evt, &id)
+                                    },
+                                    onkeydown: {
+                                        let d = d.clone();
+                                        move |evt: KeyboardEvent| {
+                                            if evt.key() == Key::Enter {
+                                                on_edit_deadline.call(d.clone());
+                                            } else if let Some(moved) = drag.nudge(&evt, &d, 60) {
+                                                on_reschedule.call(moved);
+                                            }
+                                        }
+                                    },
+                                    onclick: move |evt| {
+                                        evt.stop_propagation();
+                                        on_edit_deadline.call(d.clone());
+                                    },
+                                    span { class: "font-bold", "{time}" }
+                                    if d.recurrence.is_some() { " ↻ {d.name}" } else { " {d.name}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::config::WeekStart;
+use crate::model::workload::{daily_load, heat_level, overloads, WorkloadSettings};
+use crate::model::Deadline;
+use crate::views::Now;
+use chrono::Duration;
+use dioxus::prelude::*;
+
+/// Weeks shown in the heatmap, starting with the current one.
+const HEATMAP_WEEKS: i64 = 4;
+
+/// Heatmap of the work due each day over the coming weeks, then the days and weeks over the
+/// daily capacity with the deadlines to start earlier. Clicking a day or a suggestion opens it.
+#[component]
+pub fn WorkloadPanel(deadlines: Vec<Deadline>, on_edit_deadline: EventHandler<Deadline>) -> Element {
+    let week_start = use_context::<Signal<WeekStart>>();
+    let workload = use_context::<Signal<WorkloadSettings>>();
+    let clock = use_context::<Signal<Now>>();
+    let today = clock().0.to_naive().map(|n| n.date()).unwrap_or_default();
+
+    let capacity = workload().daily_capacity();
+    let from = week_start().week_of(today);
+    let loads = daily_load(&deadlines, from, from + Duration::weeks(HEATMAP_WEEKS) - Duration::days(1));
+    let found = overloads(&loads, capacity, today);
+    // (day, shade, hours, first deadline due that day)
+    let cells: Vec<_> = loads
+        .iter()
+        .map(|day| (day.date, heat_level(day.hours, capacity), day.hours, day.deadlines.first().cloned()))
+        .collect();
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2",
+            div {
+                class: "flex justify-between items-center",
+                span { class: "text-sm font-medium", "Workload" }
+                span { class: "text-xs text-gray-500", "{capacity} h a day" }
+            }
+            div {
+                class: "heatmap",
+                for day in week_start().days() {
+                    div { class: "text-xs text-gray-500 text-center", "{day}" }
+                }
+                for (date, level, hours, first) in cells {
+                    div {
+                        key: "{date}",
+                        class: if date == today { "heat-cell today heat-{level}" } else { "heat-cell heat-{level}" },
+                        title: "{date.format(\"%a, %b %-d\")}: {hours:.1} h due",
+                        onclick: move |_| {
+                            if let Some(d) = &first {
+                                on_edit_deadline.call(d.clone());
+                            }
+                        },
+                    }
+                }
+            }
+            if found.is_empty() {
+                p { class: "text-xs text-gray-500", "Everything fits into {capacity} hours a day." }
+            }
+            for o in found {
+                div {
+                    key: "{o.from}-{o.days}",
+                    class: "alert alert-warning text-sm flex flex-col gap-1",
+                    span {
+                        class: "font-medium",
+                        if o.is_week() { "Week of {o.from.format(\"%b %-d\")}" } else { "{o.from.format(\"%a, %b %-d\")}" }
+                        ": {o.hours:.1} h due, {o.capacity} h fits"
+                    }
+                    for (d, start) in o.start_earlier {
+                        button {
+                            class: "workload-suggestion",
+                            onclick: move |_| on_edit_deadline.call(d.clone()),
+                            "Start '{d.name}' by {start.format(\"%a, %b %-d\")}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
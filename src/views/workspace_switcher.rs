@@ -0,0 +1,81 @@
+use crate::persistence::workspace::{create_workspace, list_workspaces};
+use crate::{OpenWorkspace, Startup};
+use dioxus::prelude::*;
+
+// Option values that cannot collide with workspace names, which never contain '*'.
+const ALL_OPTION: &str = "*all";
+const NEW_OPTION: &str = "*new";
+
+/// Picks the open workspace, the merged "All workspaces" view, or creates a new workspace.
+#[component]
+pub fn WorkspaceSwitcher() -> Element {
+    let startup = use_context::<Startup>();
+    let mut open = use_context::<Signal<OpenWorkspace>>();
+    let mut creating = use_signal(|| false);
+    let mut new_name = use_signal(String::new);
+    let mut error = use_signal(|| Option::<String>::None);
+
+    let names = list_workspaces(&startup.data_dir);
+    let current = open();
+
+    let create = move |_: MouseEvent| match create_workspace(&startup.data_dir, &new_name()) {
+        Ok(name) => {
+            creating.set(false);
+            new_name.set(String::new());
+            error.set(None);
+            open.set(OpenWorkspace { name, merged: false });
+        }
+        Err(e) => error.set(Some(e.to_string())),
+    };
+
+    rsx! {
+        div {
+            class: "flex items-center gap-2",
+            if creating() {
+                input {
+                    class: "form-input",
+                    placeholder: "Workspace name",
+                    value: "{new_name}",
+                    oninput: move |e| new_name.set(e.value()),
+                    onkeydown: move |e: KeyboardEvent| {
+                        if e.key() == Key::Escape {
+                            creating.set(false);
+                            error.set(None);
+                        }
+                    },
+                }
+                button { class: "btn btn-primary", onclick: create, "Create" }
+                button {
+                    class: "btn btn-ghost",
+                    onclick: move |_| {
+                        creating.set(false);
+                        error.set(None);
+                    },
+                    "Cancel"
+                }
+            } else {
+                select {
+                    class: "form-input",
+                    title: "Workspace",
+                    onchange: move |e| match e.value().as_str() {
+                        ALL_OPTION => open.set(OpenWorkspace { name: open().name, merged: true }),
+                        NEW_OPTION => creating.set(true),
+                        name => open.set(OpenWorkspace { name: name.to_string(), merged: false }),
+                    },
+                    for name in names {
+                        option {
+                            value: "{name}",
+                            selected: !current.merged && name == current.name,
+                            "{name}"
+                        }
+                    }
+                    option { value: ALL_OPTION, selected: current.merged, "All workspaces" }
+                    option { value: NEW_OPTION, selected: false, "New workspace…" }
+                }
+            }
+            if let Some(msg) = error() {
+                span { class: "text-sm", style: "color: var(--red-800);", "{msg}" }
+            }
+        }
+    }
+}